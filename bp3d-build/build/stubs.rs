@@ -0,0 +1,530 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Generates Lua Language Server annotation stubs from the Lua bindings declared in src/lua.
+//!
+//! Bindings which cannot be matched to their declaration fail the build, so that the stubs never
+//! silently fall behind the Rust side.
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+use syn::parse::{ParseStream, Parser};
+use syn::visit::Visit;
+use syn::{
+    Attribute, Expr, ExprMethodCall, ExprTuple, FnArg, GenericArgument, Ident, ImplItem, Item,
+    ItemFn, ItemImpl, Lit, Pat, PathArguments, ReturnType, Signature, Token, Type,
+};
+
+const SOURCE_DIR: &str = "src/lua";
+
+/// A Lua library, declared through an implementation of `Lib`.
+struct Lib {
+    namespace: String,
+    /// Functions as (lua name, rust name).
+    functions: Vec<(String, String)>,
+    /// Userdata types as (lua name, rust name).
+    userdata: Vec<(String, String)>,
+}
+
+/// A userdata type, declared through `impl_userdata!`.
+struct Class {
+    name: String,
+    methods: Vec<(Vec<Attribute>, Signature)>,
+    statics: Vec<String>,
+}
+
+#[derive(Default)]
+struct Source {
+    functions: HashMap<String, ItemFn>,
+    classes: Vec<Class>,
+    libs: Vec<Lib>,
+}
+
+#[derive(Default)]
+struct Types {
+    /// Enums deriving LuaType, with their variants.
+    enums: HashMap<String, Vec<String>>,
+    /// Userdata types as (lua class name, rust name), a type may be registered more than once.
+    classes: Vec<(String, String)>,
+}
+
+impl Types {
+    /// Returns the Lua class name of a userdata type, the first registration wins.
+    fn class(&self, rust: &str) -> Option<&str> {
+        self.classes
+            .iter()
+            .find(|(_, v)| v == rust)
+            .map(|(k, _)| k.as_str())
+    }
+}
+
+fn to_camel_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = !out.is_empty();
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn derives_lua_type(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|v| {
+        v.path().is_ident("derive")
+            && v.meta
+                .require_list()
+                .is_ok_and(|v| v.tokens.to_string().contains("LuaType"))
+    })
+}
+
+fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|v| match &v.meta {
+            syn::Meta::NameValue(v) if v.path.is_ident("doc") => match &v.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn string_literal(expr: &Expr) -> Option<String> {
+    let Expr::Lit(lit) = expr else {
+        return None;
+    };
+    match &lit.lit {
+        Lit::Str(v) => Some(v.value()),
+        Lit::CStr(v) => v.value().into_string().ok(),
+        _ => None,
+    }
+}
+
+fn last_ident(path: &syn::Path) -> Option<String> {
+    path.segments.last().map(|v| v.ident.to_string())
+}
+
+/// Finds the `("name", RFunction::wrap(func))` and `add_userdata::<Type>("Name", ...)`
+/// registrations in the body of `Lib::load`.
+struct LibVisitor<'a>(&'a mut Lib);
+
+impl<'ast> Visit<'ast> for LibVisitor<'_> {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        if call.method == "add_userdata" {
+            let ty = call
+                .turbofish
+                .as_ref()
+                .and_then(|v| v.args.first())
+                .and_then(|v| match v {
+                    GenericArgument::Type(Type::Path(v)) => last_ident(&v.path),
+                    _ => None,
+                });
+            let name = call.args.first().and_then(string_literal);
+            let (Some(ty), Some(name)) = (ty, name) else {
+                panic!(
+                    "unsupported userdata registration in {}: {}",
+                    self.0.namespace,
+                    call.to_token_stream()
+                );
+            };
+            self.0.userdata.push((name, ty));
+        }
+        syn::visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_tuple(&mut self, tuple: &'ast ExprTuple) {
+        let elems: Vec<&Expr> = tuple.elems.iter().collect();
+        let [name, value] = elems[..] else {
+            return syn::visit::visit_expr_tuple(self, tuple);
+        };
+        let Some(name) = string_literal(name) else {
+            return syn::visit::visit_expr_tuple(self, tuple);
+        };
+        let func = match value {
+            Expr::Call(call) => match (&*call.func, call.args.first()) {
+                (Expr::Path(func), Some(Expr::Path(arg)))
+                    if last_ident(&func.path).as_deref() == Some("wrap") =>
+                {
+                    last_ident(&arg.path)
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(func) = func else {
+            panic!(
+                "unsupported binding {} in {}, expected RFunction::wrap(function)",
+                name, self.0.namespace
+            );
+        };
+        self.0.functions.push((name, func));
+    }
+}
+
+/// Parses a `[fn name]` entry of the static block of `impl_userdata!`.
+fn parse_static(input: ParseStream) -> syn::Result<Ident> {
+    input.parse::<Token![fn]>()?;
+    input.parse()
+}
+
+fn parse_impl_userdata(path: &Path, stream: TokenStream) -> Class {
+    let tokens: Vec<TokenTree> = stream.into_iter().collect();
+    let split = tokens
+        .iter()
+        .position(|v| matches!(v, TokenTree::Ident(v) if v == "static"))
+        .unwrap_or(tokens.len());
+    let item: ItemImpl = syn::parse2(tokens[..split].iter().cloned().collect())
+        .unwrap_or_else(|e| panic!("invalid impl_userdata! in {:?}: {}", path, e));
+    let name = match &*item.self_ty {
+        Type::Path(ty) => last_ident(&ty.path),
+        _ => None,
+    };
+    let name = name.unwrap_or_else(|| panic!("unsupported impl_userdata! type in {:?}", path));
+    let methods = item
+        .items
+        .into_iter()
+        .filter_map(|v| match v {
+            ImplItem::Fn(v) => Some((v.attrs, v.sig)),
+            _ => None,
+        })
+        .collect();
+    let mut statics = Vec::new();
+    if let Some(TokenTree::Group(group)) = tokens.get(split + 1) {
+        for token in group.stream() {
+            if let TokenTree::Group(v) = token {
+                let func = parse_static
+                    .parse2(v.stream())
+                    .unwrap_or_else(|e| panic!("invalid static of {} in {:?}: {}", name, path, e));
+                statics.push(func.to_string());
+            }
+        }
+    }
+    Class {
+        name,
+        methods,
+        statics,
+    }
+}
+
+fn parse_file(path: &Path, types: &mut Types) -> Source {
+    let content = std::fs::read_to_string(path).expect("failed to read lua binding source");
+    let file = syn::parse_file(&content).expect("failed to parse lua binding source");
+    let mut source = Source::default();
+    for item in file.items {
+        match item {
+            Item::Macro(v) => {
+                let name = v.mac.path.segments.last().map(|v| v.ident.to_string());
+                match name.as_deref() {
+                    Some("decl_lib_func") => {
+                        let func = syn::parse2::<ItemFn>(v.mac.tokens).unwrap_or_else(|e| {
+                            panic!("invalid decl_lib_func! in {:?}: {}", path, e)
+                        });
+                        source.functions.insert(func.sig.ident.to_string(), func);
+                    }
+                    Some("impl_userdata") => {
+                        source.classes.push(parse_impl_userdata(path, v.mac.tokens))
+                    }
+                    _ => (),
+                }
+            }
+            Item::Enum(v) if derives_lua_type(&v.attrs) => {
+                let variants = v
+                    .variants
+                    .iter()
+                    .map(|v| v.ident.to_string().to_lowercase());
+                types.enums.insert(v.ident.to_string(), variants.collect());
+            }
+            Item::Impl(v) => {
+                let is_lib = v
+                    .trait_
+                    .as_ref()
+                    .and_then(|(_, path, _)| path.segments.last())
+                    .is_some_and(|v| v.ident == "Lib");
+                if !is_lib {
+                    continue;
+                }
+                let mut lib = Lib {
+                    namespace: String::new(),
+                    functions: Vec::new(),
+                    userdata: Vec::new(),
+                };
+                for item in v.items {
+                    match item {
+                        ImplItem::Const(v) if v.ident == "NAMESPACE" => {
+                            if let syn::Expr::Lit(syn::ExprLit {
+                                lit: Lit::Str(s), ..
+                            }) = v.expr
+                            {
+                                lib.namespace = s.value();
+                            }
+                        }
+                        ImplItem::Fn(v) if v.sig.ident == "load" => {
+                            LibVisitor(&mut lib).visit_block(&v.block)
+                        }
+                        _ => (),
+                    }
+                }
+                for (name, ty) in &lib.userdata {
+                    let class = format!("{}.{}", lib.namespace, name);
+                    if !types.classes.iter().any(|(v, _)| *v == class) {
+                        types.classes.push((class, ty.clone()));
+                    }
+                }
+                source.libs.push(lib);
+            }
+            _ => (),
+        }
+    }
+    source
+}
+
+fn generic_args(args: &PathArguments) -> Vec<&Type> {
+    match args {
+        PathArguments::AngleBracketed(v) => v
+            .args
+            .iter()
+            .filter_map(|v| match v {
+                GenericArgument::Type(v) => Some(v),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn lua_type(ty: &Type, types: &Types) -> String {
+    match ty {
+        Type::Reference(v) => lua_type(&v.elem, types),
+        // Byte slices are Lua strings which may contain binary data.
+        Type::Slice(v) if v.elem.to_token_stream().to_string() == "u8" => "string".into(),
+        Type::Tuple(v) if v.elems.is_empty() => "nil".into(),
+        Type::Path(v) => {
+            let Some(segment) = v.path.segments.last() else {
+                return "any".into();
+            };
+            let args = generic_args(&segment.arguments);
+            let arg = |i: usize| {
+                args.get(i)
+                    .map(|v| lua_type(v, types))
+                    .unwrap_or_else(|| "any".into())
+            };
+            let name = segment.ident.to_string();
+            match name.as_str() {
+                "str" | "String" | "OsStr" | "OsString" | "CStr" => "string".into(),
+                "bool" => "boolean".into(),
+                "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize" => {
+                    "integer".into()
+                }
+                "f32" | "f64" => "number".into(),
+                "Option" => format!("{}?", arg(0)),
+                "Result" => arg(0),
+                "Vec" => format!("{}[]", arg(0)),
+                "HashMap" | "BTreeMap" => format!("table<{}, {}>", arg(0), arg(1)),
+                "SandboxPath" | "SandboxPathBuf" => "Path".into(),
+                "Table" => "table".into(),
+                "Function" | "RFunction" => "function".into(),
+                "Thread" => "thread".into(),
+                _ => {
+                    if let Some(variants) = types.enums.get(&name) {
+                        let variants: Vec<String> =
+                            variants.iter().map(|v| format!("\"{}\"", v)).collect();
+                        variants.join("|")
+                    } else if let Some(class) = types.class(&name) {
+                        class.into()
+                    } else {
+                        "any".into()
+                    }
+                }
+            }
+        }
+        _ => "any".into(),
+    }
+}
+
+fn return_types(ty: &Type, types: &Types) -> Vec<String> {
+    match ty {
+        Type::Tuple(v) => v.elems.iter().map(|v| lua_type(v, types)).collect(),
+        Type::Path(v) => match v.path.segments.last() {
+            Some(segment) if segment.ident == "Result" => generic_args(&segment.arguments)
+                .first()
+                .map(|v| return_types(v, types))
+                .unwrap_or_default(),
+            _ => vec![lua_type(ty, types)],
+        },
+        _ => vec![lua_type(ty, types)],
+    }
+}
+
+fn is_vm(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(v) => is_vm(&v.elem),
+        Type::Path(v) => v.path.segments.last().is_some_and(|v| v.ident == "Vm"),
+        _ => false,
+    }
+}
+
+/// Writes the annotations and the declaration of a function.
+fn write_function(
+    out: &mut String,
+    attrs: &[Attribute],
+    sig: &Signature,
+    types: &Types,
+    decl: &str,
+    method: bool,
+) {
+    for line in doc_lines(attrs) {
+        let _ = writeln!(out, "--- {}", line);
+    }
+    let mut names = Vec::new();
+    for (i, arg) in sig.inputs.iter().enumerate() {
+        let FnArg::Typed(arg) = arg else { continue };
+        if is_vm(&arg.ty) || (method && i == 0) {
+            continue;
+        }
+        let name = match &*arg.pat {
+            Pat::Ident(v) => to_camel_case(&v.ident.to_string()),
+            _ => format!("arg{}", i),
+        };
+        let ty = lua_type(&arg.ty, types);
+        match ty.strip_suffix('?') {
+            Some(ty) => {
+                let _ = writeln!(out, "---@param {}? {}", name, ty);
+            }
+            None => {
+                let _ = writeln!(out, "---@param {} {}", name, ty);
+            }
+        }
+        names.push(name);
+    }
+    if let ReturnType::Type(_, ty) = &sig.output {
+        for ty in return_types(ty, types).into_iter().filter(|v| v != "nil") {
+            let _ = writeln!(out, "---@return {}", ty);
+        }
+    }
+    let _ = writeln!(out, "function {}({}) end\n", decl, names.join(", "));
+}
+
+fn write_namespace(out: &mut String, namespace: &str, declared: &mut Vec<String>) {
+    let mut path = String::new();
+    for part in namespace.split('.') {
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(part);
+        if !declared.contains(&path) {
+            let _ = writeln!(out, "{} = {{}}\n", path);
+            declared.push(path.clone());
+        }
+    }
+}
+
+/// Generates the stubs of all Lua bindings into `OUT_DIR/bp3d.build.lua`.
+pub fn generate() {
+    println!("cargo:rerun-if-changed={}", SOURCE_DIR);
+    let mut files: Vec<_> = std::fs::read_dir(SOURCE_DIR)
+        .expect("failed to list lua binding sources")
+        .filter_map(|v| v.ok().map(|v| v.path()))
+        .filter(|v| v.extension().is_some_and(|v| v == "rs"))
+        .collect();
+    files.sort();
+    let mut types = Types::default();
+    let sources: Vec<Source> = files.iter().map(|v| parse_file(v, &mut types)).collect();
+    let mut out =
+        String::from("---@meta\n-- Generated from the bp3d-build Lua bindings, do not edit.\n\n");
+    let mut declared = Vec::new();
+    for source in &sources {
+        for lib in &source.libs {
+            write_namespace(&mut out, &lib.namespace, &mut declared);
+            for (name, func) in &lib.functions {
+                let decl = format!("{}.{}", lib.namespace, name);
+                let Some(func) = source.functions.get(func) else {
+                    panic!(
+                        "binding {} refers to {} which is not a decl_lib_func!",
+                        decl, func
+                    );
+                };
+                // Test mode libraries replace existing functions with the same signature.
+                if declared.contains(&decl) {
+                    continue;
+                }
+                write_function(&mut out, &func.attrs, &func.sig, &types, &decl, false);
+                declared.push(decl);
+            }
+            for (name, ty) in &lib.userdata {
+                // Userdata may be declared in another file than the library registering it.
+                let found = sources
+                    .iter()
+                    .find_map(|v| v.classes.iter().find(|c| &c.name == ty).map(|c| (v, c)));
+                let qualified = format!("{}.{}", lib.namespace, name);
+                let Some((source, class)) = found else {
+                    panic!(
+                        "userdata {} refers to {} which has no impl_userdata!",
+                        qualified, ty
+                    );
+                };
+                if declared.contains(&qualified) {
+                    continue;
+                }
+                declared.push(qualified.clone());
+                let _ = writeln!(out, "---@class {}\n{} = {{}}\n", qualified, qualified);
+                for (attrs, sig) in &class.methods {
+                    let rust = sig.ident.to_string();
+                    if rust.starts_with("__") {
+                        continue;
+                    }
+                    let decl = format!("{}:{}", qualified, to_camel_case(&rust));
+                    write_function(&mut out, attrs, sig, &types, &decl, true);
+                }
+                for rust in &class.statics {
+                    let decl = format!("{}.{}", qualified, to_camel_case(rust));
+                    let Some(func) = source.functions.get(rust) else {
+                        panic!(
+                            "static {} refers to {} which is not a decl_lib_func!",
+                            decl, rust
+                        );
+                    };
+                    write_function(&mut out, &func.attrs, &func.sig, &types, &decl, false);
+                }
+            }
+        }
+    }
+    let dest = Path::new(&std::env::var("OUT_DIR").unwrap()).join("bp3d.build.lua");
+    std::fs::write(dest, out).expect("failed to write lua stubs");
+}
@@ -0,0 +1,604 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Deterministic tar and zip archives.
+//!
+//! Entries are always written in lexicographic order and owned by root. Modification times and
+//! permissions are normalized according to [Options] so that archiving the same tree twice
+//! produces identical bytes.
+
+use bp3d_util::simple_error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+simple_error! {
+    pub Error {
+        Io(std::io::Error) => "io error: {}",
+        Zip(zip::result::ZipError) => "zip error: {}",
+        UnknownFormat(String) => "unknown archive format: {}",
+        InvalidLevel(u32) => "invalid compression level for this format: {}",
+        UnsafePath(String) => "refusing to extract entry outside of destination: {}"
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    Tar,
+    TarGz,
+    TarXz,
+    TarZst,
+    Zip,
+}
+
+impl Format {
+    /// Returns the format with the given name, for example `tar.gz`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "tar" => Some(Format::Tar),
+            "tar.gz" | "tgz" => Some(Format::TarGz),
+            "tar.xz" | "txz" => Some(Format::TarXz),
+            "tar.zst" | "tzst" => Some(Format::TarZst),
+            "zip" => Some(Format::Zip),
+            _ => None,
+        }
+    }
+
+    /// Infers the format from the extension of an archive file name.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        let (_, ext) = name.split_once('.')?;
+        // Handle names such as foo-1.0.0.tar.gz by retrying with everything after each dot.
+        let mut ext = ext;
+        loop {
+            if let Some(format) = Self::from_name(ext) {
+                return Some(format);
+            }
+            ext = ext.split_once('.')?.1;
+        }
+    }
+
+    /// Returns the highest compression level of this format, none when it is not compressed.
+    pub fn max_level(&self) -> Option<u32> {
+        match self {
+            Format::Tar => None,
+            Format::TarGz | Format::TarXz | Format::Zip => Some(9),
+            Format::TarZst => Some(22),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Tar => "tar",
+            Format::TarGz => "tar.gz",
+            Format::TarXz => "tar.xz",
+            Format::TarZst => "tar.zst",
+            Format::Zip => "zip",
+        }
+    }
+}
+
+/// Options for [create].
+pub struct Options {
+    pub format: Format,
+    /// A directory prepended to all entry names.
+    pub prefix: Option<String>,
+    /// The modification time of all entries in seconds since the UNIX epoch.
+    ///
+    /// When none, SOURCE_DATE_EPOCH is used if set, otherwise the time of each file is kept.
+    pub mtime: Option<u64>,
+    /// The permissions of all files, by default 755 for executables and 644 for other files.
+    pub file_mode: Option<u32>,
+    /// The permissions of all directories, by default 755.
+    pub dir_mode: Option<u32>,
+    /// The compression level, the default depends on the format.
+    pub level: Option<u32>,
+}
+
+impl Options {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            prefix: None,
+            mtime: None,
+            file_mode: None,
+            dir_mode: None,
+            level: None,
+        }
+    }
+}
+
+/// Options for [extract].
+pub struct ExtractOptions {
+    /// The number of leading path components to remove from entry names.
+    pub strip: usize,
+    /// Restore the permissions stored in the archive.
+    pub preserve_modes: bool,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            strip: 0,
+            preserve_modes: true,
+        }
+    }
+}
+
+/// An entry of an archive, see [list].
+pub struct Entry {
+    pub name: String,
+    pub size: u64,
+    pub mode: u32,
+    pub is_dir: bool,
+}
+
+struct Source {
+    path: PathBuf,
+    name: String,
+    is_dir: bool,
+    /// The target of a symbolic link, links are stored as links and never followed.
+    link: Option<PathBuf>,
+    size: u64,
+    mode: u32,
+    mtime: u64,
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn file_mode(metadata: &std::fs::Metadata) -> u32 {
+    match metadata.is_dir() {
+        true => 0o755,
+        false => 0o644,
+    }
+}
+
+fn source_date_epoch() -> Option<u64> {
+    std::env::var("SOURCE_DATE_EPOCH").ok()?.parse().ok()
+}
+
+fn collect(
+    root: &Path,
+    prefix: &str,
+    options: &Options,
+    out: &mut Vec<Source>,
+) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(root)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|v| v.file_name());
+    let mtime = options.mtime.or_else(source_date_epoch);
+    for entry in entries {
+        let metadata = std::fs::symlink_metadata(entry.path())?;
+        let link = match metadata.is_symlink() {
+            true => Some(std::fs::read_link(entry.path())?),
+            false => None,
+        };
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        let mode = match (metadata.is_dir(), link.is_some()) {
+            (true, _) => options.dir_mode.unwrap_or(0o755),
+            (_, true) => 0o777,
+            _ => options
+                .file_mode
+                .unwrap_or(match file_mode(&metadata) & 0o111 {
+                    0 => 0o644,
+                    _ => 0o755,
+                }),
+        };
+        let mtime = mtime.unwrap_or_else(|| {
+            metadata
+                .modified()
+                .ok()
+                .and_then(|v| v.duration_since(UNIX_EPOCH).ok())
+                .map(|v| v.as_secs())
+                .unwrap_or_default()
+        });
+        out.push(Source {
+            path: entry.path(),
+            name: name.clone(),
+            is_dir: metadata.is_dir(),
+            size: match link.is_some() {
+                true => 0,
+                false => metadata.len(),
+            },
+            link,
+            mode,
+            mtime,
+        });
+        if metadata.is_dir() {
+            collect(&entry.path(), &format!("{}/", name), options, out)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_tar<W: Write>(out: W, entries: &[Source]) -> std::io::Result<W> {
+    let mut builder = tar::Builder::new(out);
+    for entry in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_mode(entry.mode);
+        header.set_mtime(entry.mtime);
+        header.set_uid(0);
+        header.set_gid(0);
+        if entry.is_dir {
+            header.set_entry_type(tar::EntryType::Directory);
+            header.set_size(0);
+            builder.append_data(&mut header, &entry.name, std::io::empty())?;
+        } else if let Some(link) = &entry.link {
+            header.set_entry_type(tar::EntryType::Symlink);
+            header.set_size(0);
+            builder.append_link(&mut header, &entry.name, link)?;
+        } else {
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_size(entry.size);
+            builder.append_data(&mut header, &entry.name, File::open(&entry.path)?)?;
+        }
+    }
+    builder.into_inner()
+}
+
+fn write_compressed_tar(
+    file: File,
+    format: Format,
+    level: Option<u32>,
+    entries: &[Source],
+) -> std::io::Result<()> {
+    let file = BufWriter::new(file);
+    match format {
+        Format::TarGz => {
+            let level = flate2::Compression::new(level.unwrap_or(6));
+            let out = flate2::write::GzEncoder::new(file, level);
+            write_tar(out, entries)?.finish()?.flush()
+        }
+        Format::TarXz => {
+            let out = xz2::write::XzEncoder::new(file, level.unwrap_or(6));
+            write_tar(out, entries)?.finish()?.flush()
+        }
+        Format::TarZst => {
+            let out = zstd::Encoder::new(file, level.unwrap_or(3) as i32)?;
+            write_tar(out, entries)?.finish()?.flush()
+        }
+        _ => write_tar(file, entries)?.flush(),
+    }
+}
+
+/// Converts a UNIX timestamp to a zip date, zip dates cannot represent times before 1980.
+fn zip_time(secs: u64) -> zip::DateTime {
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+    // Civil from days algorithm by Howard Hinnant.
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    zip::DateTime::from_date_and_time(
+        year.clamp(0, u16::MAX as i64) as u16,
+        month as u8,
+        day as u8,
+        (rem / 3600) as u8,
+        (rem % 3600 / 60) as u8,
+        (rem % 60) as u8,
+    )
+    .unwrap_or_default()
+}
+
+fn write_zip(out: File, entries: &[Source], level: Option<u32>) -> Result<(), Error> {
+    let mut zip = zip::ZipWriter::new(BufWriter::new(out));
+    for entry in entries {
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .compression_level(level.map(|v| v as i64))
+            .last_modified_time(zip_time(entry.mtime))
+            .unix_permissions(entry.mode)
+            .large_file(entry.size >= u32::MAX as u64);
+        if entry.is_dir {
+            zip.add_directory(&*entry.name, options)
+                .map_err(Error::Zip)?;
+        } else if let Some(link) = &entry.link {
+            zip.add_symlink(&*entry.name, link.to_string_lossy(), options)
+                .map_err(Error::Zip)?;
+        } else {
+            zip.start_file(&*entry.name, options).map_err(Error::Zip)?;
+            let mut file = File::open(&entry.path).map_err(Error::Io)?;
+            std::io::copy(&mut file, &mut zip).map_err(Error::Io)?;
+        }
+    }
+    zip.finish().map_err(Error::Zip)?.flush().map_err(Error::Io)
+}
+
+/// Creates an archive from the content of the source directory.
+///
+/// Returns the number of entries written.
+pub fn create(archive: &Path, source: &Path, options: &Options) -> Result<usize, Error> {
+    if let Some(level) = options.level {
+        if options.format.max_level().is_some_and(|v| level > v) {
+            return Err(Error::InvalidLevel(level));
+        }
+    }
+    let prefix = match options.prefix.as_deref().map(|v| v.trim_matches('/')) {
+        Some(v) if !v.is_empty() => format!("{}/", v),
+        _ => String::new(),
+    };
+    let mut entries = Vec::new();
+    collect(source, &prefix, options, &mut entries).map_err(Error::Io)?;
+    let file = File::create(archive).map_err(Error::Io)?;
+    match options.format {
+        Format::Zip => write_zip(file, &entries, options.level)?,
+        format => write_compressed_tar(file, format, options.level, &entries).map_err(Error::Io)?,
+    }
+    Ok(entries.len())
+}
+
+fn tar_reader(file: File, format: Format) -> std::io::Result<Box<dyn Read>> {
+    let file = BufReader::new(file);
+    Ok(match format {
+        Format::TarGz => Box::new(flate2::read::GzDecoder::new(file)),
+        Format::TarXz => Box::new(xz2::read::XzDecoder::new(file)),
+        Format::TarZst => Box::new(zstd::Decoder::with_buffer(file)?),
+        _ => Box::new(file),
+    })
+}
+
+/// Returns true when the path is relative and does not contain any parent component.
+fn is_relative(path: &Path) -> bool {
+    path.components()
+        .all(|v| matches!(v, Component::Normal(_) | Component::CurDir))
+}
+
+/// Returns the path of an entry relative to the extraction directory, or none if the entry is
+/// removed by stripping.
+fn relative_path(path: &Path, strip: usize) -> Result<Option<PathBuf>, Error> {
+    if !is_relative(path) {
+        return Err(Error::UnsafePath(path.to_string_lossy().into()));
+    }
+    let path: PathBuf = path
+        .components()
+        .filter(|v| matches!(v, Component::Normal(_)))
+        .skip(strip)
+        .collect();
+    match path.as_os_str().is_empty() {
+        true => Ok(None),
+        false => Ok(Some(path)),
+    }
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+fn set_mode(_: &Path, _: u32) -> std::io::Result<()> {
+    Ok(())
+}
+
+fn extract_tar(
+    file: File,
+    format: Format,
+    dest: &Path,
+    options: &ExtractOptions,
+) -> Result<usize, Error> {
+    let mut archive = tar::Archive::new(tar_reader(file, format).map_err(Error::Io)?);
+    archive.set_preserve_permissions(options.preserve_modes);
+    archive.set_preserve_mtime(true);
+    let mut count = 0;
+    for entry in archive.entries().map_err(Error::Io)? {
+        let mut entry = entry.map_err(Error::Io)?;
+        let path = entry.path().map_err(Error::Io)?.into_owned();
+        let Some(path) = relative_path(&path, options.strip)? else {
+            continue;
+        };
+        let target = dest.join(path);
+        match entry.header().entry_type() {
+            tar::EntryType::Directory => std::fs::create_dir_all(&target).map_err(Error::Io)?,
+            tar::EntryType::Symlink => {
+                // A link pointing outside of the destination would let later entries escape it.
+                let link = entry.link_name().map_err(Error::Io)?.unwrap_or_default();
+                if link.as_os_str().is_empty() || !is_relative(&link) {
+                    return Err(Error::UnsafePath(link.to_string_lossy().into()));
+                }
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent).map_err(Error::Io)?;
+                }
+                entry.unpack(&target).map_err(Error::Io)?;
+            }
+            tar::EntryType::Regular | tar::EntryType::Continuous => {
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent).map_err(Error::Io)?;
+                }
+                entry.unpack(&target).map_err(Error::Io)?;
+            }
+            // Hard links, devices and other special files are not supported.
+            _ => continue,
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn extract_zip(file: File, dest: &Path, options: &ExtractOptions) -> Result<usize, Error> {
+    let mut archive = zip::ZipArchive::new(BufReader::new(file)).map_err(Error::Zip)?;
+    let mut count = 0;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(Error::Zip)?;
+        let Some(path) = entry.enclosed_name() else {
+            return Err(Error::UnsafePath(entry.name().into()));
+        };
+        let Some(path) = relative_path(&path, options.strip)? else {
+            continue;
+        };
+        let target = dest.join(path);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&target).map_err(Error::Io)?;
+        } else {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(Error::Io)?;
+            }
+            let mut out = File::create(&target).map_err(Error::Io)?;
+            std::io::copy(&mut entry, &mut out).map_err(Error::Io)?;
+        }
+        if let Some(mode) = entry.unix_mode().filter(|_| options.preserve_modes) {
+            set_mode(&target, mode & 0o7777).map_err(Error::Io)?;
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Extracts an archive into the destination directory.
+///
+/// Entries which would be written outside of the destination are rejected. Returns the number of
+/// entries extracted.
+pub fn extract(
+    archive: &Path,
+    dest: &Path,
+    format: Format,
+    options: &ExtractOptions,
+) -> Result<usize, Error> {
+    let file = File::open(archive).map_err(Error::Io)?;
+    std::fs::create_dir_all(dest).map_err(Error::Io)?;
+    match format {
+        Format::Zip => extract_zip(file, dest, options),
+        _ => extract_tar(file, format, dest, options),
+    }
+}
+
+/// Lists the entries of an archive.
+pub fn list(archive: &Path, format: Format) -> Result<Vec<Entry>, Error> {
+    let file = File::open(archive).map_err(Error::Io)?;
+    let mut entries = Vec::new();
+    if format == Format::Zip {
+        let mut archive = zip::ZipArchive::new(BufReader::new(file)).map_err(Error::Zip)?;
+        for i in 0..archive.len() {
+            let entry = archive.by_index(i).map_err(Error::Zip)?;
+            entries.push(Entry {
+                name: entry.name().trim_end_matches('/').into(),
+                size: entry.size(),
+                mode: entry.unix_mode().unwrap_or_default() & 0o7777,
+                is_dir: entry.is_dir(),
+            });
+        }
+    } else {
+        let mut archive = tar::Archive::new(tar_reader(file, format).map_err(Error::Io)?);
+        for entry in archive.entries().map_err(Error::Io)? {
+            let entry = entry.map_err(Error::Io)?;
+            let header = entry.header();
+            entries.push(Entry {
+                name: entry.path().map_err(Error::Io)?.to_string_lossy().into(),
+                size: header.size().map_err(Error::Io)?,
+                mode: header.mode().map_err(Error::Io)?,
+                is_dir: header.entry_type() == tar::EntryType::Directory,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("bp3d-archive-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_link_tar(path: &Path, link: &str) {
+        let mut builder = tar::Builder::new(File::create(path).unwrap());
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        header.set_mode(0o777);
+        builder.append_link(&mut header, "root/link", link).unwrap();
+        builder.into_inner().unwrap().flush().unwrap();
+    }
+
+    #[test]
+    fn relative_paths() {
+        let path = relative_path(Path::new("root/a/b.txt"), 0).unwrap();
+        assert_eq!(path.as_deref(), Some(Path::new("root/a/b.txt")));
+        let path = relative_path(Path::new("./root/a/b.txt"), 1).unwrap();
+        assert_eq!(path.as_deref(), Some(Path::new("a/b.txt")));
+        assert!(relative_path(Path::new("root"), 1).unwrap().is_none());
+        assert!(relative_path(Path::new("root/a"), 3).unwrap().is_none());
+    }
+
+    #[test]
+    fn escaping_paths() {
+        for path in ["../a.txt", "root/../../a.txt", "/etc/passwd"] {
+            assert!(matches!(
+                relative_path(Path::new(path), 0),
+                Err(Error::UnsafePath(_))
+            ));
+        }
+        // Stripping must not hide a parent component.
+        assert!(matches!(
+            relative_path(Path::new("root/../a.txt"), 2),
+            Err(Error::UnsafePath(_))
+        ));
+    }
+
+    #[test]
+    fn escaping_symlinks() {
+        let dir = temp_dir("symlinks");
+        for link in ["../outside", "root/../../outside", "/etc/passwd"] {
+            let archive = dir.join("links.tar");
+            write_link_tar(&archive, link);
+            let res = extract(&archive, &dir.join("out"), Format::Tar, &Default::default());
+            assert!(matches!(res, Err(Error::UnsafePath(_))), "{}", link);
+            assert!(std::fs::symlink_metadata(dir.join("out/root/link")).is_err());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn relative_symlinks() {
+        let dir = temp_dir("relative-symlinks");
+        let archive = dir.join("links.tar");
+        write_link_tar(&archive, "target.txt");
+        let count = extract(&archive, &dir.join("out"), Format::Tar, &Default::default()).unwrap();
+        assert_eq!(count, 1);
+        let link = std::fs::read_link(dir.join("out/root/link")).unwrap();
+        assert_eq!(link, Path::new("target.txt"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
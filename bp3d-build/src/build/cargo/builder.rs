@@ -27,13 +27,15 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use super::Error;
-use crate::system::artifact::{Artifact, LibType, List, Type};
-use crate::system::{BuildSystem, Context, Features};
+use crate::system::artifact::{Artifact, LibType, List, Selection, Type};
+use crate::system::target::Target;
+use crate::system::test_report::TestReport;
+use crate::system::{BuildSystem, CleanScope, Context, Features};
 use std::process::Command;
 
 pub struct CargoBuilder;
 
-fn gen_base_command(cmd: &mut Command, ctx: &Context, target: &str) {
+pub(super) fn gen_base_command(cmd: &mut Command, ctx: &Context, target: &str) {
     cmd.arg("--target").arg(target).current_dir(ctx.path);
     if ctx.configuration == "release" {
         cmd.arg("--release");
@@ -48,15 +50,31 @@ fn gen_base_command(cmd: &mut Command, ctx: &Context, target: &str) {
     }
 }
 
+fn run_clean(ctx: &Context) -> Result<(), Error> {
+    let status = Command::new("cargo")
+        .arg("clean")
+        .current_dir(ctx.path)
+        .status()
+        .map_err(Error::Io)?;
+    if !status.success() {
+        return Err(Error::CleanFailed(status.code()));
+    }
+    Ok(())
+}
+
 impl BuildSystem for CargoBuilder {
     type Error = Error;
     type Package = super::CargoWorkspace;
 
+    fn name(&self, _: &Self::Package) -> &str {
+        "Cargo"
+    }
+
     fn configure(&self, _: &Self::Package, _: &Context, _: &[&str]) -> Result<(), Self::Error> {
         Ok(())
     }
 
-    fn build(&self, _: &Self::Package, ctx: &Context, _: &str) -> Result<(), Self::Error> {
+    fn build(&self, _: &Self::Package, ctx: &Context, _: &Target) -> Result<(), Self::Error> {
         let mut cmd = Command::new("cargo");
         cmd.arg("build").current_dir(ctx.path);
         if ctx.configuration == "release" {
@@ -78,14 +96,18 @@ impl BuildSystem for CargoBuilder {
         &self,
         package: &Self::Package,
         ctx: &Context,
-        target: &str,
+        target: &Target,
     ) -> Result<List, Self::Error> {
         let mut cmd = Command::new("cargo");
         cmd.arg("build");
-        gen_base_command(&mut cmd, ctx, target);
+        gen_base_command(&mut cmd, ctx, target.name());
         cmd.status().map_err(Error::Io)?;
         let mut artifacts = List::new();
-        let target_folder = ctx.path.join("target").join(target).join(ctx.configuration);
+        let target_folder = ctx
+            .path
+            .join("target")
+            .join(target.name())
+            .join(ctx.configuration);
         let remove_debug_info = ctx.configuration == "release";
         for lib in package.libs() {
             let dy = Artifact::find_lib(&target_folder, lib, LibType::Dynamic, remove_debug_info);
@@ -101,11 +123,41 @@ impl BuildSystem for CargoBuilder {
             .add_folder(Type::Header, &ctx.path.join("include"), "")
             .map_err(Error::Io)?;
         artifacts
-            .add_folder_exclude(Type::Resource, &ctx.path.join("res"), "config", "")
+            .add_glob(
+                Type::Resource,
+                &ctx.path.join("res"),
+                &Selection::new().pattern("!config").hidden(true),
+                "",
+            )
             .map_err(Error::Io)?;
         artifacts
             .add_folder(Type::Config, &ctx.path.join("res").join("config"), "")
             .map_err(Error::Io)?;
         Ok(artifacts)
     }
+
+    fn test(
+        &self,
+        _: &Self::Package,
+        ctx: &Context,
+        target: &Target,
+    ) -> Result<TestReport, Self::Error> {
+        super::test::run(ctx, target.name())
+    }
+
+    fn clean(
+        &self,
+        _: &Self::Package,
+        ctx: &Context,
+        _: &[&str],
+        scope: CleanScope,
+    ) -> Result<(), Self::Error> {
+        // Cargo places the outputs of each target under target/<triple>, which are removed
+        // together with the packaging outputs; without -p, cargo clean ignores --target and
+        // would remove every build.
+        match scope {
+            CleanScope::All => run_clean(ctx),
+            _ => Ok(()),
+        }
+    }
 }
@@ -30,13 +30,15 @@ use bp3d_util::simple_error;
 
 mod builder;
 mod package;
+mod test;
 
 simple_error! {
     pub Error {
         Cargo(cargo_toml::Error) => "cargo manifest error: {}",
         Io(std::io::Error) => "io error: {}",
         InvalidUtf8 => "invalid utf-8",
-        Config(crate::config::Error) => "config error: {}"
+        TestFailed => "cargo test failed to run",
+        CleanFailed(Option<i32>) => "cargo clean failed with exit code {:?}"
     }
 }
 
@@ -27,7 +27,6 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use super::Error;
-use crate::config::parse_config;
 use crate::system::{static_string, Component, Package};
 use cargo_toml::{Manifest, Publish};
 use std::borrow::Cow;
@@ -54,7 +53,10 @@ pub struct CargoWorkspace {
 }
 
 impl CargoWorkspace {
-    pub fn load(root: &Path) -> Result<CargoWorkspace, Error> {
+    pub fn load(
+        root: &Path,
+        config: Option<&bp3d_config::Package>,
+    ) -> Result<CargoWorkspace, Error> {
         let manifest = Manifest::from_path(root.join("Cargo.toml")).map_err(Error::Cargo)?;
         let mut packages = Vec::new();
         let mut core_name: Option<String> = None;
@@ -96,12 +98,9 @@ impl CargoWorkspace {
             .map(|v| v.features().iter().map(|v| String::from(&**v).into()))
             .flatten()
             .collect();
-        let config = parse_config(root).map_err(Error::Config)?;
-        if let Some(config) = config {
-            if let Some(package) = config.package {
-                core_name = Some(package.name);
-                core_version = Some(package.version);
-            }
+        if let Some(package) = config {
+            core_name = Some(package.name.clone());
+            core_version = Some(package.version.clone());
         }
         Ok(CargoWorkspace {
             packages,
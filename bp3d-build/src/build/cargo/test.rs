@@ -0,0 +1,353 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use super::Error;
+use crate::system::test_report::{TestCase, TestReport, TestStatus, TestSuite};
+use crate::system::Context;
+use serde::Deserialize;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::Command;
+
+/// A cargo JSON message, only compiler messages are used.
+#[derive(Deserialize)]
+struct Message {
+    reason: String,
+    message: Option<Diagnostic>,
+}
+
+#[derive(Deserialize)]
+struct Diagnostic {
+    rendered: Option<String>,
+}
+
+/// Returns the name of a test binary from a cargo `Running` line.
+fn suite_name(line: &str) -> String {
+    // Format is "<description> (<path to test binary>)".
+    let binary = line
+        .rsplit_once(" (")
+        .and_then(|(_, v)| v.strip_suffix(')'))
+        .and_then(|v| Path::new(v).file_stem())
+        .and_then(|v| v.to_str());
+    match binary {
+        Some(v) => v.rsplit_once('-').map(|(v, _)| v).unwrap_or(v).into(),
+        None => line.into(),
+    }
+}
+
+/// Parses a libtest `test <name> ... <status>` line.
+fn parse_case(line: &str) -> Option<TestCase> {
+    let (name, status) = line.strip_prefix("test ")?.rsplit_once(" ... ")?;
+    let status = match status {
+        "ok" => TestStatus::Passed,
+        "FAILED" => TestStatus::Failed,
+        v if v.starts_with("ignored") => TestStatus::Ignored,
+        _ => return None,
+    };
+    Some(TestCase {
+        name: name.into(),
+        status,
+        time: None,
+        message: None,
+    })
+}
+
+/// Builds a test report from the combined output of cargo and libtest.
+///
+/// cargo status lines and libtest text output are read from the same stream, so that each
+/// `running N tests` line can be attributed to the preceding `Running` or `Doc-tests` line.
+/// Test binaries with `harness = false` print no libtest output and are not part of the report.
+#[derive(Default)]
+struct Parser {
+    report: TestReport,
+    name: Option<String>,
+    suite: Option<TestSuite>,
+    /// The index of the failed case whose captured output is being read, with that output.
+    failure: Option<(usize, String)>,
+}
+
+impl Parser {
+    fn end_failure(&mut self) {
+        if let (Some((index, output)), Some(suite)) = (self.failure.take(), self.suite.as_mut()) {
+            suite.cases[index].message = Some(output.trim_end().into());
+        }
+    }
+
+    fn line(&mut self, line: &str) {
+        // Captured test output may contain anything, including lines looking like cargo headers.
+        if self.failure.is_none() {
+            let trimmed = line.trim_start();
+            if let Some(v) = trimmed.strip_prefix("Running ") {
+                self.name = Some(suite_name(v));
+                return;
+            }
+            if let Some(v) = trimmed.strip_prefix("Doc-tests ") {
+                self.name = Some(format!("{}-doc", v));
+                return;
+            }
+        }
+        let Some(suite) = self.suite.as_mut() else {
+            let is_start = line.starts_with("running ")
+                && (line.ends_with(" test") || line.ends_with(" tests"));
+            if is_start {
+                let name = self
+                    .name
+                    .take()
+                    .unwrap_or_else(|| format!("suite-{}", self.report.suites().len()));
+                self.suite = Some(TestSuite::new(name));
+            }
+            return;
+        };
+        if line.starts_with("test result: ") {
+            self.end_failure();
+            if let Some(suite) = self.suite.take() {
+                self.report.add_suite(suite);
+            }
+        } else if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|v| v.strip_suffix(" stdout ----"))
+        {
+            let index = suite.cases.iter().position(|v| v.name == name);
+            self.end_failure();
+            self.failure = index.map(|v| (v, String::new()));
+        } else if line == "failures:" {
+            self.end_failure();
+        } else if let Some((_, output)) = self.failure.as_mut() {
+            output.push_str(line);
+            output.push('\n');
+        } else if let Some(case) = parse_case(line) {
+            suite.cases.push(case);
+        }
+    }
+
+    fn finish(mut self) -> TestReport {
+        self.end_failure();
+        if let Some(suite) = self.suite.take() {
+            self.report.add_suite(suite);
+        }
+        self.report
+    }
+}
+
+pub fn run(ctx: &Context, target: &str) -> Result<TestReport, Error> {
+    let mut cmd = Command::new("cargo");
+    cmd.arg("test")
+        .arg("--no-fail-fast")
+        .arg("--message-format=json");
+    super::builder::gen_base_command(&mut cmd, ctx, target);
+    let (reader, writer) = std::io::pipe().map_err(Error::Io)?;
+    cmd.stdout(writer.try_clone().map_err(Error::Io)?)
+        .stderr(writer);
+    let mut child = cmd.spawn().map_err(Error::Io)?;
+    // The command holds the write end of the pipe, which must be closed to reach end of file.
+    drop(cmd);
+    let mut parser = Parser::default();
+    for line in BufReader::new(reader).lines().map_while(|v| v.ok()) {
+        if line.starts_with('{') {
+            if let Ok(message) = serde_json::from_str::<Message>(&line) {
+                let rendered = message.message.and_then(|v| v.rendered);
+                if let ("compiler-message", Some(rendered)) = (&*message.reason, rendered) {
+                    eprint!("{}", rendered);
+                }
+                continue;
+            }
+        }
+        println!("{}", line);
+        parser.line(&line);
+    }
+    let status = child.wait().map_err(Error::Io)?;
+    let report = parser.finish();
+    if report.suites().is_empty() && !status.success() {
+        return Err(Error::TestFailed);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(output: &str) -> TestReport {
+        let mut parser = Parser::default();
+        for line in output.lines() {
+            parser.line(line);
+        }
+        parser.finish()
+    }
+
+    fn statuses(suite: &TestSuite) -> Vec<(&str, TestStatus)> {
+        suite.cases.iter().map(|v| (&*v.name, v.status)).collect()
+    }
+
+    const PASS: &str = "\
+    Finished `test` profile [unoptimized + debuginfo] target(s) in 0.52s
+     Running unittests src/lib.rs (target/debug/deps/core-3f9c2a1b5d7e8f01)
+
+running 2 tests
+test tests::parse ... ok
+test tests::write ... ok
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+";
+
+    const FAIL: &str = "\
+     Running unittests src/lib.rs (target/debug/deps/core-3f9c2a1b5d7e8f01)
+
+running 3 tests
+test tests::parse ... ok
+test tests::write ... FAILED
+test tests::read ... FAILED
+
+failures:
+
+---- tests::write stdout ----
+writing to buffer
+     Running fake header printed by the test (target/debug/deps/fake-0123456789abcdef)
+
+thread 'tests::write' panicked at src/lib.rs:12:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- tests::read stdout ----
+
+thread 'tests::read' panicked at src/lib.rs:20:9:
+not implemented
+
+failures:
+    tests::read
+    tests::write
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+error: test failed, to rerun pass `--lib`
+";
+
+    const IGNORED: &str = "\
+     Running tests/network.rs (target/debug/deps/network-0a1b2c3d4e5f6789)
+
+running 2 tests
+test download ... ignored, requires network access
+test offline ... ignored
+
+test result: ok. 0 passed; 0 failed; 2 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+";
+
+    const SUITES: &str = "\
+     Running unittests src/lib.rs (target/debug/deps/core-3f9c2a1b5d7e8f01)
+
+running 1 test
+test tests::parse ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+     Running unittests src/main.rs (target/debug/deps/tool-77aa88bb99cc00dd)
+
+running 0 tests
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+     Running tests/bench.rs (target/debug/deps/bench-1122334455667788)
+   Doc-tests core
+
+running 1 test
+test src/lib.rs - parse (line 10) ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.12s
+
+";
+
+    #[test]
+    fn pass() {
+        let report = parse(PASS);
+        assert_eq!(report.suites().len(), 1);
+        let suite = &report.suites()[0];
+        assert_eq!(suite.name, "core");
+        assert_eq!(
+            statuses(suite),
+            [
+                ("tests::parse", TestStatus::Passed),
+                ("tests::write", TestStatus::Passed)
+            ]
+        );
+        assert!(report.is_success());
+    }
+
+    #[test]
+    fn fail_with_output() {
+        let report = parse(FAIL);
+        assert_eq!(report.suites().len(), 1);
+        let suite = &report.suites()[0];
+        assert_eq!(suite.name, "core");
+        assert_eq!(
+            statuses(suite),
+            [
+                ("tests::parse", TestStatus::Passed),
+                ("tests::write", TestStatus::Failed),
+                ("tests::read", TestStatus::Failed)
+            ]
+        );
+        let message = suite.cases[1].message.as_deref().unwrap();
+        assert!(message.starts_with("writing to buffer\n"));
+        assert!(message.contains("Running fake header"));
+        assert!(message.ends_with("to display a backtrace"));
+        let message = suite.cases[2].message.as_deref().unwrap();
+        assert!(message.ends_with("not implemented"));
+        assert!(suite.cases[0].message.is_none());
+        assert!(!report.is_success());
+    }
+
+    #[test]
+    fn ignored() {
+        let report = parse(IGNORED);
+        let suite = &report.suites()[0];
+        assert_eq!(suite.name, "network");
+        assert_eq!(
+            statuses(suite),
+            [
+                ("download", TestStatus::Ignored),
+                ("offline", TestStatus::Ignored)
+            ]
+        );
+    }
+
+    #[test]
+    fn several_suites() {
+        let report = parse(SUITES);
+        let names: Vec<&str> = report.suites().iter().map(|v| &*v.name).collect();
+        assert_eq!(names, ["core", "tool", "core-doc"]);
+        assert_eq!(report.suites()[1].cases.len(), 0);
+        assert_eq!(
+            statuses(&report.suites()[2]),
+            [("src/lib.rs - parse (line 10)", TestStatus::Passed)]
+        );
+    }
+}
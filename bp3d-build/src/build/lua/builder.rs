@@ -27,42 +27,81 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::build::lua::LuaPackage;
+use crate::lua::error::LuaError;
 use crate::lua::List as LuaList;
 use crate::system::artifact::List;
-use crate::system::{BuildSystem, Context};
+use crate::system::target::Target;
+use crate::system::test_report::TestReport;
+use crate::system::{BuildSystem, CleanScope, Context};
 
 pub struct LuaBuilder;
 
 impl BuildSystem for LuaBuilder {
-    type Error = bp3d_lua::vm::error::Error;
+    type Error = LuaError;
     type Package = LuaPackage;
 
+    fn name(&self, _: &Self::Package) -> &str {
+        "Lua"
+    }
+
     fn configure(
         &self,
         package: &Self::Package,
         ctx: &Context,
         targets: &[&str],
     ) -> Result<(), Self::Error> {
-        package.vm().call_main(0, [].into_iter())?;
-        package.vm().call_target_list("configure", ctx, targets, ())
+        let vm = package.vm();
+        vm.call_main(0, [].into_iter())
+            .and_then(|_| vm.call_target_list("configure", ctx, targets, ()))
+            .map_err(|e| vm.to_error(&e).with_phase("configure", None))
     }
 
     fn build(
         &self,
         package: &Self::Package,
         ctx: &Context,
-        target: &str,
+        target: &Target,
     ) -> Result<(), Self::Error> {
-        package.vm().call_context("build", ctx, target, ())
+        let vm = package.vm();
+        vm.call_context("build", ctx, target, ())
+            .map_err(|e| vm.to_error(&e).with_phase("build", Some(target.name())))
     }
 
     fn pre_package(
         &self,
         package: &Self::Package,
         ctx: &Context,
-        target: &str,
+        target: &Target,
     ) -> Result<List, Self::Error> {
-        let lst: LuaList = package.vm().call_userdata("prePackage", ctx, target)?;
+        let vm = package.vm();
+        let lst: LuaList = vm.call_userdata("prePackage", ctx, target).map_err(|e| {
+            vm.to_error(&e)
+                .with_phase("prePackage", Some(target.name()))
+        })?;
         Ok(lst.into_inner())
     }
+
+    fn test(
+        &self,
+        package: &Self::Package,
+        ctx: &Context,
+        target: &Target,
+    ) -> Result<TestReport, Self::Error> {
+        let vm = package.vm();
+        vm.call_test_report("test", ctx, target)
+            .map_err(|e| vm.to_error(&e).with_phase("test", Some(target.name())))
+    }
+
+    fn clean(
+        &self,
+        package: &Self::Package,
+        ctx: &Context,
+        targets: &[&str],
+        scope: CleanScope,
+    ) -> Result<(), Self::Error> {
+        let vm = package.vm();
+        vm.call_main(0, [].into_iter())
+            .and_then(|_| vm.call_target_list("clean", ctx, targets, scope.name()))
+            .map_err(|e| vm.to_error(&e).with_phase("clean", None))
+    }
 }
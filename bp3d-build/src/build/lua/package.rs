@@ -27,6 +27,8 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::lua::core::Vm;
+use crate::lua::error::LuaError;
+use crate::lua::paths::SearchPaths;
 use crate::system::{Component, Package};
 use bp3d_lua::vm::table::Table;
 use std::borrow::Cow;
@@ -73,10 +75,17 @@ pub struct LuaPackage {
 }
 
 impl LuaPackage {
-    pub fn new(path: &Path) -> bp3d_lua::vm::Result<LuaPackage> {
-        let mut vm = Vm::new(path)?;
+    /// Loads the `build.lua` script of the project at `path`; when `config` is set, it overrides
+    /// the name and version declared by the script.
+    pub fn new(
+        path: &Path,
+        paths: SearchPaths,
+        config: Option<&bp3d_config::Package>,
+    ) -> Result<LuaPackage, LuaError> {
+        let mut vm = Vm::new(path, paths).map_err(|e| LuaError::new(&e, &[]))?;
         let main = path.join("build.lua");
-        vm.run(&main)?;
+        vm.run(&main)
+            .map_err(|e| vm.to_error(&e).with_phase("load", None))?;
         let mut targets: Vec<Cow<'static, str>> = Vec::new();
         let mut configurations: Vec<Cow<'static, str>> = Vec::new();
         let mut features: Vec<Cow<'static, str>> = Vec::new();
@@ -111,7 +120,12 @@ impl LuaPackage {
                 version = class.get(c"version")?;
             }
             Ok(())
-        })?;
+        })
+        .map_err(|e| vm.to_error(&e).with_phase("load", None))?;
+        if let Some(config) = config {
+            name = config.name.clone();
+            version = config.version.clone();
+        }
         Ok(LuaPackage {
             vm,
             targets,
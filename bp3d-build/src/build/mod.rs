@@ -28,3 +28,4 @@
 
 pub mod cargo;
 pub mod lua;
+pub mod plugin;
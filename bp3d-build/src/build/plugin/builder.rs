@@ -0,0 +1,155 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use super::{Error, PluginPackage};
+use crate::plugin::{abi, Collector, RawContext};
+use crate::system::artifact::List;
+use crate::system::target::Target;
+use crate::system::test_report::TestReport;
+use crate::system::{BuildSystem, CleanScope, Context};
+
+pub struct PluginBuilder;
+
+fn context(ctx: &Context) -> Result<RawContext, Error> {
+    RawContext::new(ctx).ok_or(Error::InvalidUtf8)
+}
+
+impl BuildSystem for PluginBuilder {
+    type Error = Error;
+    type Package = PluginPackage;
+
+    fn name(&self, package: &PluginPackage) -> &str {
+        unsafe { package.table().name.as_str() }.unwrap_or("Plugin")
+    }
+
+    fn configure(
+        &self,
+        package: &PluginPackage,
+        ctx: &Context,
+        targets: &[&str],
+    ) -> Result<(), Error> {
+        let ctx = context(ctx)?;
+        let targets: Vec<abi::Str> = targets.iter().map(|v| abi::Str::new(v)).collect();
+        let mut collector = Collector::new();
+        let out = collector.output();
+        let res = unsafe {
+            (package.table().configure)(
+                package.handle(),
+                ctx.as_raw(),
+                abi::StrList::new(&targets),
+                &out,
+            )
+        };
+        collector.finish(res).map(|_| ()).map_err(Error::Plugin)
+    }
+
+    fn build(&self, package: &PluginPackage, ctx: &Context, target: &Target) -> Result<(), Error> {
+        let ctx = context(ctx)?;
+        let mut collector = Collector::new();
+        let out = collector.output();
+        let res = unsafe {
+            (package.table().build)(
+                package.handle(),
+                ctx.as_raw(),
+                abi::Str::new(target.name()),
+                &out,
+            )
+        };
+        collector.finish(res).map(|_| ()).map_err(Error::Plugin)
+    }
+
+    fn pre_package(
+        &self,
+        package: &PluginPackage,
+        ctx: &Context,
+        target: &Target,
+    ) -> Result<List, Error> {
+        let ctx = context(ctx)?;
+        let mut collector = Collector::new();
+        let out = collector.output();
+        let res = unsafe {
+            (package.table().pre_package)(
+                package.handle(),
+                ctx.as_raw(),
+                abi::Str::new(target.name()),
+                &out,
+            )
+        };
+        collector.finish(res).map_err(Error::Plugin)
+    }
+
+    fn test(
+        &self,
+        package: &PluginPackage,
+        ctx: &Context,
+        target: &Target,
+    ) -> Result<TestReport, Error> {
+        let ctx = context(ctx)?;
+        let mut collector = Collector::new();
+        let out = collector.output();
+        let res = unsafe {
+            (package.table().test)(
+                package.handle(),
+                ctx.as_raw(),
+                abi::Str::new(target.name()),
+                &out,
+            )
+        };
+        collector.finish_test(res).map_err(Error::Plugin)
+    }
+
+    fn clean(
+        &self,
+        package: &PluginPackage,
+        ctx: &Context,
+        targets: &[&str],
+        scope: CleanScope,
+    ) -> Result<(), Error> {
+        let ctx = context(ctx)?;
+        let targets: Vec<abi::Str> = targets.iter().map(|v| abi::Str::new(v)).collect();
+        let scope = match scope {
+            CleanScope::Dist => abi::CLEAN_DIST,
+            CleanScope::Target => abi::CLEAN_TARGET,
+            CleanScope::Configuration => abi::CLEAN_CONFIGURATION,
+            CleanScope::Ext => abi::CLEAN_EXT,
+            CleanScope::All => abi::CLEAN_ALL,
+        };
+        let mut collector = Collector::new();
+        let out = collector.output();
+        let res = unsafe {
+            (package.table().clean)(
+                package.handle(),
+                ctx.as_raw(),
+                abi::StrList::new(&targets),
+                scope,
+                &out,
+            )
+        };
+        collector.finish(res).map(|_| ()).map_err(Error::Plugin)
+    }
+}
@@ -27,34 +27,16 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use bp3d_util::simple_error;
-use serde::Deserialize;
-use std::path::Path;
 
-#[derive(Deserialize)]
-pub struct Package {
-    pub name: String,
-    pub version: String,
-}
-
-#[derive(Deserialize)]
-pub struct Config {
-    pub package: Option<Package>,
-}
+mod builder;
+mod package;
 
 simple_error! {
     pub Error {
-        Io(std::io::Error) => "io error: {}",
-        Toml(toml::de::Error) => "toml error: {}"
+        Plugin(String) => "{}",
+        InvalidUtf8 => "invalid utf-8"
     }
 }
 
-pub fn parse_config(root: &Path) -> Result<Option<Config>, Error> {
-    let path = root.join("bp3d.toml");
-    if path.exists() && path.is_file() {
-        let bytes = std::fs::read(root.join("bp3d.toml")).map_err(Error::Io)?;
-        let config: Config = toml::from_slice(&bytes).map_err(Error::Toml)?;
-        Ok(Some(config))
-    } else {
-        Ok(None)
-    }
-}
+pub use builder::PluginBuilder;
+pub use package::PluginPackage;
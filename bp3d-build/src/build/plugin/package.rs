@@ -0,0 +1,199 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use super::Error;
+use crate::plugin::{abi, Collector};
+use crate::system::{Component, Package};
+use std::borrow::Cow;
+use std::ffi::c_void;
+use std::path::Path;
+
+struct PluginComponent {
+    name: String,
+    version: String,
+    short_name: String,
+    description: Option<String>,
+    public: bool,
+}
+
+impl Component for PluginComponent {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_version(&self) -> &str {
+        &self.version
+    }
+
+    fn get_short_name(&self) -> &str {
+        &self.short_name
+    }
+
+    fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn is_public(&self) -> bool {
+        self.public
+    }
+}
+
+fn to_string(value: abi::Str) -> Result<String, Error> {
+    unsafe { value.as_str() }
+        .map(String::from)
+        .ok_or(Error::InvalidUtf8)
+}
+
+fn to_list(value: abi::StrList) -> Result<Vec<Cow<'static, str>>, Error> {
+    unsafe { value.as_slice() }
+        .iter()
+        .map(|v| to_string(*v).map(Cow::Owned))
+        .collect()
+}
+
+pub struct PluginPackage {
+    table: &'static abi::BuildSystem,
+    handle: *mut c_void,
+    name: String,
+    version: String,
+    components: Vec<PluginComponent>,
+    targets: Vec<Cow<'static, str>>,
+    configurations: Vec<Cow<'static, str>>,
+    features: Vec<Cow<'static, str>>,
+}
+
+impl PluginPackage {
+    /// Attempts to open the project at `root` with the given plugin build system, returns
+    /// [None] if the build system does not handle this project.
+    ///
+    /// When `config` is set, it overrides the name and version reported by the plugin.
+    pub fn open(
+        table: &'static abi::BuildSystem,
+        root: &Path,
+        config: Option<&bp3d_config::Package>,
+    ) -> Result<Option<Self>, Error> {
+        let root = root.to_str().ok_or(Error::InvalidUtf8)?;
+        let mut collector = Collector::new();
+        let out = collector.output();
+        let handle = unsafe { (table.open)(abi::Str::new(root), &out) };
+        if handle.is_null() {
+            return match collector.take_error() {
+                Some(e) => Err(Error::Plugin(e)),
+                None => Ok(None),
+            };
+        }
+        let mut package = PluginPackage {
+            table,
+            handle,
+            name: String::new(),
+            version: String::new(),
+            components: Vec::new(),
+            targets: Vec::new(),
+            configurations: Vec::new(),
+            features: Vec::new(),
+        };
+        let mut info = abi::PackageInfo {
+            name: abi::Str::EMPTY,
+            version: abi::Str::EMPTY,
+            components: std::ptr::null(),
+            components_len: 0,
+            targets: abi::StrList::EMPTY,
+            configurations: abi::StrList::EMPTY,
+            features: abi::StrList::EMPTY,
+        };
+        unsafe { (table.package)(handle, &mut info) };
+        package.name = to_string(info.name)?;
+        package.version = to_string(info.version)?;
+        package.targets = to_list(info.targets)?;
+        package.configurations = to_list(info.configurations)?;
+        package.features = to_list(info.features)?;
+        if let Some(config) = config {
+            package.name = config.name.clone();
+            package.version = config.version.clone();
+        }
+        if info.components_len > 0 {
+            let components =
+                unsafe { std::slice::from_raw_parts(info.components, info.components_len) };
+            for component in components {
+                let description = to_string(component.description)?;
+                package.components.push(PluginComponent {
+                    name: to_string(component.name)?,
+                    version: to_string(component.version)?,
+                    short_name: to_string(component.short_name)?,
+                    description: (!description.is_empty()).then_some(description),
+                    public: component.public,
+                });
+            }
+        }
+        Ok(Some(package))
+    }
+
+    pub fn table(&self) -> &'static abi::BuildSystem {
+        self.table
+    }
+
+    pub fn handle(&self) -> *mut c_void {
+        self.handle
+    }
+}
+
+impl Drop for PluginPackage {
+    fn drop(&mut self) {
+        unsafe { (self.table.close)(self.handle) };
+    }
+}
+
+impl Package for PluginPackage {
+    fn get_primary_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_primary_version(&self) -> &str {
+        &self.version
+    }
+
+    fn get_components(&self) -> usize {
+        self.components.len()
+    }
+
+    fn get_component(&self, index: usize) -> &dyn Component {
+        &self.components[index]
+    }
+
+    fn targets(&self) -> &[Cow<'_, str>] {
+        &self.targets
+    }
+
+    fn configurations(&self) -> &[Cow<'_, str>] {
+        &self.configurations
+    }
+
+    fn features(&self) -> &[Cow<'_, str>] {
+        &self.features
+    }
+}
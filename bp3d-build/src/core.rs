@@ -28,10 +28,40 @@
 
 use crate::build::cargo::{CargoBuilder, CargoWorkspace};
 use crate::build::lua::{LuaBuilder, LuaPackage};
+use crate::build::plugin::{PluginBuilder, PluginPackage};
+use crate::lua::error::LuaError;
+use crate::plugin::Registry;
+use crate::report;
 use crate::system::artifact::List;
-use crate::system::{BuildSystem, Context, Features, Package};
+use crate::system::target::{self, Target};
+use crate::system::test_report::TestReport;
+use crate::system::{BuildSystem, CleanScope, Context, Features, Package};
+use crate::workspace::WorkspaceTool;
+use bp3d_debug::{info, warning};
 use bp3d_util::simple_error;
-use std::path::Path;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub struct MemberError {
+    pub member: String,
+    pub error: Box<Error>,
+}
+
+impl MemberError {
+    pub fn wrap(member: &str, error: Error) -> Error {
+        Error::Member(MemberError {
+            member: member.into(),
+            error: Box::new(error),
+        })
+    }
+}
+
+impl Display for MemberError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "in member {}: {}", self.member, self.error)
+    }
+}
 
 simple_error! {
     pub Error {
@@ -41,13 +71,22 @@ simple_error! {
         InvalidConfig(String) => "invalid configuration: {}",
         UnknownFeature(String) => "unknown feature: {}",
         BuildSystem(String) => "build error: {}",
-        ScriptSystem(String) => "script error: {}"
+        ScriptSystem(String) => "script error: {}",
+        Manifest(bp3d_config::Error) => "manifest error: {}",
+        Workspace(String) => "workspace error: {}",
+        Clean(std::io::Error) => "failed to clean outputs: {}",
+        LuaPath(crate::lua::paths::Error) => "lua search path error: {}",
+        Lua(Box<LuaError>) => "{}",
+        Member(MemberError) => "{}"
     }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 pub trait BuildTool {
+    /// Returns the name of the build system handling the project.
+    fn name(&self) -> &str;
+
     fn package(&self) -> &dyn Package;
 
     fn configure(&self, ctx: &Context, targets: &[&str]) -> Result<()>;
@@ -55,6 +94,87 @@ pub trait BuildTool {
     fn build(&self, ctx: &Context, target: &str) -> Result<()>;
 
     fn pre_package(&self, ctx: &Context, target: &str) -> Result<List>;
+
+    fn test(&self, ctx: &Context, target: &str) -> Result<TestReport>;
+
+    fn clean(&self, ctx: &Context, targets: &[&str], scope: CleanScope) -> Result<()>;
+}
+
+fn remove(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        info!("Removing {:?}...", path);
+        std::fs::remove_dir_all(path)
+    } else if path.exists() {
+        info!("Removing {:?}...", path);
+        std::fs::remove_file(path)
+    } else {
+        Ok(())
+    }
+}
+
+fn clean_dist(target_dir: &Path, configuration: &str, target: &str) -> std::io::Result<()> {
+    let target_path = target_dir.join(target).join(configuration);
+    remove(&target_path.join("dist"))?;
+    remove(&target_path.join("dist.bpx"))?;
+    remove(&target_path.join("SHA256SUMS"))?;
+    remove(&target_path.join("installer.rs"))?;
+    if target_path.is_dir() {
+        for entry in std::fs::read_dir(&target_path)? {
+            let path = entry?.path();
+            if path.extension().map(|v| v == "framework").unwrap_or(false) {
+                remove(&path)?;
+            }
+        }
+    }
+    if target_dir.is_dir() {
+        let suffix = format!(".{}", target);
+        let suffix_exe = format!(".{}.exe", target);
+        for entry in std::fs::read_dir(target_dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let is_installer = name.starts_with("install-")
+                && (name.ends_with(&suffix) || name.ends_with(&suffix_exe));
+            if is_installer || name.ends_with(".xcframework") {
+                remove(&entry.path())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Removes the outputs of the given scope which are not owned by a build system, that is
+/// packaging outputs and fpkg dependencies.
+pub(crate) fn clean_outputs(
+    path: &Path,
+    configuration: &str,
+    targets: &[&str],
+    scope: CleanScope,
+) -> Result<()> {
+    let target_dir = path.join("target");
+    let res = match scope {
+        CleanScope::All => remove(&target_dir),
+        CleanScope::Target => targets.iter().try_for_each(|v| remove(&target_dir.join(v))),
+        CleanScope::Configuration => targets
+            .iter()
+            .try_for_each(|v| remove(&target_dir.join(v).join(configuration))),
+        CleanScope::Ext => targets
+            .iter()
+            .try_for_each(|v| remove(&target_dir.join(v).join("ext"))),
+        CleanScope::Dist => targets
+            .iter()
+            .try_for_each(|v| clean_dist(&target_dir, configuration, v)),
+    };
+    res.map_err(Error::Clean)
+}
+
+/// Converts a build system error, keeping Lua errors structured.
+fn build_error<E: std::error::Error + 'static>(e: E) -> Error {
+    let e: Box<dyn std::error::Error> = Box::new(e);
+    match e.downcast::<LuaError>() {
+        Ok(e) => Error::Lua(e),
+        Err(e) => Error::BuildSystem(e.to_string()),
+    }
 }
 
 struct BuildSystemWrapper<P, B> {
@@ -75,13 +195,15 @@ impl<P, B> BuildSystemWrapper<P, B>
 where
     P: Package,
 {
-    fn check_context(&self, ctx: &Context, target: &str) -> Result<()> {
+    /// Checks the context of a per-target call, returns the parsed target.
+    fn check_context(&self, ctx: &Context, target: &str) -> Result<Target> {
         let targets = self.package.targets();
         let features = self.package.features();
         let configurations = self.package.configurations();
-        let found = targets.iter().any(|v| v == target);
+        let target = Target::parse(target).map_err(|_| Error::InvalidTarget(target.into()))?;
+        let found = targets.iter().any(|v| target::resolve(v) == target.name());
         if !found {
-            return Err(Error::InvalidTarget(target.into()));
+            return Err(Error::InvalidTarget(target.name().into()));
         }
         let configuration = configurations.iter().any(|v| v == ctx.configuration);
         if !configuration {
@@ -95,7 +217,7 @@ where
                 }
             }
         }
-        Ok(())
+        Ok(target)
     }
 }
 
@@ -104,6 +226,10 @@ where
     P: Package,
     B: BuildSystem<Package = P>,
 {
+    fn name(&self) -> &str {
+        self.build_system.name(&self.package)
+    }
+
     fn package(&self) -> &dyn Package {
         &self.package
     }
@@ -112,36 +238,163 @@ where
         for v in targets {
             self.check_context(ctx, v)?;
         }
-        self.build_system
-            .configure(&self.package, ctx, targets)
-            .map_err(|v| Error::BuildSystem(v.to_string()))
+        report::phase(self.package.get_primary_name(), "configure", None, || {
+            self.build_system
+                .configure(&self.package, ctx, targets)
+                .map_err(build_error)
+        })
     }
 
     fn build(&self, ctx: &Context, target: &str) -> Result<()> {
-        self.check_context(ctx, target)?;
-        self.build_system
-            .build(&self.package, &ctx, target)
-            .map_err(|v| Error::BuildSystem(v.to_string()))
+        let target = self.check_context(ctx, target)?;
+        report::phase(
+            self.package.get_primary_name(),
+            "build",
+            Some(target.name()),
+            || {
+                self.build_system
+                    .build(&self.package, &ctx, &target)
+                    .map_err(build_error)
+            },
+        )
     }
 
     fn pre_package(&self, ctx: &Context, target: &str) -> Result<List> {
-        self.check_context(ctx, target)?;
+        let target = self.check_context(ctx, target)?;
+        let name = self.package.get_primary_name();
+        let list = report::phase(name, "pre-package", Some(target.name()), || {
+            self.build_system
+                .pre_package(&self.package, &ctx, &target)
+                .map_err(build_error)
+        })?;
+        report::artifacts(name, target.name(), &list);
+        Ok(list)
+    }
+
+    fn test(&self, ctx: &Context, target: &str) -> Result<TestReport> {
+        let target = self.check_context(ctx, target)?;
+        report::phase(
+            self.package.get_primary_name(),
+            "test",
+            Some(target.name()),
+            || {
+                self.build_system
+                    .test(&self.package, ctx, &target)
+                    .map_err(build_error)
+            },
+        )
+    }
+
+    fn clean(&self, ctx: &Context, targets: &[&str], scope: CleanScope) -> Result<()> {
         self.build_system
-            .pre_package(&self.package, &ctx, target)
-            .map_err(|v| Error::BuildSystem(v.to_string()))
+            .clean(&self.package, ctx, targets, scope)
+            .map_err(build_error)?;
+        clean_outputs(ctx.path, ctx.configuration, targets, scope)
     }
 }
 
 pub fn open(path: &Path) -> Result<Box<dyn BuildTool>> {
+    open_nested(path, &[])
+}
+
+/// Opens a project, `workspaces` being the canonical paths of the workspaces it is a member of.
+pub(crate) fn open_nested(path: &Path, workspaces: &[PathBuf]) -> Result<Box<dyn BuildTool>> {
+    let document = bp3d_config::load(path).map_err(Error::Manifest)?;
+    if let Some(document) = &document {
+        for w in document.warnings() {
+            warning!("{}", w);
+        }
+    }
+    let config = document.map(|v| v.into_manifest()).unwrap_or_default();
+    if let Some(workspace) = &config.workspace {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.into());
+        if workspaces.contains(&canonical) {
+            return Err(Error::Workspace(format!(
+                "workspace {:?} is a member of itself",
+                canonical
+            )));
+        }
+        let mut workspaces = workspaces.to_vec();
+        workspaces.push(canonical);
+        let tool = WorkspaceTool::open(path, workspace, config.package.as_ref(), &workspaces)?;
+        return Ok(Box::new(tool));
+    }
+    let build_systems = Registry::lock().build_systems().to_vec();
+    for table in build_systems {
+        let package = PluginPackage::open(table, path, config.package.as_ref())
+            .map_err(|e| Error::InvalidPackage(e.to_string()))?;
+        if let Some(package) = package {
+            return Ok(Box::new(BuildSystemWrapper::new(package, PluginBuilder)));
+        }
+    }
     let manifest = path.join("Cargo.toml");
     if manifest.exists() {
-        let package =
-            CargoWorkspace::load(path).map_err(|e| Error::InvalidPackage(e.to_string()))?;
+        let package = CargoWorkspace::load(path, config.package.as_ref())
+            .map_err(|e| Error::InvalidPackage(e.to_string()))?;
         Ok(Box::new(BuildSystemWrapper::new(package, CargoBuilder)))
     } else if path.join("build.lua").exists() {
-        let package = LuaPackage::new(path).map_err(|e| Error::InvalidPackage(e.to_string()))?;
+        let paths = crate::lua::paths::resolve(path).map_err(Error::LuaPath)?;
+        let package = LuaPackage::new(path, paths, config.package.as_ref())
+            .map_err(|e| Error::Lua(Box::new(e)))?;
         Ok(Box::new(BuildSystemWrapper::new(package, LuaBuilder)))
     } else {
         Err(Error::UnknownProject)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST: &str = "x86_64-unknown-linux-gnu";
+    const OTHER: &str = "aarch64-apple-darwin";
+
+    fn setup(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("bp3d-clean-{}-{}", std::process::id(), name));
+        for target in [HOST, OTHER] {
+            for config in ["debug", "release"] {
+                let dir = root.join("target").join(target).join(config);
+                std::fs::create_dir_all(dir.join("dist")).unwrap();
+                std::fs::write(dir.join("libcore.a"), "").unwrap();
+            }
+        }
+        root
+    }
+
+    #[test]
+    fn clean_target() {
+        let root = setup("target");
+        clean_outputs(&root, "debug", &[HOST], CleanScope::Target).unwrap();
+        let target = root.join("target");
+        assert!(!target.join(HOST).exists());
+        assert!(target.join(OTHER).join("debug").join("libcore.a").exists());
+        assert!(target
+            .join(OTHER)
+            .join("release")
+            .join("libcore.a")
+            .exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn clean_configuration() {
+        let root = setup("config");
+        clean_outputs(&root, "debug", &[HOST], CleanScope::Configuration).unwrap();
+        let target = root.join("target");
+        assert!(!target.join(HOST).join("debug").exists());
+        assert!(target.join(HOST).join("release").join("libcore.a").exists());
+        assert!(target.join(OTHER).join("debug").join("libcore.a").exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn clean_dist_only() {
+        let root = setup("dist");
+        clean_outputs(&root, "debug", &[HOST], CleanScope::Dist).unwrap();
+        let target = root.join("target");
+        assert!(!target.join(HOST).join("debug").join("dist").exists());
+        assert!(target.join(HOST).join("debug").join("libcore.a").exists());
+        assert!(target.join(OTHER).join("debug").join("dist").exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -35,6 +35,21 @@ pub mod core;
 /// Public Lua engine.
 pub mod lua;
 
+/// Public plugin interface.
+pub mod plugin;
+
+/// External tool resolution.
+pub mod tools;
+
+/// Build timing and artifact size report.
+pub mod report;
+
+/// Deterministic tar and zip archives.
+pub mod archive;
+
+/// Text templates rendered by the Lua build scripts.
+pub mod template;
+
 // Build system implementations.
 mod build;
-mod config;
+mod workspace;
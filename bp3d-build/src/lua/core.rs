@@ -26,11 +26,26 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use crate::lua::error::LuaError;
+use crate::lua::lib_archive::ArchiveLib;
+use crate::lua::lib_cargo::CargoLib;
 use crate::lua::lib_command::CommandLib;
 use crate::lua::lib_files::FilesLib;
+use crate::lua::lib_hash::HashLib;
+use crate::lua::lib_serde::{self, JsonLib, TomlLib};
+use crate::lua::lib_template::TemplateLib;
+use crate::lua::lib_test::{MockCommandLib, TestLib};
 use crate::lua::obj_artifact::ObjArtifact;
-use crate::lua::obj_list::ObjList;
+use crate::lua::obj_list::{self, ObjList};
+use crate::lua::obj_target::{ObjTarget, Target};
+use crate::lua::paths::SearchPaths;
+use crate::lua::state;
+use crate::lua::util::convert_test_report;
+use crate::system::artifact;
+use crate::system::target;
+use crate::system::test_report::TestReport;
 use crate::system::{Context, Features};
+use crate::tools::Requirement;
 use bp3d_debug::debug;
 use bp3d_lua::libs::files::chroot;
 use bp3d_lua::libs::files::chroot::Permissions;
@@ -54,35 +69,15 @@ use bp3d_lua::vm::RootVm;
 use bp3d_os::assets::get_executable_path;
 use bp3d_os::module::loader::ModuleLoader;
 use bp3d_util::path::PathExt;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-pub fn dump_backtrace<T>(res: Result<T>) -> Result<T> {
-    if let Err(e) = &res {
-        if let Error::Runtime(err) = e {
-            println!("{}", err.backtrace());
-        }
-    }
-    res
-}
-
 struct SourcePath(PathBuf);
 
 impl SourcePath {
     pub fn new(path: impl Into<PathBuf>) -> Self {
         SourcePath(path.into())
     }
-
-    pub fn from_installed() -> Self {
-        let exe = get_executable_path().unwrap();
-        let mut path = exe.join("../usr/share/lua");
-        debug!("path: {:?}", &path);
-        if !path.exists() {
-            path = exe.join("../../res/lua");
-        }
-        debug!("path: {:?}", &path);
-        assert!(path.exists());
-        SourcePath(path)
-    }
 }
 
 impl Source for SourcePath {
@@ -91,12 +86,22 @@ impl Source for SourcePath {
         let path = self.0.join(path);
         let path = path.ensure_extension("lua");
         debug!("Injecting lua file at path: {:?}", &path);
-        dump_backtrace(vm.run(Script::from_path(path).map_err(|e| Error::Loader(e.to_string()))?))
+        vm.run(Script::from_path(path).map_err(|e| Error::Loader(e.to_string()))?)
     }
 }
 
+/// An argument declared in the `argTypes` table of a packager or script class.
+pub struct ArgType {
+    pub name: String,
+    pub ty: String,
+    pub optional: bool,
+    pub variants: Vec<String>,
+}
+
 pub struct Vm {
     vm: RootVm,
+    // Declared after the VM so that it is dropped once the VM is closed.
+    _state: Box<state::State>,
     provider: Shared<Provider>,
     search_paths: Vec<PathBuf>,
     main_class: Option<Key<bp3d_lua::vm::registry::types::Table>>,
@@ -111,14 +116,27 @@ impl Vm {
         &self.vm
     }
 
+    /// Creates a [Vm] for the given project using the search paths resolved by
+    /// [resolve](crate::lua::paths::resolve).
+    pub fn new(path: &Path, paths: SearchPaths) -> Result<Vm> {
+        Self::create(path, paths, None)
+    }
+
+    /// Creates a [Vm] for running Lua tests of the given project.
+    ///
+    /// bp3d.build.command only records commands and returns the results scripted with
+    /// bp3d.build.test.mock, and the sandbox is restricted to the test_root directory.
+    pub fn new_test(path: &Path, paths: SearchPaths, test_root: &Path) -> Result<Vm> {
+        Self::create(path, paths, Some(test_root))
+    }
+
     #[allow(dependency_on_unit_never_type_fallback)]
-    pub fn new(path: &Path) -> Result<Vm> {
+    fn create(path: &Path, paths: SearchPaths, test_root: Option<&Path>) -> Result<Vm> {
         let provider = Shared::new(Provider::new());
         let mut search_paths = Vec::new();
-        debug!("Adding root bp3d lua path...");
-        let src = SourcePath::from_installed();
-        let annoyingrust = src.0.clone();
-        provider.add_source("bp3d".into(), src);
+        let mut names = vec![String::from("bp3d")];
+        debug!("Adding root bp3d lua path: {:?}...", paths.root);
+        provider.add_source("bp3d".into(), SourcePath::new(&paths.root));
         debug!("Project root = {:?}", path);
         debug!("Project name = {:?}", path.file_name());
         if let Ok(path) = bp3d_os::fs::get_absolute_path(path) {
@@ -127,10 +145,29 @@ impl Vm {
                 debug!({ name }, "Adding project lua path: {:?}...", path);
                 search_paths.push(path.clone());
                 provider.add_source(name.into(), SourcePath::new(path));
+                names.push(name.into());
             }
         }
-        search_paths.push(annoyingrust);
+        // Each sub-directory of an additional root is exposed as a module namespace.
+        for root in paths.extra {
+            debug!("Adding lua search path: {:?}...", root);
+            for entry in std::fs::read_dir(&root).into_iter().flatten().flatten() {
+                let path = entry.path();
+                let Some(name) = path.file_name().and_then(|v| v.to_str()) else {
+                    continue;
+                };
+                if !path.is_dir() || names.iter().any(|v| v == name) {
+                    continue;
+                }
+                debug!({ name }, "Adding lua module path: {:?}...", path);
+                names.push(name.into());
+                provider.add_source(name.into(), SourcePath::new(path));
+            }
+            search_paths.push(root);
+        }
+        search_paths.push(paths.root);
         let vm = RootVm::new();
+        let state = state::init(&vm, &search_paths, test_root)?;
         Lua::new()
             .provider(provider.clone())
             .build()
@@ -138,29 +175,59 @@ impl Vm {
         Module.register(&vm)?;
         {
             let mut loader = ModuleLoader::lock();
-            let exe = get_executable_path().unwrap();
-            debug!("Adding module search path: {:?}", exe);
-            loader.add_search_path(&exe);
-            let libs = exe.parent().map(|v| v.join("lib"));
-            if let Some(libs) = libs {
-                debug!("Adding module search path: {:?}", libs);
-                loader.add_search_path(libs);
+            if let Some(exe) = get_executable_path() {
+                debug!("Adding module search path: {:?}", exe);
+                loader.add_search_path(&exe);
+                let libs = exe.parent().map(|v| v.join("lib"));
+                if let Some(libs) = libs {
+                    debug!("Adding module search path: {:?}", libs);
+                    loader.add_search_path(libs);
+                }
+            }
+        }
+        match test_root {
+            Some(root) => {
+                chroot::set_chroot(&vm, root);
+                chroot::set_access(&vm, "/", Permissions::R | Permissions::W);
+            }
+            None => {
+                chroot::set_chroot(&vm, path);
+                chroot::set_access(&vm, "/", Permissions::R);
+                chroot::set_access(&vm, "/target", Permissions::R | Permissions::W);
+                chroot::set_access(&vm, "/bp3d-build", Permissions::R | Permissions::X);
             }
         }
-        chroot::set_chroot(&vm, path);
-        chroot::set_access(&vm, "/", Permissions::R);
-        chroot::set_access(&vm, "/target", Permissions::R | Permissions::W);
-        chroot::set_access(&vm, "/bp3d-build", Permissions::R | Permissions::X);
         (Compat, Instant, Time).register(&vm)?;
         Util.register(&vm)?;
         Files.register(&vm)?;
-        CommandLib.register(&vm)?;
+        match test_root {
+            Some(_) => {
+                MockCommandLib.register(&vm)?;
+                TestLib.register(&vm)?;
+            }
+            None => CommandLib.register(&vm)?,
+        }
         FilesLib.register(&vm)?;
+        ArchiveLib.register(&vm)?;
+        CargoLib.register(&vm)?;
+        JsonLib.register(&vm)?;
+        lib_serde::init(&vm)?;
+        TomlLib.register(&vm)?;
+        HashLib.register(&vm)?;
+        TemplateLib.register(&vm)?;
         ObjArtifact.register(&vm)?;
         ObjList.register(&vm)?;
+        ObjTarget.register(&vm)?;
         vm.run_code(c"require = bp3d.lua.require")?;
+        // Concatenation may pass the string first, so it is defined in Lua where both operands
+        // can be converted with tostring. This keeps scripts written when ctx.target was a string
+        // working.
+        vm.run_code(
+            c"getmetatable(bp3d.build.Target.host()).__concat = function(a, b) return tostring(a) .. tostring(b) end",
+        )?;
         Ok(Vm {
             vm,
+            _state: state,
             provider,
             search_paths,
             main_class: None,
@@ -190,7 +257,7 @@ impl Vm {
             }
             let class = self.main_class.as_ref().unwrap().push(vm);
             let f: Function = class.get(c"init")?;
-            dump_backtrace(f.call((class.clone(), args2)))
+            f.call((class.clone(), args2))
         })
     }
 
@@ -199,12 +266,12 @@ impl Vm {
         vm: &bp3d_lua::vm::Vm,
         f: &'a Function<'a>,
         context: &Context,
-        target: &str,
+        target: &target::Target,
         arg: A,
     ) -> Result<R> {
-        let mut ctx = Table::with_capacity(vm, 0, 4);
+        let mut ctx = Table::with_capacity(vm, 0, 5);
         ctx.set(c"path", SandboxPath::from_path_unchecked(context.path))?;
-        ctx.set(c"target", target)?;
+        ctx.set(c"target", Target::from(target.clone()))?;
         ctx.set(c"configuration", context.configuration)?;
         if let Features::List(features) = context.features {
             let mut features2 = Table::with_capacity(vm, features.len(), 0);
@@ -213,7 +280,7 @@ impl Vm {
             }
             ctx.set(c"features", features2)?;
         }
-        dump_backtrace(f.call((class, ctx, arg)))
+        f.call((class, ctx, arg))
     }
 
     fn _call2<'a, A: IntoLua, R: FromLua<'a>>(
@@ -239,14 +306,14 @@ impl Vm {
             }
             ctx.set(c"features", features2)?;
         }
-        dump_backtrace(f.call((class, ctx, arg)))
+        f.call((class, ctx, arg))
     }
 
     pub fn call_userdata<R: 'static + UserDataImmutable + Clone>(
         &self,
         name: &str,
         context: &Context,
-        target: &str,
+        target: &target::Target,
     ) -> Result<R> {
         assert!(self.main_class.is_some());
         self.vm.scope(|vm| {
@@ -257,6 +324,23 @@ impl Vm {
         })
     }
 
+    /// Calls a method returning test results, see [convert_test_report].
+    pub fn call_test_report(
+        &self,
+        name: &str,
+        context: &Context,
+        target: &target::Target,
+    ) -> Result<TestReport> {
+        assert!(self.main_class.is_some());
+        self.vm.scope(|vm| {
+            let class = self.main_class.as_ref().unwrap().push(vm);
+            let f: Function = class.get(name)?;
+            let suites: Option<Vec<Table>> =
+                Self::_call(class.clone(), vm, &f, context, target, ())?;
+            convert_test_report(suites.unwrap_or_default())
+        })
+    }
+
     pub fn call_target_list<A: IntoLua>(
         &self,
         name: &str,
@@ -276,7 +360,7 @@ impl Vm {
         &self,
         name: &str,
         context: &Context,
-        target: &str,
+        target: &target::Target,
         arg: A,
     ) -> Result<()> {
         assert!(self.main_class.is_some());
@@ -287,6 +371,28 @@ impl Vm {
         })
     }
 
+    /// Calls a method with a table containing the given artifacts, as expected by packageTarget.
+    pub fn call_artifacts(
+        &self,
+        name: &str,
+        context: &Context,
+        target: &target::Target,
+        artifacts: &artifact::List,
+    ) -> Result<()> {
+        assert!(self.main_class.is_some());
+        self.vm.scope(|vm| {
+            let class = self.main_class.as_ref().unwrap().push(vm);
+            let f: Function = class.get(name)?;
+            let artifacts = obj_list::to_table(vm, artifacts)?;
+            Self::_call(class.clone(), vm, &f, context, target, artifacts)
+        })
+    }
+
+    /// Maps a Lua error back to its source using the search paths of this VM.
+    pub fn to_error(&self, err: &Error) -> LuaError {
+        LuaError::new(err, &self.search_paths)
+    }
+
     pub fn find(&self, name: &str) -> Option<PathBuf> {
         for v in &self.search_paths {
             let path = v.join(name);
@@ -298,6 +404,85 @@ impl Vm {
         None
     }
 
+    pub fn search_paths(&self) -> &[PathBuf] {
+        &self.search_paths
+    }
+
+    /// Lists all Lua files in the given folder of the search paths.
+    ///
+    /// Files are returned by name, without extension, and follow the same priority as
+    /// [find](Vm::find).
+    pub fn list(&self, folder: &str) -> Vec<(String, PathBuf)> {
+        let mut files: Vec<(String, PathBuf)> = Vec::new();
+        for v in &self.search_paths {
+            let Ok(entries) = std::fs::read_dir(v.join(folder)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_file() || path.extension().map(|v| v != "lua").unwrap_or(true) {
+                    continue;
+                }
+                let Some(name) = path.file_stem().and_then(|v| v.to_str()) else {
+                    continue;
+                };
+                if !files.iter().any(|(v, _)| v == name) {
+                    files.push((name.into(), path.clone()));
+                }
+            }
+        }
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+        files
+    }
+
+    /// Loads the class defined by the given script and returns its argument types.
+    ///
+    /// This does not change the main class of this [Vm].
+    pub fn arg_types(&self, script_path: &Path) -> Result<Vec<ArgType>> {
+        self.vm.scope(|vm| {
+            let cl: Table =
+                vm.run(Script::from_path(script_path).map_err(|e| Error::Loader(e.to_string()))?)?;
+            let types: Option<HashMap<String, Table>> = cl.get(c"argTypes")?;
+            let mut args = Vec::new();
+            for (name, info) in types.into_iter().flatten() {
+                let optional: Option<bool> = info.get(c"optional")?;
+                let variants: Option<Vec<String>> = info.get(c"enum")?;
+                args.push(ArgType {
+                    name,
+                    ty: info.get(c"type")?,
+                    optional: optional.unwrap_or(false),
+                    variants: variants.unwrap_or_default(),
+                });
+            }
+            args.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(args)
+        })
+    }
+
+    /// Loads the class defined by the given script and returns the external tools it requires.
+    pub fn requirements(&self, script_path: &Path) -> Result<Vec<Requirement>> {
+        self.vm.scope(|vm| {
+            let cl: Table =
+                vm.run(Script::from_path(script_path).map_err(|e| Error::Loader(e.to_string()))?)?;
+            let requires: Option<HashMap<String, Table>> = cl.get(c"requires")?;
+            let mut requirements = Vec::new();
+            for (name, info) in requires.into_iter().flatten() {
+                let args: Option<Vec<String>> = info.get(c"args")?;
+                let targets: Option<Vec<String>> = info.get(c"targets")?;
+                let libraries: Option<Vec<String>> = info.get(c"libraries")?;
+                requirements.push(Requirement {
+                    name,
+                    version: info.get(c"version")?,
+                    args: args.unwrap_or_default(),
+                    targets: targets.unwrap_or_default(),
+                    libraries: libraries.unwrap_or_default(),
+                });
+            }
+            requirements.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(requirements)
+        })
+    }
+
     pub fn run(&mut self, script_path: &Path) -> Result<()> {
         assert!(self.main_class.is_none());
         self.vm.scope(|vm| {
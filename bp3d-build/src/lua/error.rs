@@ -0,0 +1,254 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// Number of lines displayed around the offending line.
+const SNIPPET_CONTEXT: u32 = 2;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SnippetLine {
+    pub line: u32,
+    pub text: String,
+    pub highlight: bool,
+}
+
+/// A Lua error mapped back to its source.
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LuaError {
+    pub message: String,
+    pub chunk: Option<PathBuf>,
+    pub line: Option<u32>,
+    pub function: Option<String>,
+    pub snippet: Vec<SnippetLine>,
+    pub phase: Option<String>,
+    pub target: Option<String>,
+    pub backtrace: Option<String>,
+}
+
+struct Location<'a> {
+    chunk: &'a str,
+    line: u32,
+    /// Byte offset of the start of the location.
+    start: usize,
+    /// Byte offset of the end of the location, including the trailing ':'.
+    end: usize,
+}
+
+/// Finds the first `chunk.lua:line:` location in the given text.
+fn find_location(text: &str) -> Option<Location<'_>> {
+    let mut offset = 0;
+    while let Some(pos) = text[offset..].find(".lua:") {
+        let ext_end = offset + pos + 4;
+        let start = text[..ext_end]
+            .rfind(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+            .map(|v| v + 1)
+            .unwrap_or(0);
+        let digits = &text[ext_end + 1..];
+        let len = digits
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(digits.len());
+        if len > 0 && digits[len..].starts_with(':') {
+            if let Ok(line) = digits[..len].parse() {
+                let chunk = text[start..ext_end].trim_start_matches('@');
+                return Some(Location {
+                    chunk,
+                    line,
+                    start,
+                    end: ext_end + 1 + len + 1,
+                });
+            }
+        }
+        offset = ext_end;
+    }
+    None
+}
+
+/// Extracts the function name from a traceback frame description such as `in method 'build'`.
+fn function_name(desc: &str) -> String {
+    let desc = desc.trim().trim_start_matches("in ");
+    let mut parts = desc.split('\'');
+    match (parts.next(), parts.next()) {
+        (Some(_), Some(name)) => name.into(),
+        _ => desc.into(),
+    }
+}
+
+fn find_suffix(dir: &Path, suffix: &str) -> Option<PathBuf> {
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(path) = find_suffix(&path, suffix) {
+                return Some(path);
+            }
+        } else if path.to_string_lossy().ends_with(suffix) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Resolves a chunk name to a file, Lua truncates long chunk names with a leading `...`.
+fn resolve_chunk(chunk: &str, search_paths: &[PathBuf]) -> PathBuf {
+    match chunk.strip_prefix("...") {
+        Some(suffix) => search_paths
+            .iter()
+            .find_map(|v| find_suffix(v, suffix))
+            .unwrap_or_else(|| chunk.into()),
+        None => chunk.into(),
+    }
+}
+
+fn read_snippet(path: &Path, line: u32) -> Vec<SnippetLine> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let first = line.saturating_sub(SNIPPET_CONTEXT).max(1);
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, text)| (i as u32 + 1, text))
+        .skip_while(|(i, _)| *i < first)
+        .take_while(|(i, _)| *i <= line + SNIPPET_CONTEXT)
+        .map(|(i, text)| SnippetLine {
+            line: i,
+            text: text.into(),
+            highlight: i == line,
+        })
+        .collect()
+}
+
+impl LuaError {
+    /// Parses an error message and its optional Lua traceback.
+    ///
+    /// Truncated chunk names are resolved against the given search paths.
+    pub fn parse(message: &str, backtrace: Option<&str>, search_paths: &[PathBuf]) -> Self {
+        let message = match message.find("stack traceback:") {
+            Some(pos) => message[..pos].trim(),
+            None => message.trim(),
+        };
+        let backtrace = backtrace
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .map(String::from);
+        // Lua frames of the traceback as (location, description).
+        let frames: Vec<(Location, &str)> = backtrace
+            .iter()
+            .flat_map(|v| v.lines())
+            .filter_map(|v| {
+                let v = v.trim();
+                find_location(v).filter(|l| l.end <= v.len()).map(|l| {
+                    let end = l.end;
+                    (l, &v[end..])
+                })
+            })
+            .collect();
+        let location = find_location(message);
+        let (chunk, line, text) = match &location {
+            Some(l) => {
+                let text = format!("{}{}", &message[..l.start], message[l.end..].trim());
+                (Some(l.chunk), Some(l.line), text)
+            }
+            None => match frames.first() {
+                Some((l, _)) => (Some(l.chunk), Some(l.line), message.into()),
+                None => (None, None, message.into()),
+            },
+        };
+        let function = frames
+            .iter()
+            .find(|(l, _)| Some(l.chunk) == chunk && Some(l.line) == line)
+            .map(|(_, desc)| function_name(desc));
+        let chunk = chunk.map(|v| resolve_chunk(v, search_paths));
+        let snippet = match (&chunk, line) {
+            (Some(chunk), Some(line)) => read_snippet(chunk, line),
+            _ => Vec::new(),
+        };
+        LuaError {
+            message: text,
+            chunk,
+            line,
+            function,
+            snippet,
+            phase: None,
+            target: None,
+            backtrace,
+        }
+    }
+
+    /// Creates a new error from a Lua VM error.
+    pub fn new(err: &bp3d_lua::vm::error::Error, search_paths: &[PathBuf]) -> Self {
+        let backtrace = match err {
+            bp3d_lua::vm::error::Error::Runtime(e) => Some(e.backtrace().to_string()),
+            _ => None,
+        };
+        Self::parse(&err.to_string(), backtrace.as_deref(), search_paths)
+    }
+
+    /// Attaches the phase and target which were being run when the error occurred.
+    pub fn with_phase(mut self, phase: &str, target: Option<&str>) -> Self {
+        self.phase = Some(phase.into());
+        self.target = target.map(String::from);
+        self
+    }
+}
+
+impl Display for LuaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let (Some(chunk), Some(line)) = (&self.chunk, self.line) {
+            write!(f, "\n  --> {}:{}", chunk.display(), line)?;
+            if let Some(function) = &self.function {
+                write!(f, " in {}", function)?;
+            }
+        }
+        let width = self
+            .snippet
+            .last()
+            .map(|v| v.line.to_string().len())
+            .unwrap_or_default();
+        if !self.snippet.is_empty() {
+            write!(f, "\n{:width$} |", "")?;
+        }
+        for line in &self.snippet {
+            let marker = if line.highlight { '>' } else { '|' };
+            write!(f, "\n{:>width$} {} {}", line.line, marker, line.text)?;
+        }
+        match (&self.phase, &self.target) {
+            (Some(phase), Some(target)) => {
+                write!(f, "\n  = while running {} for target {}", phase, target)
+            }
+            (Some(phase), None) => write!(f, "\n  = while running {}", phase),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for LuaError {}
@@ -0,0 +1,135 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::archive;
+use crate::archive::{Entry, ExtractOptions, Format, Options};
+use crate::lua::{to_path, LibError};
+use bp3d_lua::decl_lib_func;
+use bp3d_lua::libs::files::SandboxPath;
+use bp3d_lua::libs::Lib;
+use bp3d_lua::util::Namespace;
+use bp3d_lua::vm::function::types::RFunction;
+use bp3d_lua::vm::table::Table;
+use bp3d_lua::vm::value::IntoLua;
+use bp3d_lua::vm::Vm;
+use bp3d_util::simple_error;
+use std::path::Path;
+
+simple_error! {
+    pub Error {
+        (impl From) Lib(LibError) => "{}",
+        Archive(archive::Error) => "{}"
+    }
+}
+
+/// Reads the format option, or infers it from the archive file name.
+fn get_format(path: &Path, options: Option<&Table>) -> Result<Format, Error> {
+    let name: Option<String> = match options {
+        Some(v) => v.get(c"format").map_err(LibError::Lua)?,
+        None => None,
+    };
+    match name {
+        Some(name) => {
+            Format::from_name(&name).ok_or(Error::Archive(archive::Error::UnknownFormat(name)))
+        }
+        None => Format::from_path(path).ok_or_else(|| {
+            Error::Archive(archive::Error::UnknownFormat(path.to_string_lossy().into()))
+        }),
+    }
+}
+
+struct Entries(Vec<Entry>);
+
+unsafe impl IntoLua for Entries {
+    fn into_lua(self, vm: &Vm) -> u16 {
+        let mut tbl = Table::with_capacity(vm, self.0.len(), 0);
+        for entry in self.0 {
+            let mut item = Table::with_capacity(vm, 0, 4);
+            item.set(c"name", &*entry.name).unwrap();
+            item.set(c"size", entry.size).unwrap();
+            item.set(c"mode", entry.mode).unwrap();
+            item.set(c"dir", entry.is_dir).unwrap();
+            tbl.push(item).unwrap();
+        }
+        1
+    }
+}
+
+decl_lib_func! {
+    fn archive_create<'a>(vm: &Vm, path: SandboxPath<'a>, source: SandboxPath<'a>, options: Option<Table>) -> Result<usize, Error> {
+        let path = to_path(vm, path)?;
+        let source = to_path(vm, source)?;
+        let mut opts = Options::new(get_format(&path, options.as_ref())?);
+        if let Some(options) = options {
+            opts.prefix = options.get(c"prefix").map_err(LibError::Lua)?;
+            opts.mtime = options.get(c"mtime").map_err(LibError::Lua)?;
+            opts.file_mode = options.get(c"fileMode").map_err(LibError::Lua)?;
+            opts.dir_mode = options.get(c"dirMode").map_err(LibError::Lua)?;
+            opts.level = options.get(c"level").map_err(LibError::Lua)?;
+        }
+        archive::create(&path, &source, &opts).map_err(Error::Archive)
+    }
+}
+
+decl_lib_func! {
+    fn archive_extract<'a>(vm: &Vm, path: SandboxPath<'a>, dest: SandboxPath<'a>, options: Option<Table>) -> Result<usize, Error> {
+        let path = to_path(vm, path)?;
+        let dest = to_path(vm, dest)?;
+        let format = get_format(&path, options.as_ref())?;
+        let mut opts = ExtractOptions::default();
+        if let Some(options) = options {
+            let strip: Option<usize> = options.get(c"strip").map_err(LibError::Lua)?;
+            let preserve_modes: Option<bool> = options.get(c"preserveModes").map_err(LibError::Lua)?;
+            opts.strip = strip.unwrap_or_default();
+            opts.preserve_modes = preserve_modes.unwrap_or(true);
+        }
+        archive::extract(&path, &dest, format, &opts).map_err(Error::Archive)
+    }
+}
+
+decl_lib_func! {
+    fn archive_list<'a>(vm: &Vm, path: SandboxPath<'a>, options: Option<Table>) -> Result<Entries, Error> {
+        let path = to_path(vm, path)?;
+        let format = get_format(&path, options.as_ref())?;
+        archive::list(&path, format).map(Entries).map_err(Error::Archive)
+    }
+}
+
+pub struct ArchiveLib;
+
+impl Lib for ArchiveLib {
+    const NAMESPACE: &'static str = "bp3d.build.archive";
+
+    fn load(&self, namespace: &mut Namespace) -> bp3d_lua::vm::Result<()> {
+        namespace.add([
+            ("create", RFunction::wrap(archive_create)),
+            ("extract", RFunction::wrap(archive_extract)),
+            ("list", RFunction::wrap(archive_list)),
+        ])
+    }
+}
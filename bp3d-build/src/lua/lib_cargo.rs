@@ -0,0 +1,276 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::lua::lib_command::CommandInfo;
+use crate::lua::{to_path, LibError};
+use bp3d_lua::decl_lib_func;
+use bp3d_lua::libs::files::{SandboxPath, SandboxPathBuf};
+use bp3d_lua::libs::Lib;
+use bp3d_lua::util::Namespace;
+use bp3d_lua::vm::function::types::RFunction;
+use bp3d_lua::vm::table::Table;
+use bp3d_lua::vm::Vm;
+use bp3d_util::simple_error;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+simple_error! {
+    pub Error {
+        (impl From) Lib(LibError) => "{}",
+        Json(serde_json::Error) => "json error: {}",
+        Cargo(String) => "cargo metadata failed: {}"
+    }
+}
+
+/// A target of a cargo package (lib, bin, test, example, etc).
+#[derive(Deserialize)]
+struct Target {
+    name: String,
+    kind: Vec<String>,
+    crate_types: Vec<String>,
+    src_path: PathBuf,
+    #[serde(default)]
+    required_features: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Dependency {
+    name: String,
+    req: String,
+    kind: Option<String>,
+    rename: Option<String>,
+    optional: bool,
+    uses_default_features: bool,
+    features: Vec<String>,
+    target: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    name: String,
+    version: String,
+    id: String,
+    manifest_path: PathBuf,
+    targets: Vec<Target>,
+    features: BTreeMap<String, Vec<String>>,
+    dependencies: Vec<Dependency>,
+}
+
+/// The output of `cargo metadata --format-version 1`.
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    workspace_members: Vec<String>,
+    workspace_root: PathBuf,
+    target_directory: PathBuf,
+}
+
+/// Options of the cargo metadata command, read from an optional Lua table.
+#[derive(Default)]
+struct Options {
+    dependencies: bool,
+    all_features: bool,
+    features: Vec<String>,
+    filter_platform: Option<String>,
+}
+
+impl Options {
+    fn from_table(table: Option<Table>) -> Result<Self, Error> {
+        let Some(table) = table else {
+            return Ok(Self::default());
+        };
+        let dependencies: Option<bool> = table.get(c"dependencies").map_err(LibError::Lua)?;
+        let all_features: Option<bool> = table.get(c"allFeatures").map_err(LibError::Lua)?;
+        let features: Option<Vec<String>> = table.get(c"features").map_err(LibError::Lua)?;
+        Ok(Self {
+            dependencies: dependencies.unwrap_or_default(),
+            all_features: all_features.unwrap_or_default(),
+            features: features.unwrap_or_default(),
+            filter_platform: table.get(c"target").map_err(LibError::Lua)?,
+        })
+    }
+}
+
+fn run_metadata(vm: &Vm, path: PathBuf, options: &Options) -> Result<Metadata, Error> {
+    let mut args = vec!["metadata", "--format-version", "1"];
+    if !options.dependencies {
+        args.push("--no-deps");
+    }
+    let features = options.features.join(",");
+    if options.all_features {
+        args.push("--all-features");
+    } else if !options.features.is_empty() {
+        args.extend(["--features", features.as_str()]);
+    }
+    if let Some(target) = &options.filter_platform {
+        args.extend(["--filter-platform", target.as_str()]);
+    }
+    let mut info = CommandInfo::new("cargo", args);
+    info.workdir = Some(path);
+    let output = info.execute(vm, true)?;
+    if !output.success {
+        return Err(Error::Cargo(output.stderr.trim().into()));
+    }
+    serde_json::from_str(&output.stdout).map_err(Error::Json)
+}
+
+fn strings<'a>(vm: &'a Vm, values: &[String]) -> bp3d_lua::vm::Result<Table<'a>> {
+    let mut tbl = Table::with_capacity(vm, values.len(), 0);
+    for value in values {
+        tbl.push(&**value)?;
+    }
+    Ok(tbl)
+}
+
+fn convert_target<'a>(vm: &'a Vm, target: &Target) -> bp3d_lua::vm::Result<Table<'a>> {
+    let mut tbl = Table::with_capacity(vm, 0, 5);
+    tbl.set(c"name", &*target.name)?;
+    tbl.set(c"kind", strings(vm, &target.kind)?)?;
+    tbl.set(c"crateTypes", strings(vm, &target.crate_types)?)?;
+    tbl.set(
+        c"srcPath",
+        SandboxPathBuf::from_path_unchecked(target.src_path.clone()),
+    )?;
+    tbl.set(c"requiredFeatures", strings(vm, &target.required_features)?)?;
+    Ok(tbl)
+}
+
+fn convert_dependency<'a>(vm: &'a Vm, dep: &Dependency) -> bp3d_lua::vm::Result<Table<'a>> {
+    let mut tbl = Table::with_capacity(vm, 0, 8);
+    tbl.set(c"name", &*dep.name)?;
+    tbl.set(c"req", &*dep.req)?;
+    // Cargo reports normal dependencies with a null kind.
+    tbl.set(c"kind", dep.kind.as_deref().unwrap_or("normal"))?;
+    tbl.set(c"rename", dep.rename.as_deref())?;
+    tbl.set(c"optional", dep.optional)?;
+    tbl.set(c"defaultFeatures", dep.uses_default_features)?;
+    tbl.set(c"features", strings(vm, &dep.features)?)?;
+    tbl.set(c"target", dep.target.as_deref())?;
+    Ok(tbl)
+}
+
+fn convert_package<'a>(
+    vm: &'a Vm,
+    package: &Package,
+    member: bool,
+) -> bp3d_lua::vm::Result<Table<'a>> {
+    let mut tbl = Table::with_capacity(vm, 0, 8);
+    tbl.set(c"name", &*package.name)?;
+    tbl.set(c"version", &*package.version)?;
+    tbl.set(c"id", &*package.id)?;
+    tbl.set(c"member", member)?;
+    tbl.set(
+        c"manifestPath",
+        SandboxPathBuf::from_path_unchecked(package.manifest_path.clone()),
+    )?;
+    let mut targets = Table::with_capacity(vm, package.targets.len(), 0);
+    for target in &package.targets {
+        targets.push(convert_target(vm, target)?)?;
+    }
+    tbl.set(c"targets", targets)?;
+    let mut features = Table::with_capacity(vm, 0, package.features.len());
+    for (name, values) in &package.features {
+        features.set(&**name, strings(vm, values)?)?;
+    }
+    tbl.set(c"features", features)?;
+    let mut dependencies = Table::with_capacity(vm, package.dependencies.len(), 0);
+    for dep in &package.dependencies {
+        dependencies.push(convert_dependency(vm, dep)?)?;
+    }
+    tbl.set(c"dependencies", dependencies)?;
+    Ok(tbl)
+}
+
+impl Metadata {
+    fn is_member(&self, package: &Package) -> bool {
+        self.workspace_members.contains(&package.id)
+    }
+
+    fn packages<'a>(&self, vm: &'a Vm, members_only: bool) -> bp3d_lua::vm::Result<Table<'a>> {
+        let mut packages = Table::with_capacity(vm, self.packages.len(), 0);
+        for package in &self.packages {
+            let member = self.is_member(package);
+            if member || !members_only {
+                packages.push(convert_package(vm, package, member)?)?;
+            }
+        }
+        Ok(packages)
+    }
+
+    fn to_table<'a>(&self, vm: &'a Vm) -> bp3d_lua::vm::Result<Table<'a>> {
+        let mut tbl = Table::with_capacity(vm, 0, 3);
+        tbl.set(
+            c"workspaceRoot",
+            SandboxPathBuf::from_path_unchecked(self.workspace_root.clone()),
+        )?;
+        tbl.set(
+            c"targetDirectory",
+            SandboxPathBuf::from_path_unchecked(self.target_directory.clone()),
+        )?;
+        tbl.set(c"packages", self.packages(vm, false)?)?;
+        Ok(tbl)
+    }
+}
+
+// Dependencies are only resolved when the dependencies option is true.
+decl_lib_func! {
+    fn cargo_metadata<'a>(vm: &'a Vm, path: SandboxPath<'a>, options: Option<Table>) -> Result<Table<'a>, Error> {
+        let options = Options::from_table(options)?;
+        let metadata = run_metadata(vm, to_path(vm, path)?, &options)?;
+        Ok(metadata.to_table(vm).map_err(LibError::Lua)?)
+    }
+}
+
+decl_lib_func! {
+    fn cargo_members<'a>(vm: &'a Vm, path: SandboxPath<'a>) -> Result<Table<'a>, Error> {
+        let metadata = run_metadata(vm, to_path(vm, path)?, &Options::default())?;
+        Ok(metadata.packages(vm, true).map_err(LibError::Lua)?)
+    }
+}
+
+decl_lib_func! {
+    fn cargo_target_directory<'a>(vm: &Vm, path: SandboxPath<'a>) -> Result<SandboxPathBuf, Error> {
+        let metadata = run_metadata(vm, to_path(vm, path)?, &Options::default())?;
+        Ok(SandboxPathBuf::from_path_unchecked(metadata.target_directory))
+    }
+}
+
+pub struct CargoLib;
+
+impl Lib for CargoLib {
+    const NAMESPACE: &'static str = "bp3d.build.cargo";
+
+    fn load(&self, namespace: &mut Namespace) -> bp3d_lua::vm::Result<()> {
+        namespace.add([
+            ("metadata", RFunction::wrap(cargo_metadata)),
+            ("members", RFunction::wrap(cargo_members)),
+            ("targetDirectory", RFunction::wrap(cargo_target_directory)),
+        ])
+    }
+}
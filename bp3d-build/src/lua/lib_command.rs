@@ -26,7 +26,10 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::lua::core::dump_backtrace;
+use crate::lua::obj_process::Process;
+use crate::lua::{lib_test, state, to_path, LibError};
+use crate::report;
+use crate::tools::find_bundled;
 use bp3d_lua::decl_lib_func;
 use bp3d_lua::libs::files::{SandboxPath, SandboxPathBuf};
 use bp3d_lua::libs::Lib;
@@ -36,134 +39,296 @@ use bp3d_lua::util::Namespace;
 use bp3d_lua::vm::function::types::RFunction;
 use bp3d_lua::vm::table::Table;
 use bp3d_lua::vm::thread::value::Thread;
+use bp3d_lua::vm::userdata::case::Camel;
 use bp3d_lua::vm::Vm;
-use bp3d_os::assets::get_executable_path;
-use bp3d_util::simple_error;
 use std::collections::HashMap;
-use std::ffi::OsStr;
-use std::io::{BufRead, BufReader};
-use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::ffi::CStr;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
-simple_error! {
-    pub Error {
-        Lua(bp3d_lua::vm::error::Error) => "lua error: {}",
-        Io(std::io::Error) => "io error: {}"
-    }
+pub(crate) enum Stdin {
+    Data(String),
+    File(PathBuf),
 }
 
-struct CommandInfo {
+pub(crate) struct CommandInfo {
     pub exe: SandboxPathBuf,
     pub args: Option<Vec<SandboxPathBuf>>,
     pub env: Option<HashMap<String, String>>,
+    pub clear_env: bool,
+    pub remove_env: Vec<String>,
     pub workdir: Option<PathBuf>,
+    pub stdin: Option<Stdin>,
+    pub stdout: Option<PathBuf>,
+    pub stderr: Option<PathBuf>,
+    pub timeout: Option<Duration>,
+}
+
+fn get_path(vm: &Vm, table: &Table, key: &CStr) -> Result<Option<PathBuf>, LibError> {
+    let path: Option<SandboxPath> = table.get(key).map_err(LibError::Lua)?;
+    path.map(|v| to_path(vm, v)).transpose()
 }
 
 impl CommandInfo {
-    pub fn from_table(vm: &Vm, table: &Table) -> bp3d_lua::vm::Result<Self> {
-        let workdir: Option<SandboxPath> = table.get(c"workdir")?;
-        let workdir = workdir
-            .map(|v| v.to_path(vm).ok().map(PathBuf::from))
-            .flatten();
+    /// Creates a command running exe with the given arguments in the current environment.
+    pub fn new<'a>(exe: &str, args: impl IntoIterator<Item = &'a str>) -> Self {
+        CommandInfo {
+            exe: SandboxPathBuf::from_path_unchecked(exe.into()),
+            args: Some(
+                args.into_iter()
+                    .map(|v| SandboxPathBuf::from_path_unchecked(v.into()))
+                    .collect(),
+            ),
+            env: None,
+            clear_env: false,
+            remove_env: Vec::new(),
+            workdir: None,
+            stdin: None,
+            stdout: None,
+            stderr: None,
+            timeout: None,
+        }
+    }
+
+    pub fn from_table(vm: &Vm, table: &Table) -> Result<Self, LibError> {
+        let clear_env: Option<bool> = table.get(c"clearEnv").map_err(LibError::Lua)?;
+        let remove_env: Option<Vec<String>> = table.get(c"removeEnv").map_err(LibError::Lua)?;
+        let stdin: Option<String> = table.get(c"stdin").map_err(LibError::Lua)?;
+        let stdin = match stdin {
+            Some(data) => Some(Stdin::Data(data)),
+            None => get_path(vm, table, c"stdinFile")?.map(Stdin::File),
+        };
+        let timeout: Option<f64> = table.get(c"timeout").map_err(LibError::Lua)?;
         Ok(CommandInfo {
-            exe: table.get(c"exe")?,
-            args: table.get(c"args")?,
-            env: table.get(c"env")?,
-            workdir,
+            exe: table.get(c"exe").map_err(LibError::Lua)?,
+            args: table.get(c"args").map_err(LibError::Lua)?,
+            env: table.get(c"env").map_err(LibError::Lua)?,
+            clear_env: clear_env.unwrap_or(false),
+            remove_env: remove_env.unwrap_or_default(),
+            workdir: get_path(vm, table, c"workdir")?,
+            stdin,
+            stdout: get_path(vm, table, c"stdout")?,
+            stderr: get_path(vm, table, c"stderr")?,
+            timeout: timeout.map(|v| Duration::from_secs_f64(v.max(0.0))),
         })
     }
-}
 
-fn join_file_cross_platform(exe_name: &OsStr, path: &Path) -> PathBuf {
-    #[cfg(target_os = "windows")]
-    let exe_name = {
-        let mut exe_name = std::ffi::os_str::OsString::from(exe_name);
-        exe_name.push(".exe");
-        exe_name
-    };
-    #[cfg(not(target_os = "windows"))]
-    let exe_name = exe_name.to_os_string();
-    path.join(&exe_name)
-}
+    /// Returns the data to write to stdin of the command, if any.
+    pub fn stdin_data(&self) -> Option<&str> {
+        match &self.stdin {
+            Some(Stdin::Data(data)) => Some(data),
+            _ => None,
+        }
+    }
 
-impl CommandInfo {
-    pub fn into_command(self) -> Command {
-        let self_exe_path = get_executable_path()
-            .map(|path| join_file_cross_platform(self.exe.as_os_str(), &path))
-            .unwrap_or(PathBuf::from("/does/not/exist"));
-        let mut cmd = if self_exe_path.exists() {
-            Command::new(self_exe_path)
-        } else {
-            Command::new(self.exe.as_os_str())
+    /// Creates the command, stdio streams which are not redirected use the default.
+    pub fn to_command(&self, default: fn() -> Stdio) -> std::io::Result<Command> {
+        let mut cmd = match find_bundled(self.exe.as_os_str()) {
+            Some(path) => Command::new(path),
+            None => Command::new(self.exe.as_os_str()),
         };
-        if let Some(args) = self.args {
+        if let Some(args) = &self.args {
             cmd.args(args.iter().map(|v| v.as_os_str()));
         }
-        if let Some(env) = self.env {
+        if self.clear_env {
+            cmd.env_clear();
+        }
+        for name in &self.remove_env {
+            cmd.env_remove(name);
+        }
+        if let Some(env) = &self.env {
             cmd.envs(env.iter().map(|(k, v)| (k, v)));
         }
-        if let Some(workdir) = self.workdir {
-            cmd.current_dir(&workdir);
+        if let Some(workdir) = &self.workdir {
+            cmd.current_dir(workdir);
+        }
+        cmd.stdin(match &self.stdin {
+            Some(Stdin::Data(_)) => Stdio::piped(),
+            Some(Stdin::File(path)) => Stdio::from(File::open(path)?),
+            None => default(),
+        });
+        cmd.stdout(match &self.stdout {
+            Some(path) => Stdio::from(File::create(path)?),
+            None => default(),
+        });
+        cmd.stderr(match &self.stderr {
+            Some(path) => Stdio::from(File::create(path)?),
+            None => default(),
+        });
+        Ok(cmd)
+    }
+
+    /// Runs the command to completion, killing it when the timeout expires.
+    ///
+    /// When capture is true, stdout and stderr are collected unless redirected. In test mode,
+    /// the command is only recorded and its scripted result is returned.
+    pub fn execute(&self, vm: &Vm, capture: bool) -> Result<Output, LibError> {
+        if state::test_root(vm).map_err(LibError::Lua)?.is_some() {
+            return lib_test::output(vm, self);
+        }
+        self.run(capture).map_err(LibError::Io)
+    }
+
+    fn run(&self, capture: bool) -> std::io::Result<Output> {
+        let default = match capture {
+            true => Stdio::piped,
+            false => Stdio::inherit,
+        };
+        let mut cmd = self.to_command(default)?;
+        report::command(&mut cmd, |cmd| {
+            let mut child = cmd.spawn()?;
+            std::thread::scope(|scope| {
+                let stdin = child.stdin.take().zip(self.stdin_data());
+                if let Some((mut stdin, data)) = stdin {
+                    scope.spawn(move || {
+                        let _ = stdin.write_all(data.as_bytes());
+                    });
+                }
+                let stdout = child.stdout.take().map(|v| scope.spawn(|| read_all(v)));
+                let stderr = child.stderr.take().map(|v| scope.spawn(|| read_all(v)));
+                let (status, timed_out) = wait_timeout(&mut child, self.timeout)?;
+                Ok(Output {
+                    stdout: stdout.map(|v| v.join().unwrap()).unwrap_or_default(),
+                    stderr: stderr.map(|v| v.join().unwrap()).unwrap_or_default(),
+                    success: status.success(),
+                    code: status.code(),
+                    timed_out,
+                })
+            })
+        })
+    }
+}
+
+fn read_all(mut stream: impl Read) -> String {
+    let mut buf = Vec::new();
+    let _ = stream.read_to_end(&mut buf);
+    String::from_utf8_lossy(&buf).into()
+}
+
+/// Waits for a child process, killing it when the timeout expires.
+///
+/// Returns the exit status and whether the timeout expired.
+pub(crate) fn wait_timeout(
+    child: &mut Child,
+    timeout: Option<Duration>,
+) -> std::io::Result<(ExitStatus, bool)> {
+    let Some(timeout) = timeout else {
+        return child.wait().map(|v| (v, false));
+    };
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok((status, false));
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            return child.wait().map(|v| (v, true));
         }
-        cmd
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// The result of a command.
+pub(crate) struct Output {
+    pub stdout: String,
+    pub stderr: String,
+    pub success: bool,
+    pub code: Option<i32>,
+    pub timed_out: bool,
+}
+
+impl Output {
+    pub fn to_table<'a>(&self, vm: &'a Vm) -> Result<Table<'a>, LibError> {
+        let mut tbl = Table::with_capacity(vm, 0, 5);
+        tbl.set(c"stdout", &*self.stdout).map_err(LibError::Lua)?;
+        tbl.set(c"stderr", &*self.stderr).map_err(LibError::Lua)?;
+        tbl.set(c"success", self.success).map_err(LibError::Lua)?;
+        tbl.set(c"code", self.code).map_err(LibError::Lua)?;
+        tbl.set(c"timedOut", self.timed_out)
+            .map_err(LibError::Lua)?;
+        Ok(tbl)
     }
 }
 
 decl_lib_func! {
-    fn command_run(vm: &Vm, table: Table) -> Result<(bool, Option<i32>), Error> {
-        let info = CommandInfo::from_table(vm, &table).map_err(Error::Lua)?;
-        let mut cmd = info.into_command();
-        let status = cmd.status().map_err(Error::Io)?;
-        Ok((status.success(), status.code()))
+    fn command_run(vm: &Vm, table: Table) -> Result<(bool, Option<i32>), LibError> {
+        let info = CommandInfo::from_table(vm, &table)?;
+        let output = info.execute(vm, false)?;
+        Ok((output.success, output.code))
     }
 }
 
 decl_lib_func! {
-    fn command_spawn(vm: &Vm, table: Table, event_thread: Thread) -> Result<(bool, Option<i32>), Error> {
-        let info = CommandInfo::from_table(vm, &table).map_err(Error::Lua)?;
-        let mut cmd = info.into_command();
-        let mut running = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn().map_err(Error::Io)?;
-        let out = running.stdout.take().unwrap();
-        let err = running.stderr.take().unwrap();
+    fn command_spawn(vm: &Vm, table: Table, event_thread: Thread) -> Result<(bool, Option<i32>, bool), LibError> {
+        let info = CommandInfo::from_table(vm, &table)?;
+        let mut cmd = info.to_command(Stdio::piped).map_err(LibError::Io)?;
+        if info.stdin.is_none() {
+            cmd.stdin(Stdio::inherit());
+        }
+        let start = Instant::now();
+        let mut running = cmd.spawn().map_err(LibError::Io)?;
+        let stdin = running.stdin.take().zip(info.stdin_data());
+        let out = running.stdout.take();
+        let err = running.stderr.take();
         let event_thread = Mutex::new(unsafe { UnsafeLuaThread::wrap(LuaThread::create(event_thread)) });
+        // The first error raised by the event handler, further events are dropped.
+        let error = Mutex::new(None);
         let val = std::thread::scope(|scope| {
-            let th = scope.spawn(|| {
-                let event_thread = &event_thread;
-                let mut reader = BufReader::new(out).lines();
-                while let Some(line) = reader.next() {
-                    if let Ok(line) = line {
-                        let res = event_thread.lock().unwrap().as_thread().resume::<()>(("out", line));
-                        let _ = dump_backtrace(res);
-                    }
-                }
-            });
-            let th1 = scope.spawn(|| {
+            if let Some((mut stdin, data)) = stdin {
+                scope.spawn(move || {
+                    let _ = stdin.write_all(data.as_bytes());
+                });
+            }
+            let dispatch = |name: &'static str, stream: Box<dyn Read + Send>| {
                 let event_thread = &event_thread;
-                let mut reader = BufReader::new(err).lines();
-                while let Some(line) = reader.next() {
-                    if let Ok(line) = line {
-                        let res = event_thread.lock().unwrap().as_thread().resume::<()>(("err", line));
-                        let _ = dump_backtrace(res);
+                let error = &error;
+                move || {
+                    for line in BufReader::new(stream).lines().map_while(|v| v.ok()) {
+                        let mut error = error.lock().unwrap();
+                        if error.is_some() {
+                            continue;
+                        }
+                        let res = event_thread.lock().unwrap().as_thread().resume::<()>((name, line));
+                        if let Err(e) = res {
+                            *error = Some(e);
+                        }
                     }
                 }
-            });
-            let status = running.wait().map_err(Error::Io)?;
-            th.join().unwrap();
-            th1.join().unwrap();
-            Ok((status.success(), status.code()))
+            };
+            let th = out.map(|v| scope.spawn(dispatch("out", Box::new(v))));
+            let th1 = err.map(|v| scope.spawn(dispatch("err", Box::new(v))));
+            let (status, timed_out) = wait_timeout(&mut running, info.timeout).map_err(LibError::Io)?;
+            for th in th.into_iter().chain(th1) {
+                th.join().unwrap();
+            }
+            report::record_command(&cmd, start.elapsed());
+            Ok((status.success(), status.code(), timed_out))
         });
         event_thread.into_inner().unwrap().delete(vm);
-        val
+        match error.into_inner().unwrap() {
+            Some(e) => Err(LibError::Lua(e)),
+            None => val,
+        }
+    }
+}
+
+// Returns a table of stdout, stderr, success, code and timedOut.
+decl_lib_func! {
+    fn command_output<'a>(vm: &'a Vm, table: Table) -> Result<Table<'a>, LibError> {
+        let info = CommandInfo::from_table(vm, &table)?;
+        info.execute(vm, true)?.to_table(vm)
     }
 }
 
 decl_lib_func! {
-    fn command_output(vm: &Vm, table: Table) -> Result<String, Error> {
-        let info = CommandInfo::from_table(vm, &table).map_err(Error::Lua)?;
-        let mut cmd = info.into_command();
-        let output = cmd.output().map_err(Error::Io)?;
-        Ok(String::from_utf8_lossy(&output.stdout).into())
+    fn command_start(vm: &Vm, table: Table) -> Result<Process, LibError> {
+        let info = CommandInfo::from_table(vm, &table)?;
+        let cmd = info.to_command(Stdio::piped).map_err(LibError::Io)?;
+        Process::start(cmd, info.stdin_data(), info.timeout).map_err(LibError::Io)
     }
 }
 
@@ -177,6 +342,8 @@ impl Lib for CommandLib {
             ("run", RFunction::wrap(command_run)),
             ("output", RFunction::wrap(command_output)),
             ("spawn", RFunction::wrap(command_spawn)),
-        ])
+            ("start", RFunction::wrap(command_start)),
+        ])?;
+        namespace.add_userdata::<Process>("Process", Camel)
     }
 }
@@ -26,6 +26,8 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use crate::lua::paths::{resources, Error};
+use crate::tools::library_path;
 use bp3d_lua::decl_lib_func;
 use bp3d_lua::libs::files::{SandboxPath, SandboxPathBuf};
 use bp3d_lua::libs::Lib;
@@ -34,32 +36,22 @@ use bp3d_lua::vm::function::types::RFunction;
 use bp3d_os::assets::get_executable_path;
 
 decl_lib_func! {
-    fn get_res_path() -> SandboxPathBuf {
-        //FIXME: Find a better way to get the path info
-        let exe = get_executable_path().unwrap();
-        let mut path = exe.join("../usr/share");
-        if !path.exists() {
-            path = exe.join("../../res");
-        }
-        SandboxPathBuf::from_path_unchecked(path)
+    fn get_res_path() -> Result<SandboxPathBuf, Error> {
+        resources().map(SandboxPathBuf::from_path_unchecked)
     }
 }
 
 decl_lib_func! {
-    fn get_exe_path() -> SandboxPathBuf {
-        let exe = get_executable_path().unwrap();
-        SandboxPathBuf::from_path_unchecked(exe.join("bp3d"))
+    fn get_exe_path() -> Result<SandboxPathBuf, Error> {
+        let exe = get_executable_path().ok_or(Error::NoExecutable)?;
+        Ok(SandboxPathBuf::from_path_unchecked(exe.join("bp3d")))
     }
 }
 
 decl_lib_func! {
-    fn get_lib_path() -> SandboxPathBuf {
-        let exe = get_executable_path().unwrap();
-        let mut path = exe.join("../lib");
-        if !path.exists() {
-            path = exe;
-        }
-        SandboxPathBuf::from_path_unchecked(path)
+    fn get_lib_path() -> Result<SandboxPathBuf, Error> {
+        let path = library_path().ok_or(Error::NoExecutable)?;
+        Ok(SandboxPathBuf::from_path_unchecked(path))
     }
 }
 
@@ -0,0 +1,270 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::lua::{to_path, LibError};
+use bp3d_lua::decl_lib_func;
+use bp3d_lua::libs::files::SandboxPath;
+use bp3d_lua::libs::Lib;
+use bp3d_lua::util::Namespace;
+use bp3d_lua::vm::function::types::RFunction;
+use bp3d_lua::vm::table::Table;
+use bp3d_lua::vm::Vm;
+use bp3d_util::simple_error;
+use sha2::Digest;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path};
+
+simple_error! {
+    pub Error {
+        (impl From) Lib(LibError) => "{}",
+        UnknownAlgorithm(String) => "unknown hash algorithm: {}",
+        InvalidLine(usize) => "invalid checksum line: {}",
+        UnsafeName(String) => "unsafe file name in checksum manifest: {}"
+    }
+}
+
+enum Hasher {
+    Sha256(sha2::Sha256),
+    Sha512(sha2::Sha512),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    /// Creates a hasher from an algorithm name, defaults to sha256.
+    fn new(algorithm: Option<&str>) -> Result<Self, Error> {
+        match algorithm.unwrap_or("sha256") {
+            "sha256" => Ok(Hasher::Sha256(sha2::Sha256::new())),
+            "sha512" => Ok(Hasher::Sha512(sha2::Sha512::new())),
+            "blake3" => Ok(Hasher::Blake3(Box::default())),
+            v => Err(Error::UnknownAlgorithm(v.into())),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(v) => v.update(data),
+            Hasher::Sha512(v) => v.update(data),
+            Hasher::Blake3(v) => {
+                v.update(data);
+            }
+        }
+    }
+
+    fn finish(self) -> String {
+        let bytes = match self {
+            Hasher::Sha256(v) => v.finalize().to_vec(),
+            Hasher::Sha512(v) => v.finalize().to_vec(),
+            Hasher::Blake3(v) => v.finalize().as_bytes().to_vec(),
+        };
+        bytes
+            .iter()
+            .fold(String::with_capacity(bytes.len() * 2), |mut out, v| {
+                let _ = write!(out, "{:02x}", v);
+                out
+            })
+    }
+}
+
+fn hash_file(path: &Path, algorithm: Option<&str>) -> Result<String, Error> {
+    let mut hasher = Hasher::new(algorithm)?;
+    let mut file = File::open(path).map_err(LibError::Io)?;
+    let mut buffer = [0; 65536];
+    loop {
+        let len = file.read(&mut buffer).map_err(LibError::Io)?;
+        if len == 0 {
+            break;
+        }
+        hasher.update(&buffer[..len]);
+    }
+    Ok(hasher.finish())
+}
+
+/// Returns the name of a file in a checksum manifest stored in the given directory.
+fn entry_name(dir: &Path, path: &Path) -> String {
+    let name = match path.strip_prefix(dir) {
+        Ok(v) => v,
+        Err(_) => path.file_name().map(Path::new).unwrap_or(path),
+    };
+    name.to_string_lossy().replace('\\', "/")
+}
+
+/// Returns true if the given manifest entry name stays inside the manifest directory.
+fn is_safe_name(name: &str) -> bool {
+    !name.is_empty()
+        && Path::new(name)
+            .components()
+            .all(|v| matches!(v, Component::Normal(_) | Component::CurDir))
+}
+
+/// Parses the lines of a checksum manifest into hash and name pairs, blank lines are skipped.
+fn parse_sums(data: &str) -> Result<Vec<(&str, &str)>, Error> {
+    let mut entries = Vec::new();
+    for (i, line) in data.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        // The separator is a space followed by a space (text mode) or a star (binary mode).
+        let (hash, name) = line
+            .split_once(' ')
+            .and_then(|(hash, name)| Some((hash, name.strip_prefix([' ', '*'])?)))
+            .ok_or(Error::InvalidLine(i + 1))?;
+        if !is_safe_name(name) {
+            return Err(Error::UnsafeName(name.into()));
+        }
+        entries.push((hash, name));
+    }
+    Ok(entries)
+}
+
+decl_lib_func! {
+    fn hash_string(data: &[u8], algorithm: Option<&str>) -> Result<String, Error> {
+        let mut hasher = Hasher::new(algorithm)?;
+        hasher.update(data);
+        Ok(hasher.finish())
+    }
+}
+
+decl_lib_func! {
+    fn hash_file_lua<'a>(vm: &Vm, path: SandboxPath<'a>, algorithm: Option<&str>) -> Result<String, Error> {
+        hash_file(&to_path(vm, path)?, algorithm)
+    }
+}
+
+// Writes a checksum manifest in the format of sha256sum, names are relative to the manifest.
+decl_lib_func! {
+    fn hash_write_sums<'a>(vm: &Vm, path: SandboxPath<'a>, files: Vec<SandboxPath<'a>>, algorithm: Option<&str>) -> Result<(), Error> {
+        let path = to_path(vm, path)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let mut out = String::new();
+        for file in files {
+            let file = to_path(vm, file)?;
+            let hash = hash_file(&file, algorithm)?;
+            let _ = writeln!(out, "{}  {}", hash, entry_name(dir, &file));
+        }
+        std::fs::write(path, out).map_err(|e| LibError::Io(e).into())
+    }
+}
+
+// Verifies a checksum manifest, returns true when all files match followed by the list of
+// files which are missing or do not match.
+decl_lib_func! {
+    fn hash_verify_sums<'a>(vm: &'a Vm, path: SandboxPath<'a>, algorithm: Option<&str>) -> Result<(bool, Table<'a>), Error> {
+        let path = to_path(vm, path)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let data = std::fs::read_to_string(&path).map_err(LibError::Io)?;
+        let mut failures = Table::with_capacity(vm, 0, 0);
+        let mut ok = true;
+        for (hash, name) in parse_sums(&data)? {
+            let matches = match hash_file(&dir.join(name), algorithm) {
+                Ok(v) => v.eq_ignore_ascii_case(hash),
+                Err(Error::Lib(LibError::Io(_))) => false,
+                Err(e) => return Err(e),
+            };
+            if !matches {
+                ok = false;
+                failures.push(name).map_err(LibError::Lua)?;
+            }
+        }
+        Ok((ok, failures))
+    }
+}
+
+pub struct HashLib;
+
+impl Lib for HashLib {
+    const NAMESPACE: &'static str = "bp3d.build.hash";
+
+    fn load(&self, namespace: &mut Namespace) -> bp3d_lua::vm::Result<()> {
+        namespace.add([
+            ("string", RFunction::wrap(hash_string)),
+            ("file", RFunction::wrap(hash_file_lua)),
+            ("writeSums", RFunction::wrap(hash_write_sums)),
+            ("verifySums", RFunction::wrap(hash_verify_sums)),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_text_and_binary() {
+        let data = "abc  a.txt\n\nDEF *dir/b.bin\n";
+        let entries = parse_sums(data).unwrap();
+        assert_eq!(entries, vec![("abc", "a.txt"), ("DEF", "dir/b.bin")]);
+    }
+
+    #[test]
+    fn parse_invalid_line() {
+        assert!(matches!(
+            parse_sums("abc  a.txt\nabc\n"),
+            Err(Error::InvalidLine(2))
+        ));
+        assert!(matches!(
+            parse_sums("abc -a.txt"),
+            Err(Error::InvalidLine(1))
+        ));
+    }
+
+    #[test]
+    fn parse_escaping_names() {
+        for name in ["../a.txt", "dir/../../a.txt", "/etc/passwd", "./.."] {
+            let data = format!("abc  {}", name);
+            assert!(
+                matches!(parse_sums(&data), Err(Error::UnsafeName(_))),
+                "{}",
+                name
+            );
+        }
+        assert!(matches!(parse_sums("abc  "), Err(Error::UnsafeName(_))));
+    }
+
+    #[test]
+    fn entry_names() {
+        let dir = Path::new("/dist");
+        assert_eq!(entry_name(dir, Path::new("/dist/a/b.txt")), "a/b.txt");
+        assert_eq!(entry_name(dir, Path::new("/other/c.txt")), "c.txt");
+    }
+
+    #[test]
+    fn known_hashes() {
+        let mut hasher = Hasher::new(None).unwrap();
+        hasher.update(b"abc");
+        assert_eq!(
+            hasher.finish(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(matches!(
+            Hasher::new(Some("md5")),
+            Err(Error::UnknownAlgorithm(_))
+        ));
+    }
+}
@@ -0,0 +1,356 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::lua::{state, to_path, LibError};
+use bp3d_lua::decl_lib_func;
+use bp3d_lua::libs::files::SandboxPath;
+use bp3d_lua::libs::Lib;
+use bp3d_lua::util::Namespace;
+use bp3d_lua::vm::function::types::RFunction;
+use bp3d_lua::vm::table::Table;
+use bp3d_lua::vm::value::any::AnyValue;
+use bp3d_lua::vm::value::IntoLua;
+use bp3d_lua::vm::Vm;
+use bp3d_util::simple_error;
+use serde_json::{Map, Number};
+use std::ffi::CStr;
+
+/// Maximum nesting of Lua tables, protects against reference cycles.
+const MAX_DEPTH: usize = 128;
+
+simple_error! {
+    pub Error {
+        (impl From) Lib(LibError) => "{}",
+        Json(serde_json::Error) => "json error: {}",
+        TomlDecode(toml::de::Error) => "toml decode error: {}",
+        TomlEncode(toml::ser::Error) => "toml encode error: {}",
+        UnsupportedKey => "unsupported table key type, expected string or number",
+        Unsupported => "unsupported value type, expected nil, boolean, number, string or table",
+        InvalidNumber(f64) => "invalid number: {}",
+        TooDeep => "value is too deeply nested or contains a cycle"
+    }
+}
+
+/// Marks the JSON null sentinel, see [init].
+const NULL_KEY: &CStr = c"__bp3d_null";
+
+/// Marks a number which is encoded as a float even without a fractional part, see [float].
+const FLOAT_KEY: &CStr = c"__bp3d_float";
+
+/// A decoded value, converted to Lua.
+enum Value<'a> {
+    Nil,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Table(Table<'a>),
+}
+
+unsafe impl IntoLua for Value<'_> {
+    fn into_lua(self, vm: &Vm) -> u16 {
+        match self {
+            Value::Nil => ().into_lua(vm),
+            Value::Boolean(v) => v.into_lua(vm),
+            Value::Integer(v) => v.into_lua(vm),
+            Value::Float(v) => v.into_lua(vm),
+            Value::String(v) => (&*v).into_lua(vm),
+            Value::Table(v) => v.into_lua(vm),
+        }
+    }
+}
+
+/// Creates the json.null sentinel, decoded arrays use it in place of null so that they keep
+/// their length.
+pub fn init(vm: &Vm) -> bp3d_lua::vm::Result<()> {
+    let mut null = Table::with_capacity(vm, 0, 1);
+    null.set(NULL_KEY, true)?;
+    state::get(vm)?.set(c"null", null.clone())?;
+    let bp3d: Table = vm.get_global(c"bp3d")?;
+    let build: Table = bp3d.get(c"build")?;
+    let mut json: Table = build.get(c"json")?;
+    json.set(c"null", null)
+}
+
+/// Converts a decoded document, objects become string keyed tables and arrays become sequences.
+///
+/// Null values are dropped from objects and replaced by the json.null sentinel in arrays.
+fn to_lua<'a>(vm: &'a Vm, value: serde_json::Value) -> bp3d_lua::vm::Result<Value<'a>> {
+    match value {
+        serde_json::Value::Null => Ok(Value::Nil),
+        serde_json::Value::Bool(v) => Ok(Value::Boolean(v)),
+        serde_json::Value::Number(v) => Ok(match v.as_i64() {
+            Some(v) => Value::Integer(v),
+            None => Value::Float(v.as_f64().unwrap_or_default()),
+        }),
+        serde_json::Value::String(v) => Ok(Value::String(v)),
+        serde_json::Value::Array(items) => {
+            let null: Table = state::get(vm)?.get(c"null")?;
+            let mut tbl = Table::with_capacity(vm, items.len(), 0);
+            for item in items {
+                match item {
+                    serde_json::Value::Null => tbl.push(null.clone())?,
+                    item => tbl.push(to_lua(vm, item)?)?,
+                }
+            }
+            Ok(Value::Table(tbl))
+        }
+        serde_json::Value::Object(items) => {
+            let mut tbl = Table::with_capacity(vm, 0, items.len());
+            for (key, item) in items {
+                if !item.is_null() {
+                    tbl.set(&*key, to_lua(vm, item)?)?;
+                }
+            }
+            Ok(Value::Table(tbl))
+        }
+    }
+}
+
+fn decode<'a>(vm: &'a Vm, value: serde_json::Value) -> Result<Value<'a>, Error> {
+    Ok(to_lua(vm, value).map_err(LibError::Lua)?)
+}
+
+/// Converts a Lua number, numbers without a fractional part are encoded as integers.
+///
+/// Lua does not distinguish integers from floats, see [float] to encode such a number as a float.
+fn number(value: f64) -> Result<serde_json::Value, Error> {
+    if value.fract() == 0.0 && value >= i64::MIN as f64 && value <= i64::MAX as f64 {
+        return Ok(serde_json::Value::Number((value as i64).into()));
+    }
+    Number::from_f64(value)
+        .map(serde_json::Value::Number)
+        .ok_or(Error::InvalidNumber(value))
+}
+
+/// Converts a Lua table, a sequence 1..n becomes an array, anything else becomes an object.
+///
+/// Empty tables are converted to objects, json.null to null and the tables returned by
+/// [float] to floats.
+fn from_lua_table(table: &Table, depth: usize) -> Result<serde_json::Value, Error> {
+    if depth > MAX_DEPTH {
+        return Err(Error::TooDeep);
+    }
+    let null: Option<bool> = table.get(NULL_KEY).map_err(LibError::Lua)?;
+    if null.unwrap_or_default() {
+        return Ok(serde_json::Value::Null);
+    }
+    let float: Option<f64> = table.get(FLOAT_KEY).map_err(LibError::Lua)?;
+    if let Some(value) = float {
+        return Number::from_f64(value)
+            .map(serde_json::Value::Number)
+            .ok_or(Error::InvalidNumber(value));
+    }
+    let mut items = Vec::new();
+    for pair in table.iter() {
+        let (key, value): (AnyValue, AnyValue) = pair.map_err(LibError::Lua)?;
+        items.push((key, from_lua(value, depth + 1)?));
+    }
+    let is_array = !items.is_empty()
+        && items.iter().all(|(k, _)| match k {
+            AnyValue::Number(v) => *v >= 1.0 && v.fract() == 0.0 && *v <= items.len() as f64,
+            _ => false,
+        });
+    if is_array {
+        let mut array = vec![serde_json::Value::Null; items.len()];
+        for (key, value) in items {
+            if let AnyValue::Number(index) = key {
+                array[index as usize - 1] = value;
+            }
+        }
+        return Ok(serde_json::Value::Array(array));
+    }
+    let mut map = Map::with_capacity(items.len());
+    for (key, value) in items {
+        let key = match key {
+            AnyValue::String(v) => v.into(),
+            AnyValue::Number(v) => v.to_string(),
+            AnyValue::Boolean(v) => v.to_string(),
+            _ => return Err(Error::UnsupportedKey),
+        };
+        map.insert(key, value);
+    }
+    Ok(serde_json::Value::Object(map))
+}
+
+fn from_lua(value: AnyValue, depth: usize) -> Result<serde_json::Value, Error> {
+    match value {
+        AnyValue::None | AnyValue::Nil => Ok(serde_json::Value::Null),
+        AnyValue::Boolean(v) => Ok(serde_json::Value::Bool(v)),
+        AnyValue::Number(v) => number(v),
+        AnyValue::String(v) => Ok(serde_json::Value::String(v.into())),
+        AnyValue::Table(v) => from_lua_table(&v, depth),
+        _ => Err(Error::Unsupported),
+    }
+}
+
+/// Converts a TOML value, datetimes are converted to strings in RFC 3339 format.
+///
+/// Datetimes are therefore encoded back as strings, scripts needing a TOML datetime must write
+/// the document themselves.
+fn from_toml(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(v) => serde_json::Value::String(v),
+        toml::Value::Integer(v) => serde_json::Value::Number(v.into()),
+        toml::Value::Float(v) => Number::from_f64(v)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        toml::Value::Boolean(v) => serde_json::Value::Bool(v),
+        toml::Value::Datetime(v) => serde_json::Value::String(v.to_string()),
+        toml::Value::Array(v) => serde_json::Value::Array(v.into_iter().map(from_toml).collect()),
+        toml::Value::Table(v) => {
+            serde_json::Value::Object(v.into_iter().map(|(k, v)| (k, from_toml(v))).collect())
+        }
+    }
+}
+
+/// Converts a Lua value to a JSON value, see [from_lua_table] for the table mapping.
+pub(super) fn to_json(value: AnyValue) -> Result<serde_json::Value, Error> {
+    from_lua(value, 0)
+}
+
+fn read_file(vm: &Vm, path: SandboxPath) -> Result<String, Error> {
+    let path = to_path(vm, path)?;
+    std::fs::read_to_string(path).map_err(|e| LibError::Io(e).into())
+}
+
+fn write_file(vm: &Vm, path: SandboxPath, data: String) -> Result<(), Error> {
+    let path = to_path(vm, path)?;
+    std::fs::write(path, data).map_err(|e| LibError::Io(e).into())
+}
+
+fn json_to_string(value: AnyValue, pretty: Option<bool>) -> Result<String, Error> {
+    let value = from_lua(value, 0)?;
+    match pretty.unwrap_or_default() {
+        true => serde_json::to_string_pretty(&value),
+        false => serde_json::to_string(&value),
+    }
+    .map_err(Error::Json)
+}
+
+fn toml_to_string(value: AnyValue) -> Result<String, Error> {
+    let value = from_lua(value, 0)?;
+    toml::to_string_pretty(&value).map_err(Error::TomlEncode)
+}
+
+decl_lib_func! {
+    fn json_decode<'a>(vm: &'a Vm, data: &str) -> Result<Value<'a>, Error> {
+        decode(vm, serde_json::from_str(data).map_err(Error::Json)?)
+    }
+}
+
+decl_lib_func! {
+    fn json_encode(value: AnyValue, pretty: Option<bool>) -> Result<String, Error> {
+        json_to_string(value, pretty)
+    }
+}
+
+decl_lib_func! {
+    fn json_read<'a>(vm: &'a Vm, path: SandboxPath<'a>) -> Result<Value<'a>, Error> {
+        let data = read_file(vm, path)?;
+        decode(vm, serde_json::from_str(&data).map_err(Error::Json)?)
+    }
+}
+
+decl_lib_func! {
+    fn json_write<'a>(vm: &Vm, path: SandboxPath<'a>, value: AnyValue, pretty: Option<bool>) -> Result<(), Error> {
+        let data = json_to_string(value, pretty)?;
+        write_file(vm, path, data)
+    }
+}
+
+// Datetimes are decoded as RFC 3339 strings.
+decl_lib_func! {
+    fn toml_decode<'a>(vm: &'a Vm, data: &str) -> Result<Value<'a>, Error> {
+        let value = toml::from_str(data).map_err(Error::TomlDecode)?;
+        decode(vm, from_toml(value))
+    }
+}
+
+decl_lib_func! {
+    fn toml_encode(value: AnyValue) -> Result<String, Error> {
+        toml_to_string(value)
+    }
+}
+
+decl_lib_func! {
+    fn toml_read<'a>(vm: &'a Vm, path: SandboxPath<'a>) -> Result<Value<'a>, Error> {
+        let data = read_file(vm, path)?;
+        let value = toml::from_str(&data).map_err(Error::TomlDecode)?;
+        decode(vm, from_toml(value))
+    }
+}
+
+decl_lib_func! {
+    fn toml_write<'a>(vm: &Vm, path: SandboxPath<'a>, value: AnyValue) -> Result<(), Error> {
+        let data = toml_to_string(value)?;
+        write_file(vm, path, data)
+    }
+}
+
+// Lua numbers without a fractional part are encoded as integers, float marks a number to encode
+// as a float instead.
+decl_lib_func! {
+    fn float<'a>(vm: &'a Vm, value: f64) -> Result<Table<'a>, Error> {
+        let mut tbl = Table::with_capacity(vm, 0, 1);
+        tbl.set(FLOAT_KEY, value).map_err(LibError::Lua)?;
+        Ok(tbl)
+    }
+}
+
+pub struct JsonLib;
+
+impl Lib for JsonLib {
+    const NAMESPACE: &'static str = "bp3d.build.json";
+
+    fn load(&self, namespace: &mut Namespace) -> bp3d_lua::vm::Result<()> {
+        namespace.add([
+            ("decode", RFunction::wrap(json_decode)),
+            ("encode", RFunction::wrap(json_encode)),
+            ("read", RFunction::wrap(json_read)),
+            ("write", RFunction::wrap(json_write)),
+            ("float", RFunction::wrap(float)),
+        ])
+    }
+}
+
+pub struct TomlLib;
+
+impl Lib for TomlLib {
+    const NAMESPACE: &'static str = "bp3d.build.toml";
+
+    fn load(&self, namespace: &mut Namespace) -> bp3d_lua::vm::Result<()> {
+        namespace.add([
+            ("decode", RFunction::wrap(toml_decode)),
+            ("encode", RFunction::wrap(toml_encode)),
+            ("read", RFunction::wrap(toml_read)),
+            ("write", RFunction::wrap(toml_write)),
+            ("float", RFunction::wrap(float)),
+        ])
+    }
+}
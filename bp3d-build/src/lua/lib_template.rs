@@ -0,0 +1,83 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::lua::{lib_serde, state};
+use crate::template;
+use crate::template::Template;
+use bp3d_lua::decl_lib_func;
+use bp3d_lua::libs::Lib;
+use bp3d_lua::util::Namespace;
+use bp3d_lua::vm::function::types::RFunction;
+use bp3d_lua::vm::value::any::AnyValue;
+use bp3d_lua::vm::Vm;
+use bp3d_util::simple_error;
+
+simple_error! {
+    pub Error {
+        Lua(bp3d_lua::vm::error::Error) => "lua error: {}",
+        Template(template::Error) => "{}",
+        Value(lib_serde::Error) => "invalid template arguments: {}"
+    }
+}
+
+fn render(vm: &Vm, template: &Template, args: AnyValue) -> Result<String, Error> {
+    let args = lib_serde::to_json(args).map_err(Error::Value)?;
+    let search_paths = state::template_paths(vm).map_err(Error::Lua)?;
+    template
+        .render(&args, &search_paths)
+        .map_err(Error::Template)
+}
+
+decl_lib_func! {
+    fn template_render(vm: &Vm, source: &str, args: AnyValue) -> Result<String, Error> {
+        let template = Template::parse("<string>", source).map_err(Error::Template)?;
+        render(vm, &template, args)
+    }
+}
+
+// Renders a template file found in the Lua search paths, for example "templates/Info.plist".
+decl_lib_func! {
+    fn template_render_file(vm: &Vm, name: &str, args: AnyValue) -> Result<String, Error> {
+        let search_paths = state::template_paths(vm).map_err(Error::Lua)?;
+        let template = template::load(name, &search_paths).map_err(Error::Template)?;
+        render(vm, &template, args)
+    }
+}
+
+pub struct TemplateLib;
+
+impl Lib for TemplateLib {
+    const NAMESPACE: &'static str = "bp3d.build.template";
+
+    fn load(&self, namespace: &mut Namespace) -> bp3d_lua::vm::Result<()> {
+        namespace.add([
+            ("render", RFunction::wrap(template_render)),
+            ("renderFile", RFunction::wrap(template_render_file)),
+        ])
+    }
+}
@@ -0,0 +1,306 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::lua::lib_command::{CommandInfo, Output};
+use crate::lua::obj_process::Process;
+use crate::lua::{state, LibError};
+use bp3d_lua::decl_lib_func;
+use bp3d_lua::libs::files::SandboxPathBuf;
+use bp3d_lua::libs::Lib;
+use bp3d_lua::util::thread::UnsafeLuaThread;
+use bp3d_lua::util::LuaThread;
+use bp3d_lua::util::Namespace;
+use bp3d_lua::vm::function::types::RFunction;
+use bp3d_lua::vm::table::Table;
+use bp3d_lua::vm::thread::value::Thread;
+use bp3d_lua::vm::userdata::case::Camel;
+use bp3d_lua::vm::Vm;
+use std::path::Path;
+
+/// A scripted result for the commands matching exe and args.
+struct Mock {
+    exe: String,
+    args: Option<Vec<String>>,
+    success: bool,
+    code: i32,
+    stdout: String,
+    stderr: String,
+}
+
+impl Mock {
+    fn from_table(table: &Table) -> Result<Self, LibError> {
+        let success: Option<bool> = table.get(c"success").map_err(LibError::Lua)?;
+        let code: Option<i32> = table.get(c"code").map_err(LibError::Lua)?;
+        let stdout: Option<String> = table.get(c"stdout").map_err(LibError::Lua)?;
+        let stderr: Option<String> = table.get(c"stderr").map_err(LibError::Lua)?;
+        let success = success.unwrap_or(code.map(|v| v == 0).unwrap_or(true));
+        Ok(Mock {
+            exe: table.get(c"exe").map_err(LibError::Lua)?,
+            args: table.get(c"args").map_err(LibError::Lua)?,
+            success,
+            code: code.unwrap_or(if success { 0 } else { 1 }),
+            stdout: stdout.unwrap_or_default(),
+            stderr: stderr.unwrap_or_default(),
+        })
+    }
+
+    fn to_table<'a>(&self, vm: &'a Vm) -> bp3d_lua::vm::Result<Table<'a>> {
+        let mut tbl = Table::with_capacity(vm, 0, 6);
+        tbl.set(c"exe", &*self.exe)?;
+        if let Some(args) = &self.args {
+            tbl.set(c"args", strings(vm, args)?)?;
+        }
+        tbl.set(c"success", self.success)?;
+        tbl.set(c"code", self.code)?;
+        tbl.set(c"stdout", &*self.stdout)?;
+        tbl.set(c"stderr", &*self.stderr)?;
+        Ok(tbl)
+    }
+
+    fn matches(&self, call: &Call) -> bool {
+        let exe = Path::new(&call.exe);
+        let exe_matches = call.exe == self.exe
+            || exe.file_name().is_some_and(|v| *v == *self.exe)
+            || exe.file_stem().is_some_and(|v| *v == *self.exe);
+        let args_match = match &self.args {
+            Some(args) => {
+                args.len() <= call.args.len() && args.iter().zip(&call.args).all(|(a, b)| a == b)
+            }
+            None => true,
+        };
+        exe_matches && args_match
+    }
+}
+
+/// A command recorded by the mock command library.
+struct Call {
+    exe: String,
+    args: Vec<String>,
+    workdir: Option<String>,
+    stdin: Option<String>,
+}
+
+impl Call {
+    fn to_table<'a>(&self, vm: &'a Vm) -> bp3d_lua::vm::Result<Table<'a>> {
+        let mut tbl = Table::with_capacity(vm, 0, 4);
+        tbl.set(c"exe", &*self.exe)?;
+        tbl.set(c"args", strings(vm, &self.args)?)?;
+        tbl.set(c"workdir", self.workdir.as_deref())?;
+        tbl.set(c"stdin", self.stdin.as_deref())?;
+        Ok(tbl)
+    }
+}
+
+fn strings<'a>(vm: &'a Vm, values: &[String]) -> bp3d_lua::vm::Result<Table<'a>> {
+    let mut tbl = Table::with_capacity(vm, values.len(), 0);
+    for value in values {
+        tbl.push(&**value)?;
+    }
+    Ok(tbl)
+}
+
+/// Resets the scripted results, recorded commands and assertion count of the test mode.
+pub fn reset(vm: &Vm) -> bp3d_lua::vm::Result<()> {
+    let mut test = state::test(vm)?;
+    test.set(c"mocks", Table::with_capacity(vm, 0, 0))?;
+    test.set(c"calls", Table::with_capacity(vm, 0, 0))?;
+    test.set(c"assertions", 0)
+}
+
+/// Returns the number of passed assertions since the last [reset].
+pub fn assertions(vm: &Vm) -> bp3d_lua::vm::Result<usize> {
+    let assertions: i32 = state::test(vm)?.get(c"assertions")?;
+    Ok(assertions as usize)
+}
+
+/// Records a command and returns its scripted result.
+///
+/// Commands without a matching mock succeed without output.
+fn record(vm: &Vm, info: &CommandInfo) -> Result<Mock, LibError> {
+    let call = Call {
+        exe: info.exe.as_os_str().to_string_lossy().into(),
+        args: info
+            .args
+            .iter()
+            .flatten()
+            .map(|v| v.as_os_str().to_string_lossy().into())
+            .collect(),
+        workdir: info.workdir.as_ref().map(|v| v.to_string_lossy().into()),
+        stdin: info.stdin_data().map(String::from),
+    };
+    let test = state::test(vm).map_err(LibError::Lua)?;
+    let mocks: Vec<Table> = test.get(c"mocks").map_err(LibError::Lua)?;
+    let mut mock = None;
+    for table in &mocks {
+        let item = Mock::from_table(table)?;
+        if item.matches(&call) {
+            mock = Some(item);
+            break;
+        }
+    }
+    let mut mock = mock.unwrap_or_else(|| Mock {
+        exe: call.exe.clone(),
+        args: None,
+        success: true,
+        code: 0,
+        stdout: String::new(),
+        stderr: String::new(),
+    });
+    let mut calls: Table = test.get(c"calls").map_err(LibError::Lua)?;
+    calls
+        .push(call.to_table(vm).map_err(LibError::Lua)?)
+        .map_err(LibError::Lua)?;
+    // Redirected streams are written like a real command would.
+    if let Some(path) = &info.stdout {
+        std::fs::write(path, std::mem::take(&mut mock.stdout)).map_err(LibError::Io)?;
+    }
+    if let Some(path) = &info.stderr {
+        std::fs::write(path, std::mem::take(&mut mock.stderr)).map_err(LibError::Io)?;
+    }
+    Ok(mock)
+}
+
+/// Records a command run to completion and returns its scripted output.
+pub(crate) fn output(vm: &Vm, info: &CommandInfo) -> Result<Output, LibError> {
+    let mock = record(vm, info)?;
+    Ok(Output {
+        stdout: mock.stdout,
+        stderr: mock.stderr,
+        success: mock.success,
+        code: Some(mock.code),
+        timed_out: false,
+    })
+}
+
+decl_lib_func! {
+    fn mock_run(vm: &Vm, table: Table) -> Result<(bool, Option<i32>), LibError> {
+        let mock = record(vm, &CommandInfo::from_table(vm, &table)?)?;
+        Ok((mock.success, Some(mock.code)))
+    }
+}
+
+decl_lib_func! {
+    fn mock_spawn(vm: &Vm, table: Table, event_thread: Thread) -> Result<(bool, Option<i32>, bool), LibError> {
+        let mock = record(vm, &CommandInfo::from_table(vm, &table)?)?;
+        let event_thread = unsafe { UnsafeLuaThread::wrap(LuaThread::create(event_thread)) };
+        let out = mock.stdout.lines().map(|v| ("out", v));
+        let err = mock.stderr.lines().map(|v| ("err", v));
+        let res = out
+            .chain(err)
+            .try_for_each(|v| event_thread.as_thread().resume::<()>(v));
+        event_thread.delete(vm);
+        res.map_err(LibError::Lua)?;
+        Ok((mock.success, Some(mock.code), false))
+    }
+}
+
+decl_lib_func! {
+    fn mock_output<'a>(vm: &'a Vm, table: Table) -> Result<Table<'a>, LibError> {
+        output(vm, &CommandInfo::from_table(vm, &table)?)?.to_table(vm)
+    }
+}
+
+decl_lib_func! {
+    fn mock_start(vm: &Vm, table: Table) -> Result<Process, LibError> {
+        let mock = record(vm, &CommandInfo::from_table(vm, &table)?)?;
+        Ok(Process::exited(mock.success, Some(mock.code), &mock.stdout, &mock.stderr))
+    }
+}
+
+/// Replaces bp3d.build.command by a recorder returning scripted results.
+pub struct MockCommandLib;
+
+impl Lib for MockCommandLib {
+    const NAMESPACE: &'static str = "bp3d.build.command";
+
+    fn load(&self, namespace: &mut Namespace) -> bp3d_lua::vm::Result<()> {
+        namespace.add([
+            ("run", RFunction::wrap(mock_run)),
+            ("output", RFunction::wrap(mock_output)),
+            ("spawn", RFunction::wrap(mock_spawn)),
+            ("start", RFunction::wrap(mock_start)),
+        ])?;
+        namespace.add_userdata::<Process>("Process", Camel)
+    }
+}
+
+// Scripts the result of the commands matching exe and the optional args prefix.
+decl_lib_func! {
+    fn mock(vm: &Vm, table: Table) -> Result<(), LibError> {
+        let mock = Mock::from_table(&table)?;
+        let test = state::test(vm).map_err(LibError::Lua)?;
+        let mut mocks: Table = test.get(c"mocks").map_err(LibError::Lua)?;
+        mocks
+            .push(mock.to_table(vm).map_err(LibError::Lua)?)
+            .map_err(LibError::Lua)
+    }
+}
+
+// Returns the commands run so far, as tables of exe, args, workdir and stdin.
+decl_lib_func! {
+    fn calls<'a>(vm: &'a Vm) -> Result<Table<'a>, LibError> {
+        let test = state::test(vm).map_err(LibError::Lua)?;
+        let calls: Vec<Table> = test.get(c"calls").map_err(LibError::Lua)?;
+        let mut tbl = Table::with_capacity(vm, calls.len(), 0);
+        for call in calls {
+            tbl.push(call).map_err(LibError::Lua)?;
+        }
+        Ok(tbl)
+    }
+}
+
+decl_lib_func! {
+    fn pass(vm: &Vm) -> Result<(), LibError> {
+        let mut test = state::test(vm).map_err(LibError::Lua)?;
+        let assertions: i32 = test.get(c"assertions").map_err(LibError::Lua)?;
+        test.set(c"assertions", assertions + 1).map_err(LibError::Lua)
+    }
+}
+
+// Returns the temporary directory the test can freely write to.
+decl_lib_func! {
+    fn root(vm: &Vm) -> Result<SandboxPathBuf, LibError> {
+        let test = state::test(vm).map_err(LibError::Lua)?;
+        test.get(c"root").map_err(LibError::Lua)
+    }
+}
+
+pub struct TestLib;
+
+impl Lib for TestLib {
+    const NAMESPACE: &'static str = "bp3d.build.test";
+
+    fn load(&self, namespace: &mut Namespace) -> bp3d_lua::vm::Result<()> {
+        namespace.add([
+            ("mock", RFunction::wrap(mock)),
+            ("calls", RFunction::wrap(calls)),
+            ("pass", RFunction::wrap(pass)),
+            ("root", RFunction::wrap(root)),
+        ])
+    }
+}
@@ -27,10 +27,57 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 pub mod core;
+pub mod error;
+mod lib_archive;
+mod lib_cargo;
 mod lib_command;
 mod lib_files;
+mod lib_hash;
+mod lib_serde;
+mod lib_template;
+mod lib_test;
 mod obj_artifact;
 mod obj_list;
+mod obj_process;
+mod obj_target;
+pub mod paths;
+mod state;
+pub mod stubs;
+pub mod test;
 pub mod util;
 
 pub use obj_list::List;
+pub use obj_target::Target;
+
+use bp3d_lua::libs::files::chroot::SandboxError;
+use bp3d_lua::libs::files::SandboxPath;
+use bp3d_lua::vm::Vm;
+use bp3d_util::simple_error;
+use std::path::{Component, PathBuf};
+
+simple_error! {
+    pub LibError {
+        Lua(bp3d_lua::vm::error::Error) => "lua error: {}",
+        Sandbox(SandboxError) => "sandbox error: {}",
+        Io(std::io::Error) => "io error: {}",
+        OutsideTestRoot(PathBuf) => "path {:?} is outside of the test root"
+    }
+}
+
+/// Resolves a path received from Lua through the sandbox of the VM.
+///
+/// In test mode, paths escaping the test root are rejected, including the unchecked paths
+/// returned by the build libraries.
+pub(crate) fn to_path(vm: &Vm, path: SandboxPath) -> Result<PathBuf, LibError> {
+    let path = path
+        .to_path(vm)
+        .map(PathBuf::from)
+        .map_err(LibError::Sandbox)?;
+    if let Some(root) = state::test_root(vm).map_err(LibError::Lua)? {
+        let escapes = path.components().any(|v| v == Component::ParentDir);
+        if escapes || !path.starts_with(&root) {
+            return Err(LibError::OutsideTestRoot(path));
+        }
+    }
+    Ok(path)
+}
@@ -26,15 +26,15 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use crate::lua::{to_path, LibError};
 use crate::system::artifact;
-use crate::system::artifact::{LibType, List, Type};
+use crate::system::artifact::{LibType, Type};
 use bp3d_lua::libs::files::chroot::SandboxError;
 use bp3d_lua::libs::files::SandboxPath;
 use bp3d_lua::libs::Lib;
 use bp3d_lua::util::Namespace;
 use bp3d_lua::vm::table::Table;
 use bp3d_lua::vm::userdata::case::Camel;
-use bp3d_lua::vm::value::IntoLua;
 use bp3d_lua::vm::Vm;
 use bp3d_lua::{decl_lib_func, decl_userdata, impl_userdata};
 use bp3d_lua_codegen::{FromParam, LuaType};
@@ -65,6 +65,64 @@ enum LuaLibType {
     Static,
 }
 
+impl From<LuaLibType> for LibType {
+    fn from(value: LuaLibType) -> Self {
+        match value {
+            LuaLibType::Dynamic => LibType::Dynamic,
+            LuaLibType::Static => LibType::Static,
+        }
+    }
+}
+
+/// Creates an artifact from an explicit path, reading the debugInfo and exports options.
+fn create(
+    vm: &Vm,
+    ty: Type,
+    path: SandboxPath,
+    name: &str,
+    options: Option<Table>,
+) -> Result<Artifact, LibError> {
+    let mut artifact = artifact::Artifact::new(ty, &to_path(vm, path)?, name, None, None);
+    if let Some(options) = options {
+        let debug_info: Option<SandboxPath> = options.get(c"debugInfo").map_err(LibError::Lua)?;
+        let exports: Option<SandboxPath> = options.get(c"exports").map_err(LibError::Lua)?;
+        if let Some(debug_info) = debug_info {
+            artifact = artifact.with_debug_info(Some(&to_path(vm, debug_info)?));
+        }
+        if let Some(exports) = exports {
+            artifact = artifact.with_exports(Some(&to_path(vm, exports)?));
+        }
+    }
+    Ok(Artifact(artifact))
+}
+
+/// Returns the name of an artifact type, as returned by Artifact:ty().
+pub(super) fn type_name(ty: Type) -> &'static str {
+    match ty {
+        Type::Bin => "bin",
+        Type::Lib(v) => match v {
+            LibType::Dynamic => "lib::dynamic",
+            LibType::Static => "lib::static",
+        },
+        Type::Header => "header",
+        Type::Config => "config",
+        Type::Resource => "resource",
+    }
+}
+
+/// Parses the name of an artifact type, as returned by Artifact:ty().
+pub(super) fn parse_type(name: &str) -> Option<Type> {
+    match name {
+        "bin" => Some(Type::Bin),
+        "lib::dynamic" => Some(Type::Lib(LibType::Dynamic)),
+        "lib::static" => Some(Type::Lib(LibType::Static)),
+        "header" => Some(Type::Header),
+        "config" => Some(Type::Config),
+        "resource" => Some(Type::Resource),
+        _ => None,
+    }
+}
+
 decl_lib_func! {
     fn find_bin(vm: &Vm, path: SandboxPath, name: &str, remove_debug_info: Option<bool>) -> Result<Option<Artifact>, SandboxError> {
         path.to_path(vm).map(|v| artifact::Artifact::find_bin(&*v, name, remove_debug_info.unwrap_or_default()).map(Artifact))
@@ -73,10 +131,21 @@ decl_lib_func! {
 
 decl_lib_func! {
     fn find_lib(vm: &Vm, path: SandboxPath, name: &str, ty: LuaLibType, remove_debug_info: Option<bool>) -> Result<Option<Artifact>, SandboxError> {
-        match ty {
-            LuaLibType::Dynamic => path.to_path(vm).map(|v| artifact::Artifact::find_lib(&*v, name, LibType::Dynamic, remove_debug_info.unwrap_or_default()).map(Artifact)),
-            LuaLibType::Static => path.to_path(vm).map(|v| artifact::Artifact::find_lib(&*v, name, LibType::Static, remove_debug_info.unwrap_or_default()).map(Artifact)),
-        }
+        path.to_path(vm).map(|v| artifact::Artifact::find_lib(&*v, name, ty.into(), remove_debug_info.unwrap_or_default()).map(Artifact))
+    }
+}
+
+// Creates a binary artifact, unlike findBin the path is used as is.
+decl_lib_func! {
+    fn bin(vm: &Vm, path: SandboxPath, name: &str, options: Option<Table>) -> Result<Artifact, LibError> {
+        create(vm, Type::Bin, path, name, options)
+    }
+}
+
+// Creates a library artifact, unlike findLib the path is used as is.
+decl_lib_func! {
+    fn lib(vm: &Vm, path: SandboxPath, name: &str, ty: LuaLibType, options: Option<Table>) -> Result<Artifact, LibError> {
+        create(vm, Type::Lib(ty.into()), path, name, options)
     }
 }
 
@@ -117,16 +186,25 @@ impl_userdata! {
         }
 
         fn ty(this: &Artifact) -> &'static str {
-            match this.0.ty() {
-                Type::Bin => "bin",
-                Type::Lib(v) => match v {
-                    LibType::Dynamic => "lib::dynamic",
-                    LibType::Static => "lib::static",
-                }
-                Type::Header => "header",
-                Type::Config => "config",
-                Type::Resource => "resource"
-            }
+            type_name(this.0.ty())
+        }
+
+        fn with_path(this: &Artifact, vm: &Vm, path: SandboxPath) -> Result<Artifact, SandboxError> {
+            path.to_path(vm).map(|v| Artifact(this.0.clone().with_path(&*v)))
+        }
+
+        fn with_name(this: &Artifact, name: &str) -> Artifact {
+            Artifact(this.0.clone().with_name(name))
+        }
+
+        fn with_debug_info(this: &Artifact, vm: &Vm, path: Option<SandboxPath>) -> Result<Artifact, SandboxError> {
+            let path = path.map(|v| v.to_path(vm)).transpose()?;
+            Ok(Artifact(this.0.clone().with_debug_info(path.as_deref())))
+        }
+
+        fn with_exports(this: &Artifact, vm: &Vm, path: Option<SandboxPath>) -> Result<Artifact, SandboxError> {
+            let path = path.map(|v| v.to_path(vm)).transpose()?;
+            Ok(Artifact(this.0.clone().with_exports(path.as_deref())))
         }
 
         fn __tostring(this: &Artifact) -> &str {
@@ -136,6 +214,8 @@ impl_userdata! {
     static {
         [fn find_bin];
         [fn find_lib];
+        [fn bin];
+        [fn lib];
         [fn header];
         [fn config];
         [fn resource];
@@ -151,14 +231,3 @@ impl Lib for ObjArtifact {
         namespace.add_userdata::<Artifact>("Artifact", Camel)
     }
 }
-
-unsafe impl IntoLua for List {
-    fn into_lua(self, vm: &Vm) -> u16 {
-        let inner = self.into_inner();
-        let mut tbl = Table::with_capacity(vm, inner.len(), 0);
-        for artifact in inner {
-            tbl.push(Artifact(artifact)).unwrap();
-        }
-        1
-    }
-}
@@ -26,23 +26,51 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::lua::obj_artifact::Artifact;
-use crate::system::artifact::{LibType, Type};
+use crate::lua::obj_artifact::{parse_type, Artifact};
+use crate::lua::{to_path, LibError};
+use crate::system::artifact::{LibType, Selection, Type};
 use bp3d_lua::libs::files::SandboxPath;
 use bp3d_lua::libs::Lib;
 use bp3d_lua::util::Namespace;
+use bp3d_lua::vm::function::types::RFunction;
+use bp3d_lua::vm::table::Table;
 use bp3d_lua::vm::userdata::case::Camel;
+use bp3d_lua::vm::value::any::AnyValue;
+use bp3d_lua::vm::value::types::Function;
+use bp3d_lua::vm::value::IntoLua;
+use bp3d_lua::vm::Vm;
 use bp3d_lua::{decl_lib_func, decl_userdata, impl_userdata};
-use bp3d_lua_codegen::{FromParam, LuaType};
+use bp3d_util::simple_error;
 use std::cell::RefCell;
 
-#[derive(LuaType, FromParam)]
-enum ArtifactType {
-    Bin,
-    Lib,
-    Header,
-    Config,
-    Other,
+simple_error! {
+    pub Error {
+        (impl From) Lib(LibError) => "{}",
+        InvalidRemap => "remap keys and values must be strings",
+        UnknownType(String) => "unknown artifact type: {}"
+    }
+}
+
+/// Reads the flatten, hidden and remap options of List:addGlob.
+fn read_selection(selection: Selection, options: &Table) -> Result<Selection, Error> {
+    let flatten: Option<bool> = options.get(c"flatten").map_err(LibError::Lua)?;
+    let hidden: Option<bool> = options.get(c"hidden").map_err(LibError::Lua)?;
+    let mut selection = selection
+        .flatten(flatten.unwrap_or_default())
+        .hidden(hidden.unwrap_or_default());
+    let remap: Option<Table> = options.get(c"remap").map_err(LibError::Lua)?;
+    if let Some(remap) = remap {
+        for pair in remap.iter() {
+            let (from, to): (AnyValue, AnyValue) = pair.map_err(LibError::Lua)?;
+            match (from, to) {
+                (AnyValue::String(from), AnyValue::String(to)) => {
+                    selection = selection.remap(from, to)
+                }
+                _ => return Err(Error::InvalidRemap),
+            }
+        }
+    }
+    Ok(selection)
 }
 
 decl_userdata!(#[derive(Clone)] pub struct List(RefCell<crate::system::artifact::List>));
@@ -59,41 +87,161 @@ impl List {
     }
 }
 
+fn get_type(name: &str) -> Result<Type, Error> {
+    parse_type(name).ok_or_else(|| Error::UnknownType(name.into()))
+}
+
+/// Parses the type of the artifacts added from a folder, "lib" and "other" are accepted as
+/// aliases of "lib::dynamic" and "resource".
+fn get_folder_type(name: &str) -> Result<Type, Error> {
+    match name {
+        "lib" => Ok(Type::Lib(LibType::Dynamic)),
+        "other" => Ok(Type::Resource),
+        v => get_type(v),
+    }
+}
+
+/// The iterator function, state and initial index returned by List:iter().
+struct Pairs(List);
+
+unsafe impl IntoLua for Pairs {
+    fn into_lua(self, vm: &Vm) -> u16 {
+        RFunction::wrap(list_next).into_lua(vm);
+        self.0.into_lua(vm);
+        0usize.into_lua(vm);
+        3
+    }
+}
+
+/// The next index and artifact of an iteration, or nil when the iteration is over.
+struct Next(Option<(usize, Artifact)>);
+
+unsafe impl IntoLua for Next {
+    fn into_lua(self, vm: &Vm) -> u16 {
+        match self.0 {
+            Some((index, artifact)) => {
+                index.into_lua(vm);
+                artifact.into_lua(vm);
+                2
+            }
+            None => None::<usize>.into_lua(vm),
+        }
+    }
+}
+
+/// Converts the artifacts of a list to a Lua sequence.
+pub fn to_table<'a>(
+    vm: &'a Vm,
+    list: &crate::system::artifact::List,
+) -> bp3d_lua::vm::Result<Table<'a>> {
+    let mut tbl = Table::with_capacity(vm, list.len(), 0);
+    for artifact in list.iter() {
+        tbl.push(Artifact::from(artifact.clone()))?;
+    }
+    Ok(tbl)
+}
+
 decl_lib_func! {
     fn new() -> List {
         List(RefCell::new(crate::system::artifact::List::new()))
     }
 }
 
+decl_lib_func! {
+    fn list_next(list: &List, index: usize) -> Next {
+        let list = list.0.borrow();
+        Next(list.as_slice().get(index).map(|v| (index + 1, Artifact::from(v.clone()))))
+    }
+}
+
 impl_userdata! {
     impl List {
         fn add(this: &List, artifact: Option<&Artifact>) -> () {
             this.0.borrow_mut().add_if_some(artifact.map(crate::system::artifact::Artifact::from))
         }
 
-        fn add_folder(this: &List, vm: &Vm, ty: ArtifactType, path: SandboxPath, name: &str) -> std::io::Result<()> {
-            let ty = match ty {
-                ArtifactType::Bin => Type::Bin,
-                ArtifactType::Lib => Type::Lib(LibType::Dynamic),
-                ArtifactType::Header => Type::Header,
-                ArtifactType::Config => Type::Config,
-                ArtifactType::Other => Type::Resource
-            };
+        fn add_folder(this: &List, vm: &Vm, ty: &str, path: SandboxPath, name: &str) -> std::io::Result<()> {
+            let ty = get_folder_type(ty).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
             let path = path.to_path(vm).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
             this.0.borrow_mut().add_folder(ty, &*path, name)
         }
 
-        fn add_folder_exclude(this: &List, vm: &Vm, ty: ArtifactType, path: SandboxPath, excluded: &str, name: &str) -> std::io::Result<()> {
-            let ty = match ty {
-                ArtifactType::Bin => Type::Bin,
-                ArtifactType::Lib => Type::Lib(LibType::Dynamic),
-                ArtifactType::Header => Type::Header,
-                ArtifactType::Config => Type::Config,
-                ArtifactType::Other => Type::Resource
-            };
+        fn add_folder_exclude(this: &List, vm: &Vm, ty: &str, path: SandboxPath, excluded: &str, name: &str) -> std::io::Result<()> {
+            let ty = get_folder_type(ty).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
             let path = path.to_path(vm).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
             this.0.borrow_mut().add_folder_exclude(ty, &*path, excluded, name)
         }
+
+        fn add_glob(this: &List, vm: &Vm, ty: &str, path: SandboxPath, patterns: Vec<&str>, name: &str, options: Option<Table>) -> Result<(), Error> {
+            let ty = get_folder_type(ty)?;
+            let path = to_path(vm, path)?;
+            let mut selection = Selection::new().patterns(patterns);
+            if let Some(options) = options {
+                selection = read_selection(selection, &options)?;
+            }
+            this.0.borrow_mut().add_glob(ty, &path, &selection, name).map_err(|e| LibError::Io(e).into())
+        }
+
+        fn len(this: &List) -> usize {
+            this.0.borrow().len()
+        }
+
+        // Iterates over a copy of the list, so that the loop body is free to modify it.
+        fn iter(this: &List) -> Pairs {
+            Pairs(this.clone())
+        }
+
+        fn find(this: &List, ty: &str) -> Result<List, Error> {
+            let ty = get_type(ty)?;
+            let mut list = crate::system::artifact::List::new();
+            for artifact in this.0.borrow().find(ty) {
+                list.add(artifact.clone());
+            }
+            Ok(list.into())
+        }
+
+        fn find_first(this: &List, ty: &str) -> Result<Option<Artifact>, Error> {
+            let ty = get_type(ty)?;
+            Ok(this.0.borrow().find_first(ty).cloned().map(Artifact::from))
+        }
+
+        fn get(this: &List, name: &str) -> Option<Artifact> {
+            this.0.borrow().get(name).cloned().map(Artifact::from)
+        }
+
+        fn remove(this: &List, name: &str) -> Option<Artifact> {
+            this.0.borrow_mut().remove(name).map(Artifact::from)
+        }
+
+        fn filter(this: &List, f: Function) -> Result<List, Error> {
+            // Work on a copy so that the callback is free to modify this list.
+            let content = this.0.borrow().clone();
+            let mut list = crate::system::artifact::List::new();
+            for artifact in content.into_inner() {
+                let keep: bool = f.call(Artifact::from(artifact.clone())).map_err(LibError::Lua)?;
+                if keep {
+                    list.add(artifact);
+                }
+            }
+            Ok(list.into())
+        }
+
+        fn merge(this: &List, other: &List) -> () {
+            let other = other.0.borrow().clone();
+            this.0.borrow_mut().merge(other)
+        }
+
+        fn to_table<'a>(this: &List, vm: &'a Vm) -> Result<Table<'a>, Error> {
+            to_table(vm, &this.0.borrow()).map_err(|e| LibError::Lua(e).into())
+        }
+
+        fn __len(this: &List) -> usize {
+            this.0.borrow().len()
+        }
+
+        fn __pairs(this: &List) -> Pairs {
+            Pairs(this.clone())
+        }
     }
     static {
         [fn new];
@@ -0,0 +1,272 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::report;
+use bp3d_lua::{decl_lib_func, decl_userdata, impl_userdata};
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, Command, ExitStatus};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+type Line = (&'static str, String);
+
+struct Inner {
+    child: Option<Child>,
+    cmd: Option<Command>,
+    stdin: Option<ChildStdin>,
+    lines: Receiver<Line>,
+    status: Option<(bool, Option<i32>)>,
+    start: Instant,
+    deadline: Option<Instant>,
+}
+
+impl Inner {
+    fn exited(&mut self, status: ExitStatus) {
+        if let Some(cmd) = &self.cmd {
+            report::record_command(cmd, self.start.elapsed());
+        }
+        self.stdin = None;
+        self.status = Some((status.success(), status.code()));
+    }
+
+    fn poll(&mut self) -> std::io::Result<Option<(bool, Option<i32>)>> {
+        if self.status.is_none() {
+            let status = self.child.as_mut().map(Child::try_wait).transpose()?;
+            if let Some(status) = status.flatten() {
+                self.exited(status);
+            } else if self.deadline.is_some_and(|v| Instant::now() >= v) {
+                self.kill()?;
+            }
+        }
+        Ok(self.status)
+    }
+
+    fn kill(&mut self) -> std::io::Result<()> {
+        if self.status.is_some() {
+            return Ok(());
+        }
+        if let Some(child) = self.child.as_mut() {
+            child.kill()?;
+            let status = child.wait()?;
+            self.exited(status);
+        }
+        Ok(())
+    }
+
+    fn read_line(&mut self, timeout: Option<Duration>) -> std::io::Result<Option<Line>> {
+        let timeout = timeout.map(|v| Instant::now() + v);
+        // Once the process exited, the remaining output is read regardless of its deadline.
+        let deadline = match (timeout, self.deadline.filter(|_| self.status.is_none())) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        let Some(deadline) = deadline else {
+            return Ok(self.lines.recv().ok());
+        };
+        match self
+            .lines
+            .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        {
+            Ok(line) => Ok(Some(line)),
+            Err(RecvTimeoutError::Disconnected) => Ok(None),
+            Err(RecvTimeoutError::Timeout) => {
+                let expired = self.deadline.is_some_and(|v| Instant::now() >= v);
+                if self.status.is_some() || !expired {
+                    return Ok(None);
+                }
+                self.kill()?;
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "process timed out",
+                ))
+            }
+        }
+    }
+
+    fn wait(&mut self, timeout: Option<Duration>) -> std::io::Result<(bool, Option<i32>)> {
+        // A child reading stdin until EOF would never exit while the pipe is open.
+        self.stdin = None;
+        let deadline = match (timeout.map(|v| Instant::now() + v), self.deadline) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        loop {
+            if let Some(status) = self.poll()? {
+                return Ok(status);
+            }
+            match deadline {
+                Some(deadline) if Instant::now() >= deadline => self.kill()?,
+                Some(_) => std::thread::sleep(Duration::from_millis(10)),
+                None => {
+                    if let Some(child) = self.child.as_mut() {
+                        let status = child.wait()?;
+                        self.exited(status);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // Kill and reap the child so that a dropped process never outlives the build.
+        let _ = self.kill();
+    }
+}
+
+fn read_lines(stream: impl Read + Send + 'static, name: &'static str, sender: Sender<Line>) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            if sender.send((name, line)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn duration(seconds: f64) -> Duration {
+    Duration::from_secs_f64(seconds.max(0.0))
+}
+
+decl_userdata!(pub struct Process(RefCell<Inner>));
+
+decl_lib_func! {
+    fn clock() -> f64 {
+        static START: OnceLock<Instant> = OnceLock::new();
+        START.get_or_init(Instant::now).elapsed().as_secs_f64()
+    }
+}
+
+impl Process {
+    /// Starts a command, writing the given data to its stdin.
+    ///
+    /// The output is read in background threads so that the process never blocks on a full pipe.
+    /// The process is killed when it is still running after the timeout.
+    pub fn start(
+        mut cmd: Command,
+        data: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> std::io::Result<Self> {
+        let start = Instant::now();
+        let mut child = cmd.spawn()?;
+        let (sender, lines) = channel();
+        if let Some(stdout) = child.stdout.take() {
+            read_lines(stdout, "out", sender.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            read_lines(stderr, "err", sender);
+        }
+        let mut stdin = child.stdin.take();
+        if let Some(data) = data {
+            // The data is written once, stdin is then closed.
+            if let Some(mut stdin) = stdin.take() {
+                let data = data.to_string();
+                std::thread::spawn(move || {
+                    let _ = stdin.write_all(data.as_bytes());
+                });
+            }
+        }
+        Ok(Process(RefCell::new(Inner {
+            stdin,
+            child: Some(child),
+            cmd: Some(cmd),
+            lines,
+            status: None,
+            start,
+            deadline: timeout.map(|v| start + v),
+        })))
+    }
+
+    /// Creates a process which already exited with the given status and output.
+    pub fn exited(success: bool, code: Option<i32>, stdout: &str, stderr: &str) -> Self {
+        let (sender, lines) = channel();
+        let out = stdout.lines().map(|v| ("out", v));
+        let err = stderr.lines().map(|v| ("err", v));
+        for (name, line) in out.chain(err) {
+            let _ = sender.send((name, line.into()));
+        }
+        Process(RefCell::new(Inner {
+            child: None,
+            cmd: None,
+            stdin: None,
+            lines,
+            status: Some((success, code)),
+            start: Instant::now(),
+            deadline: None,
+        }))
+    }
+}
+
+// readLine returns the next line and its stream ("out" or "err"), nil once both streams are closed
+// or when the timeout expires. When the timeout of the process expires first, readLine kills the
+// process and fails. wait closes stdin and kills the process when the timeout expires.
+// clock returns the seconds elapsed on a monotonic clock, for deadlines shared by processes.
+impl_userdata! {
+    impl Process {
+        fn write(this: &Process, data: &str) -> std::io::Result<()> {
+            match this.0.borrow_mut().stdin.as_mut() {
+                Some(stdin) => stdin.write_all(data.as_bytes()),
+                None => Err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "stdin is closed"))
+            }
+        }
+
+        fn close_stdin(this: &Process) -> () {
+            this.0.borrow_mut().stdin = None;
+        }
+
+        fn read_line(this: &Process, timeout: Option<f64>) -> std::io::Result<(Option<String>, Option<&'static str>)> {
+            match this.0.borrow_mut().read_line(timeout.map(duration))? {
+                Some((name, line)) => Ok((Some(line), Some(name))),
+                None => Ok((None, None))
+            }
+        }
+
+        fn poll(this: &Process) -> std::io::Result<(Option<bool>, Option<i32>)> {
+            let status = this.0.borrow_mut().poll()?;
+            Ok((status.map(|v| v.0), status.and_then(|v| v.1)))
+        }
+
+        fn wait(this: &Process, timeout: Option<f64>) -> std::io::Result<(bool, Option<i32>)> {
+            this.0.borrow_mut().wait(timeout.map(duration))
+        }
+
+        fn kill(this: &Process) -> std::io::Result<()> {
+            this.0.borrow_mut().kill()
+        }
+
+        fn elapsed(this: &Process) -> f64 {
+            this.0.borrow().start.elapsed().as_secs_f64()
+        }
+    }
+    static {
+        [fn clock];
+    }
+}
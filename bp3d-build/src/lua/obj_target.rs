@@ -0,0 +1,153 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::system::target;
+use bp3d_lua::libs::Lib;
+use bp3d_lua::util::Namespace;
+use bp3d_lua::vm::userdata::case::Camel;
+use bp3d_lua::{decl_lib_func, decl_userdata, impl_userdata};
+
+decl_userdata!(#[derive(Clone)] pub struct Target(target::Target));
+
+impl From<target::Target> for Target {
+    fn from(value: target::Target) -> Self {
+        Self(value)
+    }
+}
+
+impl Target {
+    pub fn inner(&self) -> &target::Target {
+        &self.0
+    }
+}
+
+decl_lib_func! {
+    fn parse(name: &str) -> Result<Target, target::Error> {
+        target::Target::parse(name).map(Target)
+    }
+}
+
+decl_lib_func! {
+    fn host() -> Result<Target, target::Error> {
+        target::Target::host().map(Target)
+    }
+}
+
+impl_userdata! {
+    impl Target {
+        fn name(this: &Target) -> &str {
+            this.0.name()
+        }
+
+        fn arch(this: &Target) -> &str {
+            this.0.arch()
+        }
+
+        fn vendor(this: &Target) -> &str {
+            this.0.vendor()
+        }
+
+        fn os(this: &Target) -> &str {
+            this.0.os()
+        }
+
+        fn env(this: &Target) -> Option<&str> {
+            this.0.env()
+        }
+
+        fn abi(this: &Target) -> Option<&str> {
+            this.0.abi()
+        }
+
+        fn is_windows(this: &Target) -> bool {
+            this.0.is_windows()
+        }
+
+        fn is_apple(this: &Target) -> bool {
+            this.0.is_apple()
+        }
+
+        fn is_linux(this: &Target) -> bool {
+            this.0.is_linux()
+        }
+
+        fn is_android(this: &Target) -> bool {
+            this.0.is_android()
+        }
+
+        fn is_msvc(this: &Target) -> bool {
+            this.0.is_msvc()
+        }
+
+        fn is_wasm(this: &Target) -> bool {
+            this.0.is_wasm()
+        }
+
+        fn is_unix(this: &Target) -> bool {
+            this.0.is_unix()
+        }
+
+        fn exe_suffix(this: &Target) -> &'static str {
+            this.0.exe_suffix()
+        }
+
+        fn lib_prefix(this: &Target) -> &'static str {
+            this.0.lib_prefix()
+        }
+
+        fn dylib_suffix(this: &Target) -> &'static str {
+            this.0.dylib_suffix()
+        }
+
+        fn staticlib_suffix(this: &Target) -> &'static str {
+            this.0.staticlib_suffix()
+        }
+
+        fn __tostring(this: &Target) -> &str {
+            this.0.name()
+        }
+
+        fn __eq(this: &Target, other: &Target) -> bool {
+            this.0 == other.0
+        }
+    }
+    static {
+        [fn parse];
+        [fn host];
+    }
+}
+
+pub struct ObjTarget;
+
+impl Lib for ObjTarget {
+    const NAMESPACE: &'static str = "bp3d.build";
+
+    fn load(&self, namespace: &mut Namespace) -> bp3d_lua::vm::Result<()> {
+        namespace.add_userdata::<Target>("Target", Camel)
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use bp3d_debug::{debug, warning};
+use bp3d_os::assets::get_executable_path;
+use bp3d_util::simple_error;
+use std::path::{Path, PathBuf};
+
+simple_error! {
+    pub Error {
+        NoExecutable => "unable to locate the bp3d executable",
+        MissingRoot(String) => "bp3d lua scripts not found, searched: {}",
+        MissingPath(String) => "lua search path does not exist: {}",
+        Manifest(bp3d_config::Error) => "manifest error: {}"
+    }
+}
+
+/// The environment variable listing additional Lua search paths.
+pub const LUA_PATH_VAR: &str = "BP3D_LUA_PATH";
+
+/// Lua search paths of a project.
+pub struct SearchPaths {
+    /// The bp3d Lua scripts shipped with the installation.
+    pub root: PathBuf,
+
+    /// Additional Lua roots, in order of priority.
+    pub extra: Vec<PathBuf>,
+}
+
+/// Returns the resources directory of the installation.
+pub fn resources() -> Result<PathBuf, Error> {
+    let exe = get_executable_path().ok_or(Error::NoExecutable)?;
+    let path = exe.join("../usr/share");
+    match path.exists() {
+        true => Ok(path),
+        false => Ok(exe.join("../../res")),
+    }
+}
+
+/// Returns the bp3d Lua scripts shipped with the installation.
+pub fn installed_root() -> Result<PathBuf, Error> {
+    let exe = get_executable_path().ok_or(Error::NoExecutable)?;
+    let candidates = [exe.join("../usr/share/lua"), exe.join("../../res/lua")];
+    for path in &candidates {
+        debug!("Check bp3d lua root: {:?}", path);
+        if path.is_dir() {
+            return Ok(path.clone());
+        }
+    }
+    let searched: Vec<String> = candidates.iter().map(|v| v.display().to_string()).collect();
+    Err(Error::MissingRoot(searched.join(", ")))
+}
+
+/// Returns the user-level Lua roots.
+pub fn user_paths() -> Vec<PathBuf> {
+    bp3d_os::dirs::system::get_user_home()
+        .map(|v| v.join(".bp3d").join("lua"))
+        .into_iter()
+        .filter(|v| v.is_dir())
+        .collect()
+}
+
+/// Returns the Lua roots listed in the `BP3D_LUA_PATH` environment variable.
+pub fn env_paths() -> Vec<PathBuf> {
+    let Some(var) = std::env::var_os(LUA_PATH_VAR) else {
+        return Vec::new();
+    };
+    std::env::split_paths(&var)
+        .filter(|v| !v.as_os_str().is_empty())
+        .filter(|v| {
+            let exists = v.is_dir();
+            if !exists {
+                warning!("Ignoring missing {} entry: {:?}", LUA_PATH_VAR, v);
+            }
+            exists
+        })
+        .collect()
+}
+
+/// Returns the Lua roots listed in the `lua.paths` entry of the project manifest.
+pub fn manifest_paths(project: &Path) -> Result<Vec<PathBuf>, Error> {
+    let manifest = bp3d_config::load(project)
+        .map_err(Error::Manifest)?
+        .map(|v| v.into_manifest());
+    let paths = manifest
+        .and_then(|v| v.lua)
+        .and_then(|v| v.paths)
+        .unwrap_or_default();
+    paths
+        .iter()
+        .map(|v| {
+            let path = project.join(v);
+            match path.is_dir() {
+                true => Ok(path),
+                false => Err(Error::MissingPath(path.display().to_string())),
+            }
+        })
+        .collect()
+}
+
+/// Resolves all Lua search paths of the given project.
+///
+/// Additional roots are ordered by priority: `BP3D_LUA_PATH`, the project manifest and finally
+/// the user-level directory (`~/.bp3d/lua`).
+pub fn resolve(project: &Path) -> Result<SearchPaths, Error> {
+    let mut extra = env_paths();
+    extra.extend(manifest_paths(project)?);
+    extra.extend(user_paths());
+    Ok(SearchPaths {
+        root: installed_root()?,
+        extra,
+    })
+}
@@ -0,0 +1,120 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! State of the build libraries.
+//!
+//! The state is owned by [Vm](crate::lua::core::Vm) and referenced from the Lua registry under a
+//! light userdata key, so that scripts can neither read nor replace it. Lua values of the state
+//! are kept in a table which is only reachable through the registry.
+
+use bp3d_lua::ffi::lua::{
+    lua_pushlightuserdata, lua_rawget, lua_rawset, lua_settop, lua_touserdata, State as LuaState,
+};
+use bp3d_lua::libs::files::SandboxPathBuf;
+use bp3d_lua::vm::registry::core::Key;
+use bp3d_lua::vm::registry::types;
+use bp3d_lua::vm::table::Table;
+use bp3d_lua::vm::Result;
+use bp3d_lua::vm::Vm;
+use std::ffi::{c_int, c_void};
+use std::path::{Path, PathBuf};
+
+/// LUA_REGISTRYINDEX of LuaJIT.
+const REGISTRYINDEX: c_int = -10000;
+
+/// The address of this static is the registry key of the state.
+static KEY: u8 = 0;
+
+pub struct State {
+    table: Key<types::Table>,
+    template_paths: Vec<PathBuf>,
+    test_root: Option<PathBuf>,
+}
+
+fn push_key(l: LuaState) {
+    unsafe { lua_pushlightuserdata(l, &KEY as *const u8 as *mut c_void) };
+}
+
+/// Creates the state of a VM, search_paths are used to find templates and test_root enables the
+/// test mode.
+///
+/// The returned state must outlive the VM, that is it must be dropped after the VM is closed.
+pub fn init(vm: &Vm, search_paths: &[PathBuf], test_root: Option<&Path>) -> Result<Box<State>> {
+    let mut table = Table::with_capacity(vm, 0, 1);
+    if let Some(root) = test_root {
+        let mut test = Table::with_capacity(vm, 0, 4);
+        test.set(c"root", SandboxPathBuf::from_path_unchecked(root.into()))?;
+        table.set(c"test", test)?;
+    }
+    let state = Box::new(State {
+        table: Key::new(table),
+        template_paths: search_paths.into(),
+        test_root: test_root.map(PathBuf::from),
+    });
+    let l = vm.as_ptr();
+    push_key(l);
+    unsafe {
+        lua_pushlightuserdata(l, &*state as *const State as *mut c_void);
+        lua_rawset(l, REGISTRYINDEX);
+    }
+    Ok(state)
+}
+
+fn state(vm: &Vm) -> &State {
+    let l = vm.as_ptr();
+    push_key(l);
+    let ptr = unsafe {
+        lua_rawget(l, REGISTRYINDEX);
+        let ptr = lua_touserdata(l, -1) as *const State;
+        lua_settop(l, -2);
+        ptr
+    };
+    assert!(!ptr.is_null(), "the VM has no bp3d-build state");
+    // SAFETY: the state is only dropped after the VM is closed.
+    unsafe { &*ptr }
+}
+
+/// Returns the state table of the VM.
+pub fn get(vm: &Vm) -> Result<Table> {
+    Ok(state(vm).table.push(vm))
+}
+
+/// Returns the state of the test mode, this fails outside of the test mode.
+pub fn test(vm: &Vm) -> Result<Table> {
+    get(vm)?.get(c"test")
+}
+
+/// Returns the directory the sandbox is restricted to in test mode.
+pub fn test_root(vm: &Vm) -> Result<Option<PathBuf>> {
+    Ok(state(vm).test_root.clone())
+}
+
+/// Returns the search paths used to find templates.
+pub fn template_paths(vm: &Vm) -> Result<Vec<PathBuf>> {
+    Ok(state(vm).template_paths.clone())
+}
@@ -0,0 +1,203 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::lua::paths;
+use bp3d_util::simple_error;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+simple_error! {
+    pub Error {
+        Io(std::io::Error) => "io error: {}",
+        LuaPath(paths::Error) => "lua search path error: {}"
+    }
+}
+
+/// Stubs of the Rust Lua bindings, generated by the build script.
+pub const BINDINGS: &str = include_str!(concat!(env!("OUT_DIR"), "/bp3d.build.lua"));
+
+/// Types which are built by the Rust side without a binding declaration.
+const TYPES: &str = r#"
+--- A sandboxed path, see bp3d.files.
+---@class Path
+---@field join fun(self: Path, ...: string): Path
+---@field parent fun(self: Path): Path?
+---@field extension fun(self: Path): string?
+
+--- The context passed to build system, packager and script classes.
+---@class bp3d.Context
+---@field path Path The project root.
+---@field configuration string
+---@field target bp3d.build.Target? The target being processed, for per-target calls.
+---@field targets string[]? All targets, for calls which are not per-target.
+---@field features string[]? The enabled features, nil when all features are enabled.
+---@field package table? The package being built, for packagers and scripts.
+"#;
+
+const CLASS: &str = r#"
+---@param parent? table
+---@return table
+function Class(parent) end
+
+---@generic T
+---@param class T
+---@param args? table
+---@return T
+function New(class, args) end
+"#;
+
+/// The base classes of build systems, packagers and scripts, as (name, file).
+const BASE_CLASSES: &[(&str, &str)] = &[
+    ("Builder", "builder.lua"),
+    ("Packager", "packager.lua"),
+    ("Script", "script.lua"),
+];
+
+fn param_type(name: &str) -> &'static str {
+    match name {
+        "ctx" => "bp3d.Context",
+        "artifacts" => "bp3d.build.Artifact[]",
+        "scope" => "string",
+        _ => "any",
+    }
+}
+
+fn value_type(value: &str) -> &'static str {
+    match value.chars().next() {
+        Some('{') => "table",
+        Some('"') | Some('\'') => "string",
+        Some(c) if c.is_ascii_digit() => "number",
+        _ if value == "true" || value == "false" => "boolean",
+        _ => "any",
+    }
+}
+
+fn return_type(value: &str) -> Option<&'static str> {
+    if value == "true" || value == "false" {
+        Some("boolean")
+    } else if value.starts_with("bp3d.build.List.new") {
+        Some("bp3d.build.List")
+    } else {
+        None
+    }
+}
+
+/// Generates the stubs of a class from its Lua source.
+///
+/// Comments directly above a method become its documentation, `---@` annotations are kept as is
+/// and take precedence over the generated parameter types.
+fn class_stubs(name: &str, source: &str) -> String {
+    let mut fields = Vec::new();
+    let mut methods = String::new();
+    let mut comments = Vec::new();
+    let field_prefix = format!("{}.", name);
+    let method_prefix = format!("function {}:", name);
+    let mut lines = source.lines().peekable();
+    while let Some(line) = lines.next() {
+        if let Some(comment) = line.strip_prefix("--") {
+            comments.push(comment.trim());
+            continue;
+        }
+        if let Some(decl) = line.strip_prefix(&method_prefix) {
+            let (method, params) = decl.split_once('(').unwrap_or((decl, ""));
+            let params = params.split(')').next().unwrap_or_default();
+            let params: Vec<&str> = params
+                .split(',')
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .collect();
+            for comment in &comments {
+                let _ = match comment.starts_with("-@") {
+                    true => writeln!(methods, "--{}", comment),
+                    false => writeln!(methods, "--- {}", comment),
+                };
+            }
+            for param in &params {
+                let annotation = format!("-@param {} ", param);
+                if !comments.iter().any(|v| v.starts_with(&annotation)) {
+                    let _ = writeln!(methods, "---@param {} {}", param, param_type(param));
+                }
+            }
+            // Look at the first statement of multi-line methods for the return type.
+            let body = match line.trim_end().ends_with("end") {
+                true => None,
+                false => lines.peek().map(|v| v.trim()),
+            };
+            if let Some(ty) = body
+                .and_then(|v| v.strip_prefix("return "))
+                .and_then(return_type)
+            {
+                let _ = writeln!(methods, "---@return {}", ty);
+            }
+            let _ = writeln!(
+                methods,
+                "function {}:{}({}) end\n",
+                name,
+                method,
+                params.join(", ")
+            );
+        } else if let Some((field, value)) = line
+            .strip_prefix(&field_prefix)
+            .and_then(|v| v.split_once('='))
+        {
+            fields.push((field.trim().to_string(), value_type(value.trim())));
+        }
+        comments.clear();
+    }
+    let mut out = format!("---@class {}\n", name);
+    for (field, ty) in fields {
+        let _ = writeln!(out, "---@field {} {}", field, ty);
+    }
+    let _ = writeln!(out, "{} = Class()\n", name);
+    out + &methods
+}
+
+/// Writes the Lua Language Server stubs to the given directory, returns the written files.
+///
+/// The base classes are read from the installed bp3d Lua scripts.
+pub fn write(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let root = paths::installed_root().map_err(Error::LuaPath)?;
+    let mut classes = format!("---@meta\n{}", CLASS);
+    for (name, file) in BASE_CLASSES {
+        let source = std::fs::read_to_string(root.join(file)).map_err(Error::Io)?;
+        classes.push('\n');
+        classes += class_stubs(name, &source).trim_end();
+        classes.push('\n');
+    }
+    std::fs::create_dir_all(dir).map_err(Error::Io)?;
+    let files = [
+        (dir.join("bp3d.build.lua"), format!("{}{}", BINDINGS, TYPES)),
+        (dir.join("bp3d.classes.lua"), classes),
+    ];
+    let mut paths = Vec::new();
+    for (path, content) in files {
+        std::fs::write(&path, content).map_err(Error::Io)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
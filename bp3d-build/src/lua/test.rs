@@ -0,0 +1,215 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Runner for Lua unit tests, see [Vm::new_test].
+
+use crate::lua::core::Vm;
+use crate::lua::error::LuaError;
+use crate::lua::lib_test;
+use crate::lua::paths;
+use bp3d_lua::vm::core::load::Script;
+use bp3d_lua::vm::error::Error as LuaVmError;
+use bp3d_lua::vm::table::Table;
+use bp3d_lua::vm::value::types::Function;
+use bp3d_util::simple_error;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+simple_error! {
+    pub Error {
+        Io(std::io::Error) => "io error: {}",
+        Vm(LuaVmError) => "failed to create test vm: {}",
+        Paths(paths::Error) => "lua search path error: {}"
+    }
+}
+
+/// Suffix of Lua test files.
+pub const SUFFIX: &str = "_test.lua";
+
+pub struct CaseResult {
+    pub name: String,
+    pub assertions: usize,
+    pub error: Option<LuaError>,
+}
+
+pub struct FileResult {
+    pub path: PathBuf,
+    pub cases: Vec<CaseResult>,
+    /// The error raised while loading the file, if any.
+    pub error: Option<LuaError>,
+    /// The error raised while removing the temporary directory of the file, if any.
+    pub cleanup: Option<std::io::Error>,
+}
+
+impl FileResult {
+    pub fn failures(&self) -> usize {
+        self.cases.iter().filter(|v| v.error.is_some()).count() + self.error.is_some() as usize
+    }
+}
+
+fn discover_in(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|v| v.to_str()) else {
+            continue;
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            if name != "target" {
+                discover_in(&path, files)?;
+            }
+        } else if name.ends_with(SUFFIX) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Finds all Lua test files under the given path, skipping hidden and target directories.
+pub fn discover(path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if path.is_file() {
+        files.push(path.into());
+    } else {
+        discover_in(path, &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn temp_root() -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!("bp3d-test-{}-{}", std::process::id(), id))
+}
+
+fn run_cases(vm: &Vm, file: &Path) -> Result<Vec<CaseResult>, LuaVmError> {
+    vm.get().scope(|lua| {
+        let script = Script::from_path(file).map_err(|e| LuaVmError::Loader(e.to_string()))?;
+        let cases: Option<Vec<Table>> = lua.run(script)?;
+        let mut results = Vec::new();
+        for case in cases.into_iter().flatten() {
+            let name: String = case.get(c"name")?;
+            let f: Function = case.get(c"fn")?;
+            lib_test::reset(lua)?;
+            let res: Result<(), LuaVmError> = f.call(());
+            results.push(CaseResult {
+                name,
+                assertions: lib_test::assertions(lua)?,
+                error: res.err().map(|e| vm.to_error(&e)),
+            });
+        }
+        Ok(results)
+    })
+}
+
+/// Runs all test cases of a Lua test file.
+///
+/// The file is expected to return the cases declared with the bp3d.test module. Each file runs
+/// in a new [Vm] sandboxed to a temporary directory which is removed afterwards.
+pub fn run_file(project: &Path, file: &Path) -> Result<FileResult, Error> {
+    let paths = paths::resolve(project).map_err(Error::Paths)?;
+    let root = temp_root();
+    std::fs::create_dir_all(&root).map_err(Error::Io)?;
+    let res = Vm::new_test(project, paths, &root).map(|vm| {
+        let res = run_cases(&vm, file);
+        res.map_err(|e| vm.to_error(&e))
+    });
+    // The result is kept even when the cleanup fails, the cleanup error is reported separately.
+    let cleanup = std::fs::remove_dir_all(&root).err();
+    let (cases, error) = match res.map_err(Error::Vm)? {
+        Ok(cases) => (cases, None),
+        Err(e) => (Vec::new(), Some(e.with_phase("load", None))),
+    };
+    Ok(FileResult {
+        path: file.into(),
+        cases,
+        error,
+        cleanup,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lua::state;
+
+    #[test]
+    fn cannot_leave_test_mode() {
+        let root = temp_root();
+        std::fs::create_dir_all(&root).unwrap();
+        let paths = paths::SearchPaths {
+            root: root.join("lua"),
+            extra: Vec::new(),
+        };
+        let vm = Vm::new_test(&root, paths, &root).unwrap();
+        vm.get()
+            .scope(|lua| {
+                lib_test::reset(lua)?;
+                lua.run_code::<()>(
+                    c"assert(__bp3d_build == nil)
+                    __bp3d_build = {}
+                    rawset(_G, '__bp3d_build', nil)
+                    assert(bp3d.build.test.root() ~= nil)
+                    bp3d.build.test.pass()",
+                )?;
+                assert_eq!(lib_test::assertions(lua)?, 1);
+                assert_eq!(state::test_root(lua)?.as_deref(), Some(&*root));
+                Ok(())
+            })
+            .unwrap();
+        drop(vm);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn target_behaves_like_a_string() {
+        let root = temp_root();
+        std::fs::create_dir_all(&root).unwrap();
+        let paths = paths::SearchPaths {
+            root: root.join("lua"),
+            extra: Vec::new(),
+        };
+        let vm = Vm::new_test(&root, paths, &root).unwrap();
+        vm.get()
+            .run_code::<()>(
+                c"local target = bp3d.build.Target.parse('x86_64-pc-windows-msvc')
+                assert(tostring(target) == 'x86_64-pc-windows-msvc')
+                assert('target/' .. target == 'target/x86_64-pc-windows-msvc')
+                assert(target .. '/debug' == 'x86_64-pc-windows-msvc/debug')
+                assert(target == bp3d.build.Target.parse('x86_64-pc-windows-msvc'))
+                assert(target ~= bp3d.build.Target.parse('x86_64-unknown-linux-gnu'))
+                assert(target:isWindows())",
+            )
+            .unwrap();
+        drop(vm);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
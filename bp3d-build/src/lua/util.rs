@@ -26,6 +26,7 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use crate::system::test_report::{TestCase, TestReport, TestStatus, TestSuite};
 use crate::system::Package;
 use bp3d_lua::vm::table::Table;
 use bp3d_lua::vm::Result;
@@ -57,3 +58,37 @@ pub fn convert_package<'a>(vm: &'a Vm, package: &dyn Package) -> Result<Table<'a
     }
     Ok(res)
 }
+
+/// Converts the result of a Lua `test` method.
+///
+/// The expected format is a list of suites `{ name = "...", tests = { ... } }` where each test is
+/// `{ name = "...", status = "passed" | "failed" | "ignored", time = 0.1, message = "..." }`.
+pub fn convert_test_report(suites: Vec<Table>) -> Result<TestReport> {
+    let mut report = TestReport::new();
+    for suite in suites {
+        let name: String = suite.get(c"name")?;
+        let mut res = TestSuite::new(name);
+        let tests: Option<Vec<Table>> = suite.get(c"tests")?;
+        for test in tests.into_iter().flatten() {
+            let status: String = test.get(c"status")?;
+            let mut message: Option<String> = test.get(c"message")?;
+            let status = match &*status {
+                "passed" | "ok" => TestStatus::Passed,
+                "ignored" | "skipped" => TestStatus::Ignored,
+                "failed" => TestStatus::Failed,
+                _ => {
+                    message = Some(format!("unknown test status: {}", status));
+                    TestStatus::Failed
+                }
+            };
+            res.cases.push(TestCase {
+                name: test.get(c"name")?,
+                status,
+                time: test.get(c"time")?,
+                message,
+            });
+        }
+        report.add_suite(res);
+    }
+    Ok(report)
+}
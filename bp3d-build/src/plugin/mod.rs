@@ -0,0 +1,220 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::system::artifact::{Artifact, LibType, List, Type};
+use crate::system::test_report::{TestCase, TestReport, TestStatus};
+use crate::system::{Context, Features};
+use bp3d_debug::warning;
+use std::ffi::c_void;
+use std::path::Path;
+
+pub use bp3d_plugin as abi;
+
+mod registry;
+
+pub use registry::{search_paths, Error, Plugin, Registry};
+
+fn type_to_raw(ty: Type) -> u32 {
+    match ty {
+        Type::Bin => abi::ARTIFACT_BIN,
+        Type::Lib(LibType::Dynamic) => abi::ARTIFACT_DYNAMIC_LIB,
+        Type::Lib(LibType::Static) => abi::ARTIFACT_STATIC_LIB,
+        Type::Header => abi::ARTIFACT_HEADER,
+        Type::Config => abi::ARTIFACT_CONFIG,
+        Type::Resource => abi::ARTIFACT_RESOURCE,
+    }
+}
+
+fn type_from_raw(ty: u32) -> Option<Type> {
+    match ty {
+        abi::ARTIFACT_BIN => Some(Type::Bin),
+        abi::ARTIFACT_DYNAMIC_LIB => Some(Type::Lib(LibType::Dynamic)),
+        abi::ARTIFACT_STATIC_LIB => Some(Type::Lib(LibType::Static)),
+        abi::ARTIFACT_HEADER => Some(Type::Header),
+        abi::ARTIFACT_CONFIG => Some(Type::Config),
+        abi::ARTIFACT_RESOURCE => Some(Type::Resource),
+        _ => None,
+    }
+}
+
+fn path_to_raw(path: Option<&Path>) -> Option<abi::Str> {
+    match path {
+        Some(path) => path.to_str().map(abi::Str::new),
+        None => Some(abi::Str::EMPTY),
+    }
+}
+
+/// Converts an artifact to its ABI representation, returns [None] if a path is not valid UTF-8.
+pub fn artifact_to_raw(artifact: &Artifact) -> Option<abi::Artifact> {
+    Some(abi::Artifact {
+        ty: type_to_raw(artifact.ty()),
+        name: abi::Str::new(artifact.name()),
+        path: path_to_raw(Some(artifact.path()))?,
+        debug_info: path_to_raw(artifact.debug_info())?,
+        exports: path_to_raw(artifact.exports())?,
+    })
+}
+
+/// Converts an artifact from its ABI representation.
+///
+/// # Safety
+///
+/// All strings in `artifact` must be valid for reads.
+pub unsafe fn artifact_from_raw(artifact: &abi::Artifact) -> Option<Artifact> {
+    let optional = |v: abi::Str| v.as_str().map(|v| (!v.is_empty()).then(|| Path::new(v)));
+    Some(Artifact::new(
+        type_from_raw(artifact.ty)?,
+        Path::new(artifact.path.as_str()?),
+        artifact.name.as_str()?,
+        optional(artifact.debug_info)?,
+        optional(artifact.exports)?,
+    ))
+}
+
+/// ABI representation of a build [Context].
+pub struct RawContext {
+    _features: Vec<abi::Str>,
+    raw: abi::Context,
+}
+
+impl RawContext {
+    /// Converts a build context, returns [None] if the project path is not valid UTF-8.
+    pub fn new(ctx: &Context) -> Option<Self> {
+        let features: Vec<abi::Str> = ctx.features.iter().map(|v| abi::Str::new(v)).collect();
+        let raw = abi::Context {
+            path: abi::Str::new(ctx.path.to_str()?),
+            configuration: abi::Str::new(ctx.configuration),
+            all_features: ctx.features == Features::All,
+            features: abi::StrList::new(&features),
+        };
+        Some(Self {
+            _features: features,
+            raw,
+        })
+    }
+
+    pub fn as_raw(&self) -> &abi::Context {
+        &self.raw
+    }
+}
+
+/// Collects artifacts and errors returned by a plugin through an [abi::Output].
+pub struct Collector {
+    list: List,
+    report: TestReport,
+    error: Option<String>,
+}
+
+impl Default for Collector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        Self {
+            list: List::new(),
+            report: TestReport::new(),
+            error: None,
+        }
+    }
+
+    /// Returns the output table pointing to this collector.
+    ///
+    /// The returned table must not outlive this collector and the collector must not move while
+    /// the table is in use.
+    pub fn output(&mut self) -> abi::Output {
+        abi::Output {
+            data: self as *mut Collector as *mut c_void,
+            add_artifact,
+            add_test,
+            set_error,
+        }
+    }
+
+    /// Returns the error message reported by the plugin, if any.
+    pub fn take_error(&mut self) -> Option<String> {
+        self.error.take()
+    }
+
+    /// Finishes a call to the plugin, returns the collected test report on success or the error
+    /// message on failure.
+    pub fn finish_test(self, success: bool) -> Result<TestReport, String> {
+        match success {
+            true => Ok(self.report),
+            false => Err(self.error.unwrap_or_else(|| "unknown plugin error".into())),
+        }
+    }
+
+    /// Finishes a call to the plugin, returns the list of collected artifacts on success or the
+    /// error message on failure.
+    pub fn finish(self, success: bool) -> Result<List, String> {
+        match success {
+            true => Ok(self.list),
+            false => Err(self.error.unwrap_or_else(|| "unknown plugin error".into())),
+        }
+    }
+}
+
+unsafe extern "C" fn add_artifact(data: *mut c_void, artifact: *const abi::Artifact) {
+    let collector = &mut *(data as *mut Collector);
+    match artifact_from_raw(&*artifact) {
+        Some(artifact) => collector.list.add(artifact),
+        None => warning!("Ignored invalid artifact returned by plugin"),
+    }
+}
+
+unsafe extern "C" fn add_test(data: *mut c_void, test: *const abi::TestCase) {
+    let collector = &mut *(data as *mut Collector);
+    let test = &*test;
+    let status = match test.status {
+        abi::TEST_PASSED => TestStatus::Passed,
+        abi::TEST_IGNORED => TestStatus::Ignored,
+        _ => TestStatus::Failed,
+    };
+    let (Some(suite), Some(name), Some(message)) = (
+        test.suite.as_str(),
+        test.name.as_str(),
+        test.message.as_str(),
+    ) else {
+        warning!("Ignored invalid test case returned by plugin");
+        return;
+    };
+    collector.report.suite_mut(suite).cases.push(TestCase {
+        name: name.into(),
+        status,
+        time: (test.time >= 0.0).then_some(test.time),
+        message: (!message.is_empty()).then(|| message.into()),
+    });
+}
+
+unsafe extern "C" fn set_error(data: *mut c_void, message: abi::Str) {
+    let collector = &mut *(data as *mut Collector);
+    collector.error = Some(message.as_str().unwrap_or("invalid utf-8").into());
+}
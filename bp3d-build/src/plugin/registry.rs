@@ -0,0 +1,245 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::plugin::abi;
+use bp3d_debug::{debug, warning};
+use bp3d_os::module::library::Library;
+use bp3d_os::module::loader::ModuleLoader;
+use bp3d_util::simple_error;
+use std::ffi::c_void;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+simple_error! {
+    pub Error {
+        Load(bp3d_os::module::error::Error) => "failed to load module: {}",
+        InvalidFileName => "invalid plugin file name",
+        MissingEntryPoint => "missing plugin entry point",
+        InvalidAbi(u32) => "unsupported plugin ABI version: {}",
+        InvalidUtf8 => "invalid utf-8",
+        AlreadyLoaded(String) => "plugin already loaded: {}"
+    }
+}
+
+/// Information about a loaded plugin.
+pub struct Plugin {
+    pub name: String,
+    pub version: String,
+    pub path: PathBuf,
+    pub build_systems: Vec<String>,
+    pub packagers: Vec<String>,
+}
+
+#[derive(Default)]
+struct Registrations {
+    build_systems: Vec<&'static abi::BuildSystem>,
+    packagers: Vec<&'static abi::Packager>,
+}
+
+unsafe extern "C" fn register_build_system(data: *mut c_void, table: *const abi::BuildSystem) {
+    let registrations = &mut *(data as *mut Registrations);
+    registrations.build_systems.push(&*table);
+}
+
+unsafe extern "C" fn register_packager(data: *mut c_void, table: *const abi::Packager) {
+    let registrations = &mut *(data as *mut Registrations);
+    registrations.packagers.push(&*table);
+}
+
+fn table_name(name: abi::Str) -> Result<String, Error> {
+    unsafe { name.as_str() }
+        .map(String::from)
+        .ok_or(Error::InvalidUtf8)
+}
+
+/// Returns the module name of a plugin library, which is its file name without the platform
+/// specific prefix and extension.
+fn module_name(path: &Path) -> Option<&str> {
+    let stem = path.file_stem()?.to_str()?;
+    Some(
+        stem.strip_prefix(std::env::consts::DLL_PREFIX)
+            .unwrap_or(stem),
+    )
+}
+
+/// Loads a plugin library through the module loader and returns its descriptor.
+fn load_module(path: &Path) -> Result<&'static abi::Plugin, Error> {
+    let name = module_name(path).ok_or(Error::InvalidFileName)?;
+    let mut loader = ModuleLoader::lock();
+    if let Some(dir) = path.parent() {
+        loader.add_search_path(dir);
+    }
+    let module = unsafe { loader.load(name) }.map_err(Error::Load)?;
+    let symbol = module
+        .lib()
+        .load_symbol::<abi::Plugin>(abi::ENTRY_POINT)
+        .map_err(Error::Load)?
+        .ok_or(Error::MissingEntryPoint)?;
+    // Modules are never unloaded by the registry, which makes the descriptor 'static.
+    Ok(unsafe { &*(symbol.as_ptr() as *const abi::Plugin) })
+}
+
+/// The global list of loaded plugins.
+///
+/// Plugin modules are never unloaded, which is what allows handing out `'static` references to
+/// the registered tables.
+pub struct Registry {
+    plugins: Vec<Plugin>,
+    build_systems: Vec<&'static abi::BuildSystem>,
+    packagers: Vec<(String, &'static abi::Packager)>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    plugins: Vec::new(),
+    build_systems: Vec::new(),
+    packagers: Vec::new(),
+});
+
+impl Registry {
+    pub fn lock() -> MutexGuard<'static, Registry> {
+        REGISTRY.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Loads a single plugin library.
+    pub fn load(&mut self, path: &Path) -> Result<&Plugin, Error> {
+        debug!("Loading plugin {:?}...", path);
+        let plugin = load_module(path)?;
+        if plugin.abi_version != abi::ABI_VERSION {
+            return Err(Error::InvalidAbi(plugin.abi_version));
+        }
+        let name = table_name(plugin.name)?;
+        let version = table_name(plugin.version)?;
+        if self.plugins.iter().any(|v| v.name == name) {
+            return Err(Error::AlreadyLoaded(name));
+        }
+        let mut registrations = Registrations::default();
+        let registrar = abi::Registrar {
+            data: &mut registrations as *mut Registrations as *mut c_void,
+            register_build_system,
+            register_packager,
+        };
+        unsafe { (plugin.register)(&registrar) };
+        // Validate every table before registering anything, so that a failure leaves the
+        // registry untouched.
+        let build_systems = registrations
+            .build_systems
+            .into_iter()
+            .map(|table| Ok((table_name(table.name)?, table)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let packagers = registrations
+            .packagers
+            .into_iter()
+            .map(|table| Ok((table_name(table.name)?, table)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut info = Plugin {
+            name,
+            version,
+            path: path.into(),
+            build_systems: Vec::new(),
+            packagers: Vec::new(),
+        };
+        for (name, table) in build_systems {
+            info.build_systems.push(name);
+            self.build_systems.push(table);
+        }
+        for (name, table) in packagers {
+            if self.packagers.iter().any(|(v, _)| *v == name) {
+                warning!("Packager {} is already registered, ignoring...", name);
+                continue;
+            }
+            info.packagers.push(name.clone());
+            self.packagers.push((name, table));
+        }
+        self.plugins.push(info);
+        Ok(self.plugins.last().unwrap())
+    }
+
+    /// Loads all plugin libraries found in the given directory.
+    ///
+    /// Plugins which fail to load are reported as warnings.
+    pub fn load_dir(&mut self, dir: &Path) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_library = path
+                .extension()
+                .map(|v| v == std::env::consts::DLL_EXTENSION)
+                .unwrap_or(false);
+            if !is_library {
+                continue;
+            }
+            if let Err(e) = self.load(&path) {
+                warning!("Failed to load plugin {:?}: {}", path, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads all plugins found in the default [search_paths].
+    pub fn load_default(&mut self) {
+        for path in search_paths() {
+            if path.is_dir() {
+                if let Err(e) = self.load_dir(&path) {
+                    warning!("Failed to read plugin directory {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+
+    /// Returns the list of loaded plugins.
+    pub fn plugins(&self) -> &[Plugin] {
+        &self.plugins
+    }
+
+    /// Returns all registered build systems, in load order.
+    pub fn build_systems(&self) -> &[&'static abi::BuildSystem] {
+        &self.build_systems
+    }
+
+    pub fn find_packager(&self, name: &str) -> Option<&'static abi::Packager> {
+        self.packagers
+            .iter()
+            .find(|(v, _)| v == name)
+            .map(|(_, table)| *table)
+    }
+}
+
+/// Returns the list of directories to search for plugins.
+///
+/// This includes all directories listed in the `BP3D_PLUGIN_PATH` environment variable followed
+/// by the plugin directories of the bp3d installation.
+pub fn search_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(var) = std::env::var_os("BP3D_PLUGIN_PATH") {
+        paths.extend(std::env::split_paths(&var));
+    }
+    if let Some(exe) = bp3d_os::assets::get_executable_path() {
+        paths.push(exe.join("../lib/bp3d/plugins"));
+        paths.push(exe.join("plugins"));
+    }
+    paths
+}
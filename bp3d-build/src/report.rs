@@ -0,0 +1,308 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::system::artifact::{LibType, List, Type};
+use bp3d_util::simple_error;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+simple_error! {
+    pub Error {
+        Io(std::io::Error) => "io error: {}",
+        Json(serde_json::Error) => "json error: {}"
+    }
+}
+
+/// The name of the report file, relative to the project target directory.
+pub const REPORT_NAME: &str = "bp3d-report.json";
+
+/// Duration of a build phase.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Phase {
+    pub package: String,
+    pub name: String,
+    pub target: Option<String>,
+    /// Duration in seconds.
+    pub duration: f64,
+}
+
+/// Duration of a command spawned while building.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommandTime {
+    pub package: Option<String>,
+    pub phase: Option<String>,
+    pub target: Option<String>,
+    pub command: String,
+    /// Duration in seconds.
+    pub duration: f64,
+}
+
+/// Size of an artifact produced by the pre-package phase.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ArtifactSize {
+    pub package: String,
+    pub target: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub path: PathBuf,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+/// Difference of a metric between two reports.
+pub struct Delta {
+    pub key: String,
+    pub old: Option<f64>,
+    pub new: Option<f64>,
+}
+
+impl Delta {
+    /// Returns the relative change in percent, if the metric exists in both reports.
+    pub fn percent(&self) -> Option<f64> {
+        match (self.old, self.new) {
+            (Some(old), Some(new)) if old > 0.0 => Some((new - old) / old * 100.0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Report {
+    pub phases: Vec<Phase>,
+    pub commands: Vec<CommandTime>,
+    pub artifacts: Vec<ArtifactSize>,
+}
+
+impl Report {
+    pub fn is_empty(&self) -> bool {
+        self.phases.is_empty() && self.commands.is_empty() && self.artifacts.is_empty()
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let data = std::fs::read(path).map_err(Error::Io)?;
+        serde_json::from_slice(&data).map_err(Error::Json)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let data = serde_json::to_vec_pretty(self).map_err(Error::Json)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(Error::Io)?;
+        }
+        std::fs::write(path, data).map_err(Error::Io)
+    }
+
+    /// Returns the total duration of each phase keyed by `package/phase[/target]`.
+    pub fn phase_times(&self) -> BTreeMap<String, f64> {
+        let mut map = BTreeMap::new();
+        for phase in &self.phases {
+            let key = match &phase.target {
+                Some(target) => format!("{}/{}/{}", phase.package, phase.name, target),
+                None => format!("{}/{}", phase.package, phase.name),
+            };
+            *map.entry(key).or_default() += phase.duration;
+        }
+        map
+    }
+
+    /// Returns the size of each artifact keyed by `package/target/name (type)`.
+    pub fn artifact_sizes(&self) -> BTreeMap<String, u64> {
+        self.artifacts
+            .iter()
+            .map(|v| {
+                let key = format!("{}/{}/{} ({})", v.package, v.target, v.name, v.ty);
+                (key, v.size)
+            })
+            .collect()
+    }
+
+    fn diff(old: BTreeMap<String, f64>, new: BTreeMap<String, f64>) -> Vec<Delta> {
+        let mut deltas: Vec<Delta> = new
+            .iter()
+            .map(|(k, v)| Delta {
+                key: k.clone(),
+                old: old.get(k).copied(),
+                new: Some(*v),
+            })
+            .collect();
+        deltas.extend(
+            old.iter()
+                .filter(|(k, _)| !new.contains_key(*k))
+                .map(|(k, v)| Delta {
+                    key: k.clone(),
+                    old: Some(*v),
+                    new: None,
+                }),
+        );
+        deltas
+    }
+
+    /// Compares phase durations of this report against an older report.
+    pub fn compare_times(&self, old: &Report) -> Vec<Delta> {
+        Self::diff(old.phase_times(), self.phase_times())
+    }
+
+    /// Compares artifact sizes of this report against an older report.
+    pub fn compare_sizes(&self, old: &Report) -> Vec<Delta> {
+        let map = |v: BTreeMap<String, u64>| -> BTreeMap<String, f64> {
+            v.into_iter().map(|(k, v)| (k, v as f64)).collect()
+        };
+        Self::diff(map(old.artifact_sizes()), map(self.artifact_sizes()))
+    }
+}
+
+struct Scope {
+    package: String,
+    phase: String,
+    target: Option<String>,
+}
+
+struct Recorder {
+    report: Report,
+    scopes: Vec<Scope>,
+}
+
+static RECORDER: Mutex<Recorder> = Mutex::new(Recorder {
+    report: Report {
+        phases: Vec::new(),
+        commands: Vec::new(),
+        artifacts: Vec::new(),
+    },
+    scopes: Vec::new(),
+});
+
+/// Locks the recorder, a panic while recording must not prevent saving the report.
+fn recorder() -> MutexGuard<'static, Recorder> {
+    RECORDER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn ty_name(ty: Type) -> &'static str {
+    match ty {
+        Type::Bin => "bin",
+        Type::Lib(LibType::Dynamic) => "lib::dynamic",
+        Type::Lib(LibType::Static) => "lib::static",
+        Type::Header => "header",
+        Type::Config => "config",
+        Type::Resource => "resource",
+    }
+}
+
+fn size_of(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|v| v.filter_map(|v| v.ok()).map(|v| size_of(&v.path())).sum())
+        .unwrap_or_default()
+}
+
+/// Runs the given function as a build phase, recording its duration.
+///
+/// Commands spawned while the function runs are attributed to the innermost phase.
+pub fn phase<T>(package: &str, name: &str, target: Option<&str>, f: impl FnOnce() -> T) -> T {
+    recorder().scopes.push(Scope {
+        package: package.into(),
+        phase: name.into(),
+        target: target.map(|v| v.into()),
+    });
+    let start = Instant::now();
+    let res = f();
+    let duration = start.elapsed().as_secs_f64();
+    let mut recorder = recorder();
+    if let Some(scope) = recorder.scopes.pop() {
+        recorder.report.phases.push(Phase {
+            package: scope.package,
+            name: scope.phase,
+            target: scope.target,
+            duration,
+        });
+    }
+    res
+}
+
+/// Records the duration of a command spawned while building.
+pub fn record_command(cmd: &Command, duration: Duration) {
+    let mut command = cmd.get_program().to_string_lossy().into_owned();
+    for arg in cmd.get_args() {
+        command.push(' ');
+        command += &arg.to_string_lossy();
+    }
+    let mut recorder = recorder();
+    let scope = recorder.scopes.last();
+    let time = CommandTime {
+        package: scope.map(|v| v.package.clone()),
+        phase: scope.map(|v| v.phase.clone()),
+        target: scope.and_then(|v| v.target.clone()),
+        command,
+        duration: duration.as_secs_f64(),
+    };
+    recorder.report.commands.push(time);
+}
+
+/// Runs the given command using the given function, recording its duration.
+pub fn command<T>(cmd: &mut Command, f: impl FnOnce(&mut Command) -> T) -> T {
+    let start = Instant::now();
+    let res = f(cmd);
+    record_command(cmd, start.elapsed());
+    res
+}
+
+/// Records the size of all artifacts in the given list.
+pub fn artifacts(package: &str, target: &str, list: &List) {
+    let sizes: Vec<ArtifactSize> = list
+        .iter()
+        .map(|v| ArtifactSize {
+            package: package.into(),
+            target: target.into(),
+            name: v.name().into(),
+            ty: ty_name(v.ty()).into(),
+            path: v.path().into(),
+            size: size_of(v.path()),
+        })
+        .collect();
+    recorder().report.artifacts.extend(sizes);
+}
+
+/// Returns the report recorded so far and resets the recorder.
+pub fn take() -> Report {
+    std::mem::take(&mut recorder().report)
+}
+
+/// Discards everything recorded so far, including phases left open by a panic.
+pub fn reset() {
+    let mut recorder = recorder();
+    recorder.report = Report::default();
+    recorder.scopes.clear();
+}
@@ -28,8 +28,9 @@
 
 use crate::system::finder::Finder;
 use bp3d_debug::warning;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use std::io::{Error, ErrorKind};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 #[derive(Eq, PartialEq, Debug, Copy, Clone)]
 pub enum LibType {
@@ -56,6 +57,22 @@ pub struct Artifact {
 }
 
 impl Artifact {
+    pub fn new(
+        ty: Type,
+        path: &Path,
+        name: &str,
+        debug_info: Option<&Path>,
+        exports: Option<&Path>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            debug_info: debug_info.map(|v| v.into()),
+            exports: exports.map(|v| v.into()),
+            name: name.into(),
+            ty,
+        }
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -76,6 +93,26 @@ impl Artifact {
         self.ty
     }
 
+    pub fn with_path(mut self, path: &Path) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn with_debug_info(mut self, debug_info: Option<&Path>) -> Self {
+        self.debug_info = debug_info.map(|v| v.into());
+        self
+    }
+
+    pub fn with_exports(mut self, exports: Option<&Path>) -> Self {
+        self.exports = exports.map(|v| v.into());
+        self
+    }
+
     pub fn find_bin(path: &Path, name: &str, remove_debug_info: bool) -> Option<Self> {
         let mut res = Finder::new(path).find_bin(name);
         if remove_debug_info {
@@ -135,7 +172,118 @@ impl Artifact {
     }
 }
 
-#[derive(Clone)]
+/// Describes which files of a folder are added to a [List] by [List::add_glob] and under which
+/// names.
+#[derive(Clone, Default)]
+pub struct Selection {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    remap: Vec<(String, String)>,
+    flatten: bool,
+    hidden: bool,
+}
+
+impl Selection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a glob pattern matched against the path of each file relative to the folder, using
+    /// '/' as separator, `*` does not match '/' while `**` matches any number of folders.
+    /// Patterns starting with '!' exclude files instead of including them. When no include
+    /// pattern is given all files are included.
+    pub fn pattern(mut self, pattern: &str) -> Self {
+        match pattern.strip_prefix('!') {
+            Some(v) => self.exclude.push(v.into()),
+            None => self.include.push(pattern.into()),
+        }
+        self
+    }
+
+    pub fn patterns<'a>(self, patterns: impl IntoIterator<Item = &'a str>) -> Self {
+        patterns
+            .into_iter()
+            .fold(self, |v, pattern| v.pattern(pattern))
+    }
+
+    /// Renames files whose relative path starts with the folder `from` so that they start with
+    /// `to` instead. When multiple rules apply, the longest `from` wins.
+    pub fn remap(mut self, from: &str, to: &str) -> Self {
+        self.remap.push((
+            from.trim_end_matches('/').into(),
+            to.trim_end_matches('/').into(),
+        ));
+        self
+    }
+
+    /// Drops the folder structure, naming each artifact after its file name only.
+    pub fn flatten(mut self, flag: bool) -> Self {
+        self.flatten = flag;
+        self
+    }
+
+    /// Includes hidden files and folders (names starting with '.'), which are skipped by default.
+    pub fn hidden(mut self, flag: bool) -> Self {
+        self.hidden = flag;
+        self
+    }
+
+    fn map_name(&self, path: &str) -> String {
+        let remapped = self
+            .remap
+            .iter()
+            .filter_map(|(from, to)| {
+                let rest = match from.is_empty() {
+                    true => Some(path),
+                    false => path.strip_prefix(from.as_str())?.strip_prefix('/'),
+                }?;
+                Some((from.len(), to, rest))
+            })
+            .max_by_key(|(len, _, _)| *len)
+            .map(|(_, to, rest)| match to.is_empty() {
+                true => rest.to_string(),
+                false => format!("{}/{}", to, rest),
+            })
+            .unwrap_or_else(|| path.into());
+        match self.flatten {
+            true => match remapped.rsplit_once('/') {
+                Some((_, v)) => v.into(),
+                None => remapped,
+            },
+            false => remapped,
+        }
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> std::io::Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, e))
+}
+
+/// Returns true when the name is relative and does not contain any parent component.
+fn is_relative(name: &str) -> bool {
+    !name.is_empty()
+        && Path::new(name)
+            .components()
+            .all(|v| matches!(v, Component::Normal(_) | Component::CurDir))
+}
+
+struct Matcher<'a> {
+    selection: &'a Selection,
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+#[derive(Clone, Default)]
 pub struct List {
     content: Vec<Artifact>,
 }
@@ -178,7 +326,7 @@ impl List {
                     + file
                         .file_name()
                         .to_str()
-                        .ok_or_else(|| Error::new(ErrorKind::Other, "invalid filename"))?;
+                        .ok_or_else(|| Error::other("invalid filename"))?;
                 if !excluded.is_empty() && name1.starts_with(excluded) {
                     // Skip excluded folder
                     continue;
@@ -204,15 +352,266 @@ impl List {
         self.add_folder_exclude(ty1, path, "", name)
     }
 
+    fn add_glob_recursive(
+        &mut self,
+        ty1: Type,
+        path: &Path,
+        matcher: &Matcher,
+        relative: &str,
+        name: &str,
+        parents: &mut Vec<PathBuf>,
+    ) -> std::io::Result<()> {
+        let mut files = std::fs::read_dir(path)?.collect::<std::io::Result<Vec<_>>>()?;
+        // Sort entries so that the resulting list does not depend on the file system.
+        files.sort_by_key(|v| v.file_name());
+        for file in files {
+            // Symbolic links are followed, broken links are skipped.
+            let ty = match std::fs::metadata(file.path()) {
+                Ok(v) => v.file_type(),
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            let file_name = file.file_name();
+            let file_name = file_name
+                .to_str()
+                .ok_or_else(|| Error::other("invalid filename"))?;
+            if !matcher.selection.hidden && file_name.starts_with('.') {
+                continue;
+            }
+            let relative1 = String::from(relative) + file_name;
+            if matcher.exclude.is_match(&relative1) {
+                continue;
+            }
+            if ty.is_file() {
+                if !matcher.selection.include.is_empty() && !matcher.include.is_match(&relative1) {
+                    continue;
+                }
+                let mapped = matcher.selection.map_name(&relative1);
+                // A remap to a parent or absolute folder would place the artifact outside of
+                // the folder it is packaged in.
+                if !is_relative(&mapped) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("artifact name {} escapes its folder", mapped),
+                    ));
+                }
+                let artifact = Artifact {
+                    path: file.path(),
+                    debug_info: None,
+                    exports: None,
+                    name: String::from(name) + &mapped,
+                    ty: ty1,
+                };
+                self.add(artifact);
+            } else if ty.is_dir() {
+                // Skip links pointing to a parent folder which would otherwise recurse forever.
+                let path = file.path();
+                let real = path.canonicalize()?;
+                if parents.contains(&real) {
+                    continue;
+                }
+                parents.push(real);
+                let res =
+                    self.add_glob_recursive(ty1, &path, matcher, &(relative1 + "/"), name, parents);
+                parents.pop();
+                res?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the files of a folder which are selected by the given [Selection], prefixing the name
+    /// of each artifact with `name`.
+    pub fn add_glob(
+        &mut self,
+        ty1: Type,
+        path: &Path,
+        selection: &Selection,
+        name: &str,
+    ) -> std::io::Result<()> {
+        let matcher = Matcher {
+            selection,
+            include: build_glob_set(&selection.include)?,
+            exclude: build_glob_set(&selection.exclude)?,
+        };
+        if path.exists() {
+            let mut parents = vec![path.canonicalize()?];
+            self.add_glob_recursive(ty1, path, &matcher, "", name, &mut parents)?;
+        }
+        Ok(())
+    }
+
+    pub fn merge(&mut self, other: List) {
+        for artifact in other.content {
+            self.add(artifact);
+        }
+    }
+
+    pub fn merge_namespaced(&mut self, namespace: &str, other: List) {
+        for mut artifact in other.content {
+            artifact.name = format!("{}/{}", namespace, artifact.name);
+            self.add(artifact);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Artifact> {
+        self.content.iter()
+    }
+
+    pub fn as_slice(&self) -> &[Artifact] {
+        &self.content
+    }
+
     pub fn find(&self, ty: Type) -> impl Iterator<Item = &Artifact> {
         self.content.iter().filter(move |v| v.ty == ty)
     }
 
     pub fn find_first(&self, ty: Type) -> Option<&Artifact> {
-        self.content.iter().filter(move |v| v.ty == ty).next()
+        self.content.iter().find(move |v| v.ty == ty)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Artifact> {
+        self.content.iter().find(|v| v.name == name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Artifact> {
+        let index = self.content.iter().position(|v| v.name == name)?;
+        Some(self.content.remove(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.content.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
     }
 
     pub fn into_inner(self) -> Vec<Artifact> {
         self.content
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("bp3d-glob-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&root);
+        for file in [
+            "top.h",
+            "a/x.h",
+            "a/internal/y.h",
+            "b/r.txt",
+            ".hidden/z.h",
+            "a/.w.h",
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, file).unwrap();
+        }
+        root
+    }
+
+    fn names(root: &Path, selection: Selection) -> Vec<String> {
+        let mut list = List::new();
+        list.add_glob(Type::Header, root, &selection, "include/")
+            .unwrap();
+        list.iter().map(|v| v.name().into()).collect()
+    }
+
+    #[test]
+    fn patterns() {
+        let root = tree("patterns");
+        let all = names(&root, Selection::new());
+        assert_eq!(
+            all,
+            [
+                "include/a/internal/y.h",
+                "include/a/x.h",
+                "include/b/r.txt",
+                "include/top.h"
+            ]
+        );
+        assert_eq!(
+            names(&root, Selection::new().pattern("*.h")),
+            ["include/top.h"]
+        );
+        let headers = names(
+            &root,
+            Selection::new().pattern("**/*.h").pattern("!a/internal"),
+        );
+        assert_eq!(headers, ["include/a/x.h", "include/top.h"]);
+        let hidden = names(&root, Selection::new().pattern("**/*.h").hidden(true));
+        assert_eq!(
+            hidden,
+            [
+                "include/.hidden/z.h",
+                "include/a/.w.h",
+                "include/a/internal/y.h",
+                "include/a/x.h",
+                "include/top.h"
+            ]
+        );
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn remap_and_flatten() {
+        let root = tree("remap");
+        let selection = Selection::new()
+            .pattern("a/**")
+            .remap("a", "headers")
+            .remap("a/internal", "");
+        assert_eq!(
+            names(&root, selection),
+            ["include/y.h", "include/headers/x.h"]
+        );
+        let selection = Selection::new().pattern("**/*.h").flatten(true);
+        assert_eq!(
+            names(&root, selection),
+            ["include/y.h", "include/x.h", "include/top.h"]
+        );
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn escaping_paths() {
+        let root = tree("escaping");
+        // Relative paths never contain parent components so such patterns match nothing.
+        assert!(names(&root.join("a"), Selection::new().pattern("../*")).is_empty());
+        for to in ["..", "../out", "/abs", "a/../.."] {
+            let selection = Selection::new().pattern("b/*").remap("b", to);
+            let mut list = List::new();
+            let err = list
+                .add_glob(Type::Resource, &root, &selection, "")
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidInput, "{}", to);
+        }
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks() {
+        let root = tree("symlinks");
+        let outside = tree("symlinks-outside");
+        std::os::unix::fs::symlink(&root, root.join("a/loop")).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("b/out")).unwrap();
+        std::os::unix::fs::symlink(root.join("missing"), root.join("b/broken.h")).unwrap();
+        let names = names(&root, Selection::new().pattern("b/**"));
+        assert_eq!(
+            names,
+            [
+                "include/b/out/a/internal/y.h",
+                "include/b/out/a/x.h",
+                "include/b/out/b/r.txt",
+                "include/b/out/top.h",
+                "include/b/r.txt"
+            ]
+        );
+        std::fs::remove_dir_all(&root).unwrap();
+        std::fs::remove_dir_all(&outside).unwrap();
+    }
+}
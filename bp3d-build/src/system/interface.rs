@@ -26,6 +26,7 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use crate::system::target::Target;
 use std::borrow::Cow;
 use std::ops::Deref;
 use std::path::Path;
@@ -60,10 +61,40 @@ pub struct Context<'a> {
     pub features: Features<'a>,
 }
 
+/// The scope of a clean operation.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum CleanScope {
+    /// Packaging outputs only (dist folders, installers, frameworks).
+    Dist,
+    /// All outputs of the given targets.
+    Target,
+    /// All outputs of the current configuration for the given targets.
+    Configuration,
+    /// Dependencies installed by fpkg for the given targets.
+    Ext,
+    /// Everything.
+    All,
+}
+
+impl CleanScope {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CleanScope::Dist => "dist",
+            CleanScope::Target => "target",
+            CleanScope::Configuration => "configuration",
+            CleanScope::Ext => "ext",
+            CleanScope::All => "all",
+        }
+    }
+}
+
 pub trait BuildSystem {
-    type Error: std::error::Error;
+    type Error: std::error::Error + 'static;
     type Package: Package;
 
+    /// Returns the name of this build system.
+    fn name(&self, package: &Self::Package) -> &str;
+
     /// Configure the build.
     fn configure(
         &self,
@@ -77,7 +108,7 @@ pub trait BuildSystem {
         &self,
         package: &Self::Package,
         ctx: &Context,
-        target: &str,
+        target: &Target,
     ) -> Result<(), Self::Error>;
 
     /// Prepares the project for packaging to a specific target.
@@ -88,8 +119,27 @@ pub trait BuildSystem {
         &self,
         package: &Self::Package,
         ctx: &Context,
-        target: &str,
+        target: &Target,
     ) -> Result<crate::system::artifact::List, Self::Error>;
+
+    /// Runs the tests of the project for a specific target.
+    fn test(
+        &self,
+        package: &Self::Package,
+        ctx: &Context,
+        target: &Target,
+    ) -> Result<crate::system::test_report::TestReport, Self::Error>;
+
+    /// Cleans build outputs owned by the build system for the given scope.
+    ///
+    /// Dist and fpkg outputs are removed by bp3d after this hook returns.
+    fn clean(
+        &self,
+        package: &Self::Package,
+        ctx: &Context,
+        targets: &[&str],
+        scope: CleanScope,
+    ) -> Result<(), Self::Error>;
 }
 
 pub trait Component {
@@ -29,6 +29,8 @@
 pub mod artifact;
 pub mod finder;
 mod interface;
+pub mod target;
+pub mod test_report;
 
 pub use interface::*;
 
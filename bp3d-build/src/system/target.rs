@@ -0,0 +1,305 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Parsed target triples.
+
+use bp3d_util::simple_error;
+use std::fmt::{Display, Formatter};
+
+simple_error! {
+    pub Error {
+        Empty => "empty target triple",
+        Invalid(String) => "invalid target triple: {}"
+    }
+}
+
+/// The target the running bp3d binary was built for.
+pub const HOST: &str = env!("BP3D_HOST_TARGET");
+
+/// Known environments, used to split the last component of a triple into env and abi.
+///
+/// The longest matching environment wins so that `gnullvm` is not split into `gnu` and `llvm`.
+const ENVS: &[&str] = &[
+    "gnu", "gnullvm", "musl", "msvc", "android", "uclibc", "newlib", "ohos", "sgx",
+];
+
+/// Operating systems which may directly follow the architecture when the vendor is omitted.
+const VENDORLESS_OS: &[&str] = &["linux", "none", "wasi"];
+
+fn host_arch() -> &'static str {
+    HOST.split('-').next().unwrap_or_default()
+}
+
+/// Resolves a target alias to a full triple.
+///
+/// `host` is the target of the running binary, `linux`, `windows` and `macos` are the host
+/// architecture with the default triple of that system. Other names are returned unchanged.
+pub fn resolve(name: &str) -> String {
+    match name {
+        "host" => HOST.into(),
+        "linux" => format!("{}-unknown-linux-gnu", host_arch()),
+        "windows" => format!("{}-pc-windows-msvc", host_arch()),
+        "macos" => format!("{}-apple-darwin", host_arch()),
+        _ => name.into(),
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Target {
+    name: String,
+    arch: String,
+    vendor: String,
+    os: String,
+    env: Option<String>,
+    abi: Option<String>,
+}
+
+impl Target {
+    /// Parses a target triple, resolving aliases, see [resolve].
+    pub fn parse(name: &str) -> Result<Self, Error> {
+        let name = resolve(name);
+        if name.is_empty() {
+            return Err(Error::Empty);
+        }
+        let parts: Vec<&str> = name.split('-').collect();
+        if parts.iter().any(|v| v.is_empty()) {
+            return Err(Error::Invalid(name));
+        }
+        let (arch, vendor, os, last) = match parts[..] {
+            [arch, os] => (arch, "unknown", os, None),
+            [arch, os, env] if VENDORLESS_OS.contains(&os) => (arch, "unknown", os, Some(env)),
+            [arch, vendor, os] => (arch, vendor, os, None),
+            [arch, vendor, os, last] => (arch, vendor, os, Some(last)),
+            _ => return Err(Error::Invalid(name)),
+        };
+        let (env, abi) = match last {
+            Some(last) => {
+                let env = ENVS
+                    .iter()
+                    .filter(|v| last.starts_with(**v))
+                    .max_by_key(|v| v.len());
+                match env {
+                    Some(env) if last.len() > env.len() => {
+                        (Some(env.to_string()), Some(last[env.len()..].to_string()))
+                    }
+                    Some(env) => (Some(env.to_string()), None),
+                    None => (None, Some(last.to_string())),
+                }
+            }
+            None => (None, None),
+        };
+        Ok(Target {
+            arch: arch.into(),
+            vendor: vendor.into(),
+            os: os.into(),
+            env,
+            abi,
+            name,
+        })
+    }
+
+    /// Returns the target of the running binary.
+    pub fn host() -> Result<Self, Error> {
+        Self::parse(HOST)
+    }
+
+    /// Returns the full triple.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn arch(&self) -> &str {
+        &self.arch
+    }
+
+    pub fn vendor(&self) -> &str {
+        &self.vendor
+    }
+
+    pub fn os(&self) -> &str {
+        &self.os
+    }
+
+    pub fn env(&self) -> Option<&str> {
+        self.env.as_deref()
+    }
+
+    pub fn abi(&self) -> Option<&str> {
+        self.abi.as_deref()
+    }
+
+    pub fn is_windows(&self) -> bool {
+        self.os == "windows"
+    }
+
+    pub fn is_apple(&self) -> bool {
+        self.vendor == "apple"
+    }
+
+    pub fn is_linux(&self) -> bool {
+        self.os == "linux"
+    }
+
+    pub fn is_android(&self) -> bool {
+        self.env.as_deref() == Some("android")
+    }
+
+    pub fn is_msvc(&self) -> bool {
+        self.env.as_deref() == Some("msvc")
+    }
+
+    pub fn is_wasm(&self) -> bool {
+        self.arch.starts_with("wasm")
+    }
+
+    /// Returns true for all targets using the unix family of operating systems.
+    pub fn is_unix(&self) -> bool {
+        !self.is_windows() && !self.is_wasm() && self.os != "none"
+    }
+
+    /// Returns the extension of executables, including the dot.
+    pub fn exe_suffix(&self) -> &'static str {
+        match self.is_windows() {
+            true => ".exe",
+            false => "",
+        }
+    }
+
+    /// Returns the prefix of library file names.
+    pub fn lib_prefix(&self) -> &'static str {
+        match self.is_msvc() {
+            true => "",
+            false => "lib",
+        }
+    }
+
+    /// Returns the extension of dynamic libraries, including the dot.
+    pub fn dylib_suffix(&self) -> &'static str {
+        if self.is_windows() {
+            ".dll"
+        } else if self.is_apple() {
+            ".dylib"
+        } else {
+            ".so"
+        }
+    }
+
+    /// Returns the extension of static libraries, including the dot.
+    pub fn staticlib_suffix(&self) -> &'static str {
+        match self.is_msvc() {
+            true => ".lib",
+            false => ".a",
+        }
+    }
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_alias() {
+        let target = Target::parse("host").unwrap();
+        assert_eq!(target.name(), HOST);
+        assert_eq!(target, Target::host().unwrap());
+        assert_eq!(target.arch(), host_arch());
+    }
+
+    #[test]
+    fn apple() {
+        let target = Target::parse("aarch64-apple-darwin").unwrap();
+        assert_eq!(target.arch(), "aarch64");
+        assert_eq!(target.vendor(), "apple");
+        assert_eq!(target.os(), "darwin");
+        assert_eq!(target.env(), None);
+        assert!(target.is_apple() && target.is_unix());
+        assert_eq!(target.dylib_suffix(), ".dylib");
+        assert_eq!(target.exe_suffix(), "");
+    }
+
+    #[test]
+    fn windows_gnu() {
+        let target = Target::parse("x86_64-pc-windows-gnu").unwrap();
+        assert_eq!(target.vendor(), "pc");
+        assert_eq!(target.env(), Some("gnu"));
+        assert!(target.is_windows() && !target.is_msvc() && !target.is_unix());
+        assert_eq!(target.exe_suffix(), ".exe");
+        assert_eq!(target.lib_prefix(), "lib");
+        assert_eq!(target.dylib_suffix(), ".dll");
+        assert_eq!(target.staticlib_suffix(), ".a");
+        let target = Target::parse("x86_64-pc-windows-gnullvm").unwrap();
+        assert_eq!(target.env(), Some("gnullvm"));
+        assert_eq!(target.abi(), None);
+    }
+
+    #[test]
+    fn musl() {
+        let target = Target::parse("x86_64-unknown-linux-musl").unwrap();
+        assert_eq!(target.env(), Some("musl"));
+        assert!(target.is_linux());
+        let target = Target::parse("armv7-unknown-linux-musleabihf").unwrap();
+        assert_eq!(target.env(), Some("musl"));
+        assert_eq!(target.abi(), Some("eabihf"));
+    }
+
+    #[test]
+    fn unknown_vendor() {
+        let target = Target::parse("aarch64-linux-android").unwrap();
+        assert_eq!(target.vendor(), "unknown");
+        assert_eq!(target.os(), "linux");
+        assert!(target.is_android());
+        let target = Target::parse("wasm32-wasi").unwrap();
+        assert_eq!(target.vendor(), "unknown");
+        assert_eq!(target.os(), "wasi");
+        assert!(target.is_wasm() && !target.is_unix());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(Target::parse(""), Err(Error::Empty)));
+        assert!(matches!(Target::parse("x86_64"), Err(Error::Invalid(_))));
+        assert!(matches!(
+            Target::parse("x86_64--linux"),
+            Err(Error::Invalid(_))
+        ));
+        assert!(matches!(
+            Target::parse("x86_64-linux-"),
+            Err(Error::Invalid(_))
+        ));
+        assert!(matches!(
+            Target::parse("x86_64-unknown-linux-gnu-extra"),
+            Err(Error::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt::Write;
+
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+#[derive(Clone, Debug)]
+pub struct TestCase {
+    pub name: String,
+    pub status: TestStatus,
+    /// Execution time in seconds.
+    pub time: Option<f64>,
+    /// Failure message or captured output.
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TestSuite {
+    pub name: String,
+    pub cases: Vec<TestCase>,
+}
+
+impl TestSuite {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            cases: Vec::new(),
+        }
+    }
+
+    pub fn count(&self, status: TestStatus) -> usize {
+        self.cases.iter().filter(|v| v.status == status).count()
+    }
+
+    pub fn time(&self) -> f64 {
+        self.cases.iter().filter_map(|v| v.time).sum()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TestReport {
+    suites: Vec<TestSuite>,
+}
+
+fn escape(value: &str) -> String {
+    let mut res = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            '\'' => res.push_str("&apos;"),
+            // Control characters are not allowed in XML 1.0.
+            c if c.is_control() && c != '\n' && c != '\r' && c != '\t' => (),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+impl TestReport {
+    pub fn new() -> Self {
+        Self { suites: Vec::new() }
+    }
+
+    pub fn suites(&self) -> &[TestSuite] {
+        &self.suites
+    }
+
+    pub fn add_suite(&mut self, suite: TestSuite) {
+        self.suites.push(suite);
+    }
+
+    /// Returns the suite with the given name, creating it if it does not exist.
+    pub fn suite_mut(&mut self, name: &str) -> &mut TestSuite {
+        let index = match self.suites.iter().position(|v| v.name == name) {
+            Some(index) => index,
+            None => {
+                self.suites.push(TestSuite::new(name));
+                self.suites.len() - 1
+            }
+        };
+        &mut self.suites[index]
+    }
+
+    pub fn merge_namespaced(&mut self, namespace: &str, other: TestReport) {
+        for mut suite in other.suites {
+            suite.name = format!("{}/{}", namespace, suite.name);
+            self.suites.push(suite);
+        }
+    }
+
+    pub fn count(&self, status: TestStatus) -> usize {
+        self.suites.iter().map(|v| v.count(status)).sum()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.count(TestStatus::Failed) == 0
+    }
+
+    /// Renders this report as a JUnit XML document.
+    pub fn to_junit(&self, name: &str) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let tests: usize = self.suites.iter().map(|v| v.cases.len()).sum();
+        let time: f64 = self.suites.iter().map(|v| v.time()).sum();
+        // Writing to a String never fails.
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+            escape(name),
+            tests,
+            self.count(TestStatus::Failed),
+            self.count(TestStatus::Ignored),
+            time
+        );
+        for suite in &self.suites {
+            let _ = writeln!(
+                xml,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+                escape(&suite.name),
+                suite.cases.len(),
+                suite.count(TestStatus::Failed),
+                suite.count(TestStatus::Ignored),
+                suite.time()
+            );
+            for case in &suite.cases {
+                let _ = write!(
+                    xml,
+                    "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                    escape(&case.name),
+                    escape(&suite.name),
+                    case.time.unwrap_or(0.0)
+                );
+                match case.status {
+                    TestStatus::Passed => xml.push_str("/>\n"),
+                    TestStatus::Ignored => xml.push_str(">\n      <skipped/>\n    </testcase>\n"),
+                    TestStatus::Failed => {
+                        let message = case.message.as_deref().unwrap_or("");
+                        let _ = write!(
+                            xml,
+                            ">\n      <failure message=\"test failed\">{}</failure>\n    </testcase>\n",
+                            escape(message)
+                        );
+                    }
+                }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
@@ -0,0 +1,992 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A small text template engine.
+//!
+//! Syntax:
+//! - `{{ expr }}` outputs a value, `expr` is a dotted path (`package.name`), a string or a number
+//!   literal, optionally followed by filters: `{{ name | xml }}`, `{{ list | join(", ") }}`.
+//! - `{% if cond %}`, `{% elif cond %}`, `{% else %}` and `{% endif %}` where `cond` is
+//!   `[not] expr [== expr | != expr]`.
+//! - `{% for item in expr %}` and `{% for key, value in expr %}` up to `{% endfor %}`, the `loop`
+//!   variable contains `index` (starting at 1), `first`, `last` and `length`.
+//! - `{% include "name" %}` renders another template found in the search paths.
+//! - `{# comment #}`.
+//! - `{% raw %}` and `{% endraw %}` output the text between them as is, including tags.
+//!
+//! Lines containing only a `{% %}` or `{# #}` tag are removed from the output.
+
+use bp3d_util::simple_error;
+use serde_json::{Map, Number, Value};
+use std::fmt::Display;
+use std::path::{Component, Path, PathBuf};
+
+simple_error! {
+    pub Error {
+        Io(std::io::Error) => "io error: {}",
+        Syntax(String) => "template syntax error at {}",
+        Render(String) => "template error at {}",
+        NotFound(String) => "template not found: {}"
+    }
+}
+
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+fn syntax_error(name: &str, line: usize, msg: impl Display) -> Error {
+    Error::Syntax(format!("{}:{}: {}", name, line, msg))
+}
+
+fn render_error(name: &str, line: usize, msg: impl Display) -> Error {
+    Error::Render(format!("{}:{}: {}", name, line, msg))
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Str(String),
+    Num(f64),
+    Ident(String),
+    Pipe,
+    LParen,
+    RParen,
+    Comma,
+    Eq,
+    Ne,
+}
+
+fn tokenize(name: &str, line: usize, source: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '|' => tokens.push(Token::Pipe),
+            '(' => tokens.push(Token::LParen),
+            ')' => tokens.push(Token::RParen),
+            ',' => tokens.push(Token::Comma),
+            '=' | '!' if chars.peek() == Some(&'=') => {
+                chars.next();
+                tokens.push(if c == '=' { Token::Eq } else { Token::Ne });
+            }
+            '"' | '\'' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(v) if v == c => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(v) => value.push(v),
+                            None => return Err(syntax_error(name, line, "unterminated string")),
+                        },
+                        Some(v) => value.push(v),
+                        None => return Err(syntax_error(name, line, "unterminated string")),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut value = String::from(c);
+                while let Some(v) = chars.next_if(|v| v.is_ascii_digit() || *v == '.') {
+                    value.push(v);
+                }
+                let number = value
+                    .parse()
+                    .map_err(|_| syntax_error(name, line, format!("invalid number {}", value)))?;
+                tokens.push(Token::Num(number));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut value = String::from(c);
+                while let Some(v) = chars.next_if(|v| v.is_alphanumeric() || *v == '_' || *v == '.')
+                {
+                    value.push(v);
+                }
+                tokens.push(Token::Ident(value));
+            }
+            c => {
+                return Err(syntax_error(
+                    name,
+                    line,
+                    format!("unexpected character '{}'", c),
+                ))
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        Value::Number((value as i64).into())
+    } else {
+        Number::from_f64(value)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
+
+enum Operand {
+    Path(Vec<String>),
+    Literal(Value),
+}
+
+struct Filter {
+    name: String,
+    arg: Option<Value>,
+}
+
+struct Expr {
+    operand: Operand,
+    filters: Vec<Filter>,
+}
+
+struct Condition {
+    line: usize,
+    negate: bool,
+    left: Expr,
+    compare: Option<(bool, Expr)>,
+}
+
+/// A cursor over the tokens of a tag.
+struct Tokens<'a> {
+    name: &'a str,
+    line: usize,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(name: &'a str, line: usize, source: &str) -> Result<Self, Error> {
+        Ok(Self {
+            name,
+            line,
+            tokens: tokenize(name, line, source)?,
+            pos: 0,
+        })
+    }
+
+    fn error(&self, msg: impl Display) -> Error {
+        syntax_error(self.name, self.line, msg)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn next_if(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn ident(&mut self) -> Result<String, Error> {
+        match self.next() {
+            Some(Token::Ident(v)) => Ok(v.clone()),
+            _ => Err(self.error("expected identifier")),
+        }
+    }
+
+    fn end(&self) -> Result<(), Error> {
+        match self.pos >= self.tokens.len() {
+            true => Ok(()),
+            false => Err(self.error(format!("unexpected {:?}", self.tokens[self.pos]))),
+        }
+    }
+
+    fn literal(&mut self) -> Result<Value, Error> {
+        match self.next() {
+            Some(Token::Str(v)) => Ok(Value::String(v.clone())),
+            Some(Token::Num(v)) => Ok(number(*v)),
+            Some(Token::Ident(v)) if v == "true" => Ok(Value::Bool(true)),
+            Some(Token::Ident(v)) if v == "false" => Ok(Value::Bool(false)),
+            _ => Err(self.error("expected literal")),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        let operand = match self.next() {
+            Some(Token::Str(v)) => Operand::Literal(Value::String(v.clone())),
+            Some(Token::Num(v)) => Operand::Literal(number(*v)),
+            Some(Token::Ident(v)) => match &**v {
+                "true" => Operand::Literal(Value::Bool(true)),
+                "false" => Operand::Literal(Value::Bool(false)),
+                "nil" => Operand::Literal(Value::Null),
+                v => Operand::Path(v.split('.').map(String::from).collect()),
+            },
+            _ => return Err(self.error("expected expression")),
+        };
+        let mut filters = Vec::new();
+        while self.next_if(&Token::Pipe) {
+            let name = self.ident()?;
+            let mut arg = None;
+            if self.next_if(&Token::LParen) {
+                arg = Some(self.literal()?);
+                if !self.next_if(&Token::RParen) {
+                    return Err(self.error("expected ')'"));
+                }
+            }
+            filters.push(Filter { name, arg });
+        }
+        Ok(Expr { operand, filters })
+    }
+
+    fn condition(&mut self) -> Result<Condition, Error> {
+        let negate = self.next_if(&Token::Ident("not".into()));
+        let left = self.expr()?;
+        let compare = if self.next_if(&Token::Eq) {
+            Some((true, self.expr()?))
+        } else if self.next_if(&Token::Ne) {
+            Some((false, self.expr()?))
+        } else {
+            None
+        };
+        self.end()?;
+        Ok(Condition {
+            line: self.line,
+            negate,
+            left,
+            compare,
+        })
+    }
+}
+
+enum Node {
+    Text(String),
+    Output(Expr, usize),
+    If(Vec<(Condition, Vec<Node>)>, Vec<Node>),
+    For {
+        key: Option<String>,
+        value: String,
+        source: Expr,
+        line: usize,
+        body: Vec<Node>,
+    },
+    Include(String, usize),
+}
+
+enum Raw<'a> {
+    Text(&'a str),
+    Output(&'a str, usize),
+    Block(&'a str, usize),
+}
+
+/// Returns the end of the text preceding a block or comment tag and the end of the tag, lines
+/// containing only the tag are removed.
+fn standalone(source: &str, start: usize, end: usize, text_start: usize) -> (usize, usize) {
+    let line_start = source[..start].rfind('\n').map(|v| v + 1).unwrap_or(0);
+    let line_end = source[end..].find('\n').map(|v| end + v + 1);
+    let before = source[line_start..start].trim().is_empty();
+    let after = source[end..line_end.unwrap_or(source.len())]
+        .trim()
+        .is_empty();
+    match before && after && line_start >= text_start {
+        true => (line_start, line_end.unwrap_or(source.len())),
+        false => (start, end),
+    }
+}
+
+/// Returns the start and end of the next endraw tag.
+fn find_endraw(source: &str, mut pos: usize) -> Option<(usize, usize)> {
+    while let Some(offset) = source[pos..].find("{%") {
+        let start = pos + offset;
+        let len = source[start + 2..].find("%}")?;
+        if source[start + 2..start + 2 + len].trim() == "endraw" {
+            return Some((start, start + len + 4));
+        }
+        pos = start + 2;
+    }
+    None
+}
+
+/// Splits a template into text and tags.
+fn lex<'a>(name: &str, source: &'a str) -> Result<Vec<Raw<'a>>, Error> {
+    let mut raw = Vec::new();
+    let mut pos = 0;
+    let mut line = 1;
+    let mut line_pos = 0;
+    let mut text_start = 0;
+    while let Some(offset) = source[pos..].find('{') {
+        let start = pos + offset;
+        let close = match source.get(start + 1..start + 2) {
+            Some("{") => "}}",
+            Some("%") => "%}",
+            Some("#") => "#}",
+            _ => {
+                pos = start + 1;
+                continue;
+            }
+        };
+        line += source[line_pos..start].matches('\n').count();
+        let content_start = start + 2;
+        let Some(len) = source[content_start..].find(close) else {
+            return Err(syntax_error(name, line, format!("missing '{}'", close)));
+        };
+        let content = &source[content_start..content_start + len];
+        let mut end = content_start + len + 2;
+        let mut text_end = start;
+        if close != "}}" {
+            (text_end, end) = standalone(source, start, end, text_start);
+        }
+        if text_end > text_start {
+            raw.push(Raw::Text(&source[text_start..text_end]));
+        }
+        match close {
+            "}}" => raw.push(Raw::Output(content.trim(), line)),
+            "%}" if content.trim() == "raw" => {
+                let Some((tag_start, tag_end)) = find_endraw(source, end) else {
+                    return Err(syntax_error(name, line, "missing endraw"));
+                };
+                let (raw_end, tag_end) = standalone(source, tag_start, tag_end, end);
+                if raw_end > end {
+                    raw.push(Raw::Text(&source[end..raw_end]));
+                }
+                end = tag_end;
+            }
+            "%}" => raw.push(Raw::Block(content.trim(), line)),
+            _ => (),
+        }
+        line += source[start..end].matches('\n').count();
+        line_pos = end;
+        pos = end;
+        text_start = end;
+    }
+    if text_start < source.len() {
+        raw.push(Raw::Text(&source[text_start..]));
+    }
+    Ok(raw)
+}
+
+/// A block terminating a sequence of nodes: keyword, remaining content and line.
+type Terminator<'a> = Option<(&'a str, &'a str, usize)>;
+
+fn parse_nodes<'a>(
+    name: &str,
+    iter: &mut std::vec::IntoIter<Raw<'a>>,
+    end: &[&str],
+) -> Result<(Vec<Node>, Terminator<'a>), Error> {
+    let mut nodes = Vec::new();
+    while let Some(raw) = iter.next() {
+        let (content, line) = match raw {
+            Raw::Text(v) => {
+                nodes.push(Node::Text(v.into()));
+                continue;
+            }
+            Raw::Output(v, line) => {
+                let mut tokens = Tokens::new(name, line, v)?;
+                let expr = tokens.expr()?;
+                tokens.end()?;
+                nodes.push(Node::Output(expr, line));
+                continue;
+            }
+            Raw::Block(v, line) => (v, line),
+        };
+        let (keyword, rest) = content
+            .split_once(char::is_whitespace)
+            .unwrap_or((content, ""));
+        if end.contains(&keyword) {
+            return Ok((nodes, Some((keyword, rest, line))));
+        }
+        match keyword {
+            "if" => {
+                let mut branches = Vec::new();
+                let mut otherwise = Vec::new();
+                let mut condition = Tokens::new(name, line, rest)?.condition()?;
+                loop {
+                    let (body, terminator) = parse_nodes(name, iter, &["elif", "else", "endif"])?;
+                    branches.push((condition, body));
+                    match terminator {
+                        Some(("elif", rest, line)) => {
+                            condition = Tokens::new(name, line, rest)?.condition()?;
+                        }
+                        Some(("else", _, _)) => {
+                            let (body, terminator) = parse_nodes(name, iter, &["endif"])?;
+                            if terminator.is_none() {
+                                return Err(syntax_error(name, line, "missing endif"));
+                            }
+                            otherwise = body;
+                            break;
+                        }
+                        Some(_) => break,
+                        None => return Err(syntax_error(name, line, "missing endif")),
+                    }
+                }
+                nodes.push(Node::If(branches, otherwise));
+            }
+            "for" => {
+                let mut tokens = Tokens::new(name, line, rest)?;
+                let mut value = tokens.ident()?;
+                let mut key = None;
+                if tokens.next_if(&Token::Comma) {
+                    key = Some(value);
+                    value = tokens.ident()?;
+                }
+                if !tokens.next_if(&Token::Ident("in".into())) {
+                    return Err(syntax_error(name, line, "expected 'in'"));
+                }
+                let source = tokens.expr()?;
+                tokens.end()?;
+                let (body, terminator) = parse_nodes(name, iter, &["endfor"])?;
+                if terminator.is_none() {
+                    return Err(syntax_error(name, line, "missing endfor"));
+                }
+                nodes.push(Node::For {
+                    key,
+                    value,
+                    source,
+                    line,
+                    body,
+                });
+            }
+            "include" => {
+                let mut tokens = Tokens::new(name, line, rest)?;
+                let Some(Token::Str(path)) = tokens.next() else {
+                    return Err(syntax_error(name, line, "expected template name"));
+                };
+                let path = path.clone();
+                tokens.end()?;
+                nodes.push(Node::Include(path, line));
+            }
+            v => return Err(syntax_error(name, line, format!("unexpected '{}'", v))),
+        }
+    }
+    Ok((nodes, None))
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(v) => *v,
+        Value::Number(v) => v.as_f64() != Some(0.0),
+        Value::String(v) => !v.is_empty(),
+        Value::Array(v) => !v.is_empty(),
+        Value::Object(v) => !v.is_empty(),
+    }
+}
+
+fn equals(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => a == b,
+    }
+}
+
+fn to_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => Some(String::new()),
+        Value::Bool(v) => Some(v.to_string()),
+        Value::Number(v) => Some(v.to_string()),
+        Value::String(v) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Quotes a value for POSIX shells, values containing only safe characters are kept as is.
+fn escape_shell(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:=@%+,".contains(c));
+    match safe {
+        true => value.into(),
+        false => format!("'{}'", value.replace('\'', "'\\''")),
+    }
+}
+
+/// Escapes a value for use inside a quoted CMake argument.
+fn escape_cmake(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | '"' | '$' | ';' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+struct Renderer<'a> {
+    root: &'a Value,
+    search_paths: &'a [PathBuf],
+    scopes: Vec<(String, Value)>,
+    depth: usize,
+}
+
+impl Renderer<'_> {
+    fn lookup(&self, path: &[String]) -> Value {
+        let first = &path[0];
+        let value = self
+            .scopes
+            .iter()
+            .rev()
+            .find(|(k, _)| k == first)
+            .map(|(_, v)| v)
+            .or_else(|| self.root.get(first));
+        let mut value = match value {
+            Some(v) => v,
+            None => return Value::Null,
+        };
+        for segment in &path[1..] {
+            let next = match value {
+                Value::Array(v) => segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| i.checked_sub(1))
+                    .and_then(|i| v.get(i)),
+                v => v.get(segment),
+            };
+            match next {
+                Some(v) => value = v,
+                None => return Value::Null,
+            }
+        }
+        value.clone()
+    }
+
+    fn filter(&self, filter: &Filter, value: Value) -> Result<Value, String> {
+        let text =
+            || to_text(&value).ok_or_else(|| format!("filter {} expects a value", filter.name));
+        let arg = filter.arg.as_ref().and_then(to_text);
+        Ok(match &*filter.name {
+            "xml" => Value::String(escape_xml(&text()?)),
+            "shell" => Value::String(escape_shell(&text()?)),
+            "cmake" => Value::String(escape_cmake(&text()?)),
+            "upper" => Value::String(text()?.to_uppercase()),
+            "lower" => Value::String(text()?.to_lowercase()),
+            "trim" => Value::String(text()?.trim().into()),
+            "json" => Value::String(value.to_string()),
+            "length" => match &value {
+                Value::Array(v) => v.len().into(),
+                Value::Object(v) => v.len().into(),
+                Value::String(v) => v.chars().count().into(),
+                _ => return Err("filter length expects a list or a string".into()),
+            },
+            "join" => match &value {
+                Value::Array(items) => {
+                    let items: Option<Vec<String>> = items.iter().map(to_text).collect();
+                    let items = items.ok_or("filter join expects a list of values")?;
+                    Value::String(items.join(arg.as_deref().unwrap_or("")))
+                }
+                Value::Null => Value::String(String::new()),
+                _ => return Err("filter join expects a list".into()),
+            },
+            "default" => match truthy(&value) {
+                true => value,
+                false => filter.arg.clone().unwrap_or(Value::Null),
+            },
+            v => return Err(format!("unknown filter '{}'", v)),
+        })
+    }
+
+    fn eval(&self, name: &str, line: usize, expr: &Expr) -> Result<Value, Error> {
+        let mut value = match &expr.operand {
+            Operand::Path(path) => self.lookup(path),
+            Operand::Literal(v) => v.clone(),
+        };
+        for filter in &expr.filters {
+            value = self
+                .filter(filter, value)
+                .map_err(|e| render_error(name, line, e))?;
+        }
+        Ok(value)
+    }
+
+    fn test(&self, name: &str, condition: &Condition) -> Result<bool, Error> {
+        let line = condition.line;
+        let left = self.eval(name, line, &condition.left)?;
+        let res = match &condition.compare {
+            Some((equal, right)) => equals(&left, &self.eval(name, line, right)?) == *equal,
+            None => truthy(&left),
+        };
+        Ok(res != condition.negate)
+    }
+
+    fn render(&mut self, name: &str, nodes: &[Node], out: &mut String) -> Result<(), Error> {
+        for node in nodes {
+            match node {
+                Node::Text(v) => out.push_str(v),
+                Node::Output(expr, line) => {
+                    let value = self.eval(name, *line, expr)?;
+                    let text = to_text(&value).ok_or_else(|| {
+                        render_error(
+                            name,
+                            *line,
+                            "cannot output a table, use a loop or the join filter",
+                        )
+                    })?;
+                    out.push_str(&text);
+                }
+                Node::If(branches, otherwise) => {
+                    let mut body = otherwise;
+                    for (condition, nodes) in branches {
+                        if self.test(name, condition)? {
+                            body = nodes;
+                            break;
+                        }
+                    }
+                    self.render(name, body, out)?;
+                }
+                Node::For {
+                    key,
+                    value,
+                    source,
+                    line,
+                    body,
+                } => {
+                    let source = self.eval(name, *line, source)?;
+                    let is_table = source.is_object();
+                    let items: Vec<(Value, Value)> = match source {
+                        Value::Array(v) => v
+                            .into_iter()
+                            .enumerate()
+                            .map(|(i, v)| (Value::from(i + 1), v))
+                            .collect(),
+                        Value::Object(v) => {
+                            v.into_iter().map(|(k, v)| (Value::String(k), v)).collect()
+                        }
+                        Value::Null => Vec::new(),
+                        _ => return Err(render_error(name, *line, "cannot iterate over a value")),
+                    };
+                    let length = items.len();
+                    for (i, (k, v)) in items.into_iter().enumerate() {
+                        let mut state = Map::new();
+                        state.insert("index".into(), (i + 1).into());
+                        state.insert("first".into(), (i == 0).into());
+                        state.insert("last".into(), (i + 1 == length).into());
+                        state.insert("length".into(), length.into());
+                        let scopes = self.scopes.len();
+                        self.scopes.push(("loop".into(), Value::Object(state)));
+                        match key {
+                            Some(key) => {
+                                self.scopes.push((key.clone(), k));
+                                self.scopes.push((value.clone(), v));
+                            }
+                            // Iterating a table with a single variable yields its keys.
+                            None if is_table => self.scopes.push((value.clone(), k)),
+                            None => self.scopes.push((value.clone(), v)),
+                        }
+                        let res = self.render(name, body, out);
+                        self.scopes.truncate(scopes);
+                        res?;
+                    }
+                }
+                Node::Include(path, line) => {
+                    if self.depth >= MAX_INCLUDE_DEPTH {
+                        return Err(render_error(name, *line, "too many nested includes"));
+                    }
+                    let template =
+                        load(path, self.search_paths).map_err(|e| render_error(name, *line, e))?;
+                    self.depth += 1;
+                    let res = self.render(&template.name, &template.nodes, out);
+                    self.depth -= 1;
+                    res?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct Template {
+    name: String,
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    /// Parses a template, name is used in error messages.
+    pub fn parse(name: &str, source: &str) -> Result<Self, Error> {
+        let raw = lex(name, source)?;
+        let (nodes, terminator) = parse_nodes(name, &mut raw.into_iter(), &[])?;
+        if let Some((keyword, _, line)) = terminator {
+            return Err(syntax_error(
+                name,
+                line,
+                format!("unexpected '{}'", keyword),
+            ));
+        }
+        Ok(Template {
+            name: name.into(),
+            nodes,
+        })
+    }
+
+    /// Renders this template, includes are looked up in the given search paths.
+    pub fn render(&self, args: &Value, search_paths: &[PathBuf]) -> Result<String, Error> {
+        let mut renderer = Renderer {
+            root: args,
+            search_paths,
+            scopes: Vec::new(),
+            depth: 0,
+        };
+        let mut out = String::new();
+        renderer.render(&self.name, &self.nodes, &mut out)?;
+        Ok(out)
+    }
+}
+
+/// Returns the path of a template in the first search path containing it.
+///
+/// The resolved path must stay inside the search path it was found in, so a symbolic link cannot
+/// be used to include a file from elsewhere.
+pub fn find(name: &str, search_paths: &[PathBuf]) -> Option<PathBuf> {
+    let relative = Path::new(name);
+    if !relative
+        .components()
+        .all(|v| matches!(v, Component::Normal(_) | Component::CurDir))
+    {
+        return None;
+    }
+    search_paths.iter().find_map(|root| {
+        let path = root.join(relative).canonicalize().ok()?;
+        let root = root.canonicalize().ok()?;
+        (path.is_file() && path.starts_with(root)).then_some(path)
+    })
+}
+
+/// Loads and parses a template from the search paths.
+pub fn load(name: &str, search_paths: &[PathBuf]) -> Result<Template, Error> {
+    let path = find(name, search_paths).ok_or_else(|| Error::NotFound(name.into()))?;
+    let source = std::fs::read_to_string(path).map_err(Error::Io)?;
+    Template::parse(name, &source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, Error, Template};
+    use serde_json::{json, Value};
+
+    fn render(source: &str, args: Value) -> Result<String, Error> {
+        Template::parse("test", source)?.render(&args, &[])
+    }
+
+    fn syntax(source: &str) -> String {
+        match Template::parse("test", source) {
+            Err(Error::Syntax(v)) => v,
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("expected a syntax error"),
+        }
+    }
+
+    fn error(source: &str, args: Value) -> String {
+        match render(source, args) {
+            Err(Error::Render(v)) => v,
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("expected a render error"),
+        }
+    }
+
+    #[test]
+    fn output() {
+        let args = json!({"package": {"name": "test", "version": 1}, "list": ["a", "b"]});
+        assert_eq!(
+            render("{{ package.name }}-{{package.version}}", args.clone()).unwrap(),
+            "test-1"
+        );
+        assert_eq!(render("{{ list.2 }}", args.clone()).unwrap(), "b");
+        assert_eq!(render("{{ missing }}", args).unwrap(), "");
+        assert_eq!(render("{{ 'a' }}{{ 12 }}", Value::Null).unwrap(), "a12");
+        assert_eq!(render("{ a } {b}", Value::Null).unwrap(), "{ a } {b}");
+    }
+
+    #[test]
+    fn filters() {
+        let args = json!({"name": "<a & 'b'>", "list": ["x", "y"], "empty": ""});
+        assert_eq!(
+            render("{{ name | xml }}", args.clone()).unwrap(),
+            "&lt;a &amp; &apos;b&apos;&gt;"
+        );
+        assert_eq!(
+            render("{{ name | shell }}", args.clone()).unwrap(),
+            "'<a & '\\''b'\\''>'"
+        );
+        assert_eq!(
+            render("{{ list | join(\", \") | upper }}", args.clone()).unwrap(),
+            "X, Y"
+        );
+        assert_eq!(render("{{ list | length }}", args.clone()).unwrap(), "2");
+        assert_eq!(
+            render("{{ empty | default('none') }}", args).unwrap(),
+            "none"
+        );
+    }
+
+    #[test]
+    fn conditions() {
+        let source = "{% if a == 1 %}one{% elif not b %}not b{% else %}other{% endif %}";
+        assert_eq!(render(source, json!({"a": 1})).unwrap(), "one");
+        assert_eq!(render(source, json!({"a": 2})).unwrap(), "not b");
+        assert_eq!(render(source, json!({"b": true})).unwrap(), "other");
+        assert_eq!(
+            render("{% if a != 'x' %}yes{% endif %}", json!({"a": "y"})).unwrap(),
+            "yes"
+        );
+    }
+
+    #[test]
+    fn loops() {
+        let args = json!({"list": ["a", "b", "c"], "map": {"k": "v"}});
+        assert_eq!(
+            render(
+                "{% for v in list %}{{ loop.index }}{{ v }}{% if not loop.last %},{% endif %}{% endfor %}",
+                args.clone()
+            )
+            .unwrap(),
+            "1a,2b,3c"
+        );
+        assert_eq!(
+            render(
+                "{% for k, v in map %}{{ k }}={{ v }}{% endfor %}",
+                args.clone()
+            )
+            .unwrap(),
+            "k=v"
+        );
+        assert_eq!(
+            render("{% for k in map %}{{ k }}{% endfor %}", args).unwrap(),
+            "k"
+        );
+        assert_eq!(
+            render("{% for v in missing %}x{% endfor %}", Value::Null).unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn standalone_lines() {
+        let source = "a\n  {% if true %}\nb\n{# comment #}\n{% endif %}\nc\n";
+        assert_eq!(render(source, Value::Null).unwrap(), "a\nb\nc\n");
+        assert_eq!(
+            render("a {% if true %}b{% endif %}\n", Value::Null).unwrap(),
+            "a b\n"
+        );
+    }
+
+    #[test]
+    fn raw() {
+        let source = "{% raw %}{{ a }} {% if %}{% endraw %}{{ a }}";
+        assert_eq!(
+            render(source, json!({"a": 1})).unwrap(),
+            "{{ a }} {% if %}1"
+        );
+        let source = "x\n{% raw %}\n{{ a }}\n{% endraw %}\ny";
+        assert_eq!(render(source, Value::Null).unwrap(), "x\n{{ a }}\ny");
+        assert_eq!(render("{{ '{{' }}", Value::Null).unwrap(), "{{");
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert_eq!(syntax("a\n{{ a"), "test:2: missing '}}'");
+        assert_eq!(syntax("{% if a %}"), "test:1: missing endif");
+        assert_eq!(syntax("{% for a in b %}"), "test:1: missing endfor");
+        assert_eq!(syntax("{% for a b %}{% endfor %}"), "test:1: expected 'in'");
+        assert_eq!(syntax("\n\n{% endif %}"), "test:3: unexpected 'endif'");
+        assert_eq!(syntax("{% foo %}"), "test:1: unexpected 'foo'");
+        assert_eq!(syntax("{{ 'a }}"), "test:1: unterminated string");
+        assert_eq!(syntax("{{ a b }}"), "test:1: unexpected Ident(\"b\")");
+        assert_eq!(syntax("{{ a | }}"), "test:1: expected identifier");
+        assert_eq!(syntax("{{ # }}"), "test:1: unexpected character '#'");
+        assert_eq!(syntax("{% raw %}{{ a }}"), "test:1: missing endraw");
+        assert_eq!(syntax("{% include a %}"), "test:1: expected template name");
+    }
+
+    #[test]
+    fn render_errors() {
+        assert_eq!(
+            error("{{ a | foo }}", Value::Null),
+            "test:1: unknown filter 'foo'"
+        );
+        assert_eq!(
+            error("\n{{ a }}", json!({"a": [1]})),
+            "test:2: cannot output a table, use a loop or the join filter"
+        );
+        assert_eq!(
+            error("{% for v in a %}{% endfor %}", json!({"a": 1})),
+            "test:1: cannot iterate over a value"
+        );
+        assert_eq!(
+            error("{% include 'missing' %}", Value::Null),
+            "test:1: template not found: missing"
+        );
+        assert_eq!(
+            error("{% include '../a' %}", Value::Null),
+            "test:1: template not found: ../a"
+        );
+    }
+
+    #[test]
+    fn find_in_search_paths() {
+        let root = std::env::temp_dir().join(format!("bp3d-template-{}", std::process::id()));
+        let first = root.join("first");
+        let second = root.join("second");
+        std::fs::create_dir_all(first.join("sub")).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+        std::fs::write(first.join("sub/a.txt"), "a").unwrap();
+        std::fs::write(second.join("b.txt"), "b").unwrap();
+        std::fs::write(root.join("secret.txt"), "secret").unwrap();
+        let paths = [first.clone(), second.clone()];
+        let found = find("sub/a.txt", &paths).unwrap();
+        assert!(found.ends_with("first/sub/a.txt"));
+        let found = find("./b.txt", &paths).unwrap();
+        assert!(found.ends_with("second/b.txt"));
+        assert_eq!(find("sub", &paths), None);
+        assert_eq!(find("../secret.txt", &paths), None);
+        assert_eq!(find("sub/../../secret.txt", &paths), None);
+        let absolute = root.join("secret.txt");
+        assert_eq!(find(&absolute.to_string_lossy(), &paths), None);
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(root.join("secret.txt"), first.join("link.txt")).unwrap();
+            std::os::unix::fs::symlink(&root, first.join("dir")).unwrap();
+            assert_eq!(find("link.txt", &paths), None);
+            assert_eq!(find("dir/secret.txt", &paths), None);
+            assert!(find("secret.txt", &[first.join("dir")]).is_some());
+        }
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
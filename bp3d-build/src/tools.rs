@@ -0,0 +1,252 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::system::target::Target;
+use bp3d_os::assets::get_executable_path;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// An external tool required by a packager or script.
+pub struct Requirement {
+    pub name: String,
+    /// The minimum version of the tool.
+    pub version: Option<String>,
+    /// The arguments used to print the version of the tool, defaults to `--version`.
+    pub args: Vec<String>,
+    /// Restricts the requirement to targets matching any of these patterns, see [target_matches].
+    pub targets: Vec<String>,
+    /// Native libraries which must be present in the bp3d library path.
+    pub libraries: Vec<String>,
+}
+
+impl Requirement {
+    /// Returns true if this requirement applies to any of the given targets.
+    pub fn applies_to(&self, targets: &[&str]) -> bool {
+        self.targets.is_empty()
+            || targets
+                .iter()
+                .any(|target| self.targets.iter().any(|v| target_matches(target, v)))
+    }
+}
+
+/// Returns true if the given target matches a requirement pattern.
+///
+/// A pattern matches the full target name, one of its components (arch, vendor, os, env or abi)
+/// or one of the `unix`, `windows` and `apple` families.
+pub fn target_matches(target: &str, pattern: &str) -> bool {
+    if target == pattern {
+        return true;
+    }
+    match Target::parse(target) {
+        Ok(target) => {
+            let components = [
+                Some(target.arch()),
+                Some(target.vendor()),
+                Some(target.os()),
+                target.env(),
+                target.abi(),
+            ];
+            components.contains(&Some(pattern))
+                || match pattern {
+                    "unix" => target.is_unix(),
+                    "windows" => target.is_windows(),
+                    "apple" => target.is_apple(),
+                    _ => false,
+                }
+        }
+        Err(_) => target.split('-').any(|v| v == pattern),
+    }
+}
+
+pub enum Status {
+    Found {
+        path: PathBuf,
+        version: Option<String>,
+    },
+    Missing,
+    Outdated {
+        path: PathBuf,
+        version: String,
+    },
+    UnknownVersion {
+        path: PathBuf,
+    },
+    MissingLibrary {
+        path: PathBuf,
+        library: String,
+    },
+}
+
+impl Status {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Status::Found { .. })
+    }
+}
+
+fn exe_name(name: &OsStr) -> std::ffi::OsString {
+    #[cfg(target_os = "windows")]
+    {
+        let mut name = name.to_os_string();
+        name.push(".exe");
+        name
+    }
+    #[cfg(not(target_os = "windows"))]
+    name.to_os_string()
+}
+
+/// Returns the path to a tool shipped next to the bp3d executable.
+pub fn find_bundled(name: &OsStr) -> Option<PathBuf> {
+    let path = get_executable_path()?.join(exe_name(name));
+    path.exists().then_some(path)
+}
+
+/// Resolves a tool the same way commands are run: next to the bp3d executable first, then in
+/// PATH.
+pub fn find_executable(name: &OsStr) -> Option<PathBuf> {
+    if let Some(path) = find_bundled(name) {
+        return Some(path);
+    }
+    if Path::new(name).components().count() > 1 {
+        let path = PathBuf::from(name);
+        return path.is_file().then_some(path);
+    }
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths)
+        .map(|v| v.join(exe_name(name)))
+        .find(|v| v.is_file())
+}
+
+/// Returns the path where bp3d native libraries are installed.
+pub fn library_path() -> Option<PathBuf> {
+    let exe = get_executable_path()?;
+    let path = exe.join("../lib");
+    match path.exists() {
+        true => Some(path),
+        false => Some(exe),
+    }
+}
+
+fn has_library(dir: &Path, name: &str) -> bool {
+    [
+        format!("lib{}.a", name),
+        format!("lib{}.so", name),
+        format!("lib{}.dylib", name),
+        format!("{}.lib", name),
+        format!("{}.dll", name),
+    ]
+    .iter()
+    .any(|v| dir.join(v).exists())
+}
+
+fn parse_numbers(version: &str) -> Option<Vec<u64>> {
+    let numbers: Option<Vec<u64>> = version
+        .split('.')
+        .filter(|v| !v.is_empty())
+        .map(|v| v.parse().ok())
+        .collect();
+    numbers.filter(|v| !v.is_empty())
+}
+
+fn parse_version(text: &str) -> Option<Vec<u64>> {
+    text.split(|c: char| !c.is_ascii_digit() && c != '.')
+        .filter(|v| v.contains('.'))
+        .find_map(parse_numbers)
+}
+
+fn is_older(version: &[u64], minimum: &[u64]) -> bool {
+    for i in 0..version.len().max(minimum.len()) {
+        let a = version.get(i).copied().unwrap_or(0);
+        let b = minimum.get(i).copied().unwrap_or(0);
+        if a != b {
+            return a < b;
+        }
+    }
+    false
+}
+
+fn query_version(path: &Path, args: &[String]) -> Option<String> {
+    let mut cmd = Command::new(path);
+    match args.is_empty() {
+        true => cmd.arg("--version"),
+        false => cmd.args(args),
+    };
+    let output = cmd.output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let version = parse_version(&stdout).or_else(|| parse_version(&stderr))?;
+    Some(
+        version
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join("."),
+    )
+}
+
+/// Checks if a requirement is satisfied on this system.
+pub fn check(requirement: &Requirement) -> Status {
+    let Some(path) = find_executable(OsStr::new(&requirement.name)) else {
+        return Status::Missing;
+    };
+    if !requirement.libraries.is_empty() {
+        let dir = library_path();
+        for library in &requirement.libraries {
+            if !dir
+                .as_ref()
+                .map(|v| has_library(v, library))
+                .unwrap_or(false)
+            {
+                return Status::MissingLibrary {
+                    path,
+                    library: library.clone(),
+                };
+            }
+        }
+    }
+    let Some(minimum) = &requirement.version else {
+        return Status::Found {
+            path,
+            version: None,
+        };
+    };
+    let Some(version) = query_version(&path, &requirement.args) else {
+        return Status::UnknownVersion { path };
+    };
+    let older = match (parse_numbers(&version), parse_numbers(minimum)) {
+        (Some(a), Some(b)) => is_older(&a, &b),
+        _ => false,
+    };
+    match older {
+        true => Status::Outdated { path, version },
+        false => Status::Found {
+            path,
+            version: Some(version),
+        },
+    }
+}
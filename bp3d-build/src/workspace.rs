@@ -0,0 +1,361 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::core::{clean_outputs, open_nested, BuildTool, Error, MemberError, Result};
+use crate::system::artifact::List;
+use crate::system::test_report::TestReport;
+use crate::system::{CleanScope, Component, Context, Features, Package};
+use bp3d_config::Workspace;
+use bp3d_debug::info;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+struct MemberComponent {
+    name: String,
+    version: String,
+    short_name: String,
+    description: Option<String>,
+    public: bool,
+}
+
+impl MemberComponent {
+    fn new(component: &dyn Component) -> Self {
+        Self {
+            name: component.get_name().into(),
+            version: component.get_version().into(),
+            short_name: component.get_short_name().into(),
+            description: component.get_description().map(String::from),
+            public: component.is_public(),
+        }
+    }
+}
+
+impl Component for MemberComponent {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_version(&self) -> &str {
+        &self.version
+    }
+
+    fn get_short_name(&self) -> &str {
+        &self.short_name
+    }
+
+    fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn is_public(&self) -> bool {
+        self.public
+    }
+}
+
+pub struct WorkspacePackage {
+    name: String,
+    version: String,
+    components: Vec<MemberComponent>,
+    targets: Vec<Cow<'static, str>>,
+    configurations: Vec<Cow<'static, str>>,
+    features: Vec<Cow<'static, str>>,
+}
+
+impl Package for WorkspacePackage {
+    fn get_primary_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_primary_version(&self) -> &str {
+        &self.version
+    }
+
+    fn get_components(&self) -> usize {
+        self.components.len()
+    }
+
+    fn get_component(&self, index: usize) -> &dyn Component {
+        &self.components[index]
+    }
+
+    fn targets(&self) -> &[Cow<'_, str>] {
+        &self.targets
+    }
+
+    fn configurations(&self) -> &[Cow<'_, str>] {
+        &self.configurations
+    }
+
+    fn features(&self) -> &[Cow<'_, str>] {
+        &self.features
+    }
+}
+
+struct Member {
+    name: String,
+    tool: Box<dyn BuildTool>,
+}
+
+/// A build tool which runs each member of a `[workspace]` in dependency order.
+///
+/// The workspace root itself is not built, only its members are.
+pub struct WorkspaceTool {
+    package: WorkspacePackage,
+    members: Vec<Member>,
+}
+
+fn sort_members(workspace: &Workspace) -> Result<Vec<&str>> {
+    let is_member = |name: &str| workspace.members.iter().any(|v| v == name);
+    if let Some(dependencies) = &workspace.dependencies {
+        for (name, list) in dependencies {
+            if let Some(unknown) = std::iter::once(name).chain(list).find(|v| !is_member(v)) {
+                return Err(Error::Workspace(format!("unknown member {:?}", unknown)));
+            }
+        }
+    }
+    let depends_on = |name: &str| {
+        workspace
+            .dependencies
+            .as_ref()
+            .and_then(|v| v.get(name))
+            .map(|v| v.as_slice())
+            .unwrap_or_default()
+    };
+    let mut remaining: Vec<&str> = Vec::with_capacity(workspace.members.len());
+    for member in &workspace.members {
+        if remaining.contains(&&**member) {
+            return Err(Error::Workspace(format!("duplicate member {:?}", member)));
+        }
+        remaining.push(member);
+    }
+    let mut sorted: Vec<&str> = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        let pos = remaining
+            .iter()
+            .position(|v| depends_on(v).iter().all(|v| sorted.contains(&&**v)));
+        match pos {
+            Some(pos) => sorted.push(remaining.remove(pos)),
+            None => {
+                return Err(Error::Workspace(format!(
+                    "dependency cycle between members {:?}",
+                    remaining
+                )))
+            }
+        }
+    }
+    Ok(sorted)
+}
+
+fn intersect(list: &mut Vec<Cow<'static, str>>, other: &[Cow<str>]) {
+    list.retain(|v| other.iter().any(|o| o == v));
+}
+
+impl WorkspaceTool {
+    pub fn open(
+        root: &Path,
+        workspace: &Workspace,
+        config: Option<&bp3d_config::Package>,
+        workspaces: &[PathBuf],
+    ) -> Result<Self> {
+        let mut members = Vec::with_capacity(workspace.members.len());
+        for name in sort_members(workspace)? {
+            let tool = open_nested(&root.join(name), workspaces)
+                .map_err(|e| MemberError::wrap(name, e))?;
+            members.push(Member {
+                name: name.into(),
+                tool,
+            });
+        }
+        let first = workspace
+            .members
+            .first()
+            .and_then(|name| members.iter().find(|v| &v.name == name))
+            .ok_or_else(|| Error::Workspace("workspace has no members".into()))?;
+        let (name, version) = match config {
+            Some(package) => (package.name.clone(), package.version.clone()),
+            None => (
+                first.tool.package().get_primary_name().into(),
+                first.tool.package().get_primary_version().into(),
+            ),
+        };
+        let to_owned = |v: &[Cow<str>]| -> Vec<Cow<'static, str>> {
+            v.iter().map(|v| Cow::Owned(v.to_string())).collect()
+        };
+        let mut targets = to_owned(first.tool.package().targets());
+        let mut configurations = to_owned(first.tool.package().configurations());
+        let mut components = Vec::new();
+        let mut features = Vec::new();
+        for member in &members {
+            let package = member.tool.package();
+            intersect(&mut targets, package.targets());
+            intersect(&mut configurations, package.configurations());
+            if package.get_components() == 0 {
+                components.push(MemberComponent {
+                    name: package.get_primary_name().into(),
+                    version: package.get_primary_version().into(),
+                    short_name: member.name.clone(),
+                    description: None,
+                    public: true,
+                });
+            }
+            for i in 0..package.get_components() {
+                components.push(MemberComponent::new(package.get_component(i)));
+            }
+            for feature in package.features() {
+                features.push(Cow::Owned(format!("{}/{}", member.name, feature)));
+            }
+        }
+        Ok(Self {
+            package: WorkspacePackage {
+                name,
+                version,
+                components,
+                targets,
+                configurations,
+                features,
+            },
+            members,
+        })
+    }
+
+    /// Selects the features of the given member.
+    ///
+    /// A `member/feature` entry only applies to that member, a plain entry applies to every
+    /// member which declares it.
+    fn member_features<'a>(member: &Member, features: &[&'a str]) -> Vec<&'a str> {
+        features
+            .iter()
+            .filter_map(|v| match v.rsplit_once('/') {
+                Some((name, feature)) => (name == member.name).then_some(feature),
+                None => {
+                    let features = member.tool.package().features();
+                    features.iter().any(|f| f == v).then_some(*v)
+                }
+            })
+            .collect()
+    }
+
+    fn check_features(&self, ctx: &Context) -> Result<()> {
+        if let Features::List(list) = &ctx.features {
+            for feature in *list {
+                let found = match feature.rsplit_once('/') {
+                    Some((name, feature)) => self.members.iter().any(|v| {
+                        v.name == name && v.tool.package().features().iter().any(|f| f == feature)
+                    }),
+                    None => self
+                        .members
+                        .iter()
+                        .any(|v| v.tool.package().features().iter().any(|f| f == feature)),
+                };
+                if !found {
+                    return Err(Error::UnknownFeature((*feature).into()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn for_each_member(
+        &self,
+        ctx: &Context,
+        mut f: impl FnMut(&Member, &Context) -> Result<()>,
+    ) -> Result<()> {
+        self.check_features(ctx)?;
+        for member in &self.members {
+            let path = ctx.path.join(&member.name);
+            let features = match ctx.features {
+                Features::All => None,
+                Features::List(list) => Some(Self::member_features(member, list)),
+            };
+            let ctx = Context {
+                path: &path,
+                configuration: ctx.configuration,
+                features: match &features {
+                    Some(v) => Features::List(v),
+                    None => Features::All,
+                },
+            };
+            f(member, &ctx).map_err(|e| MemberError::wrap(&member.name, e))?;
+        }
+        Ok(())
+    }
+}
+
+impl BuildTool for WorkspaceTool {
+    fn name(&self) -> &str {
+        "Workspace"
+    }
+
+    fn package(&self) -> &dyn Package {
+        &self.package
+    }
+
+    fn configure(&self, ctx: &Context, targets: &[&str]) -> Result<()> {
+        self.for_each_member(ctx, |member, ctx| {
+            info!("Configuring member {}...", member.name);
+            member.tool.configure(ctx, targets)
+        })
+    }
+
+    fn build(&self, ctx: &Context, target: &str) -> Result<()> {
+        self.for_each_member(ctx, |member, ctx| {
+            info!("Building member {}...", member.name);
+            member.tool.build(ctx, target)
+        })
+    }
+
+    fn pre_package(&self, ctx: &Context, target: &str) -> Result<List> {
+        let mut artifacts = List::new();
+        self.for_each_member(ctx, |member, ctx| {
+            info!("Packaging member {}...", member.name);
+            let list = member.tool.pre_package(ctx, target)?;
+            artifacts.merge_namespaced(&member.name, list);
+            Ok(())
+        })?;
+        Ok(artifacts)
+    }
+
+    fn test(&self, ctx: &Context, target: &str) -> Result<TestReport> {
+        let mut report = TestReport::new();
+        self.for_each_member(ctx, |member, ctx| {
+            info!("Testing member {}...", member.name);
+            let res = member.tool.test(ctx, target)?;
+            report.merge_namespaced(&member.name, res);
+            Ok(())
+        })?;
+        Ok(report)
+    }
+
+    fn clean(&self, ctx: &Context, targets: &[&str], scope: CleanScope) -> Result<()> {
+        self.for_each_member(ctx, |member, ctx| {
+            info!("Cleaning member {}...", member.name);
+            member.tool.clean(ctx, targets, scope)
+        })?;
+        clean_outputs(ctx.path, ctx.configuration, targets, scope)
+    }
+}
@@ -0,0 +1,348 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::error::{Diagnostic, Error};
+use crate::model::Manifest;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use toml::de::{DeTable, DeValue};
+use toml::Spanned;
+
+/// Name of the project manifest file.
+pub const MANIFEST_NAME: &str = "bp3d.toml";
+
+fn path_to_key(path: &serde_ignored::Path, out: &mut Vec<String>) {
+    match path {
+        serde_ignored::Path::Root => (),
+        serde_ignored::Path::Seq { parent, index } => {
+            path_to_key(parent, out);
+            out.push(index.to_string());
+        }
+        serde_ignored::Path::Map { parent, key } => {
+            path_to_key(parent, out);
+            out.push(key.clone());
+        }
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => path_to_key(parent, out),
+    }
+}
+
+fn join_key(segments: &[String]) -> String {
+    if segments.is_empty() {
+        ".".into()
+    } else {
+        segments.join(".")
+    }
+}
+
+/// The spans of all keys of a TOML document, keyed by their path.
+#[derive(Default)]
+struct Spans(HashMap<Vec<String>, Range<usize>>);
+
+impl Spans {
+    fn new(root: &Spanned<DeTable>) -> Self {
+        let mut spans = Spans::default();
+        spans.0.insert(Vec::new(), root.span());
+        spans.add_table(&mut Vec::new(), root.get_ref());
+        spans
+    }
+
+    fn add_table(&mut self, path: &mut Vec<String>, table: &DeTable) {
+        for (key, value) in table.iter() {
+            path.push(key.get_ref().to_string());
+            self.0.insert(path.clone(), key.span());
+            self.add_value(path, value.get_ref());
+            path.pop();
+        }
+    }
+
+    fn add_value(&mut self, path: &mut Vec<String>, value: &DeValue) {
+        match value {
+            DeValue::Table(table) => self.add_table(path, table),
+            DeValue::Array(array) => {
+                for (index, item) in array.iter().enumerate() {
+                    path.push(index.to_string());
+                    self.0.insert(path.clone(), item.span());
+                    self.add_value(path, item.get_ref());
+                    path.pop();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Returns the span of the key designated by the given path.
+    fn locate(&self, segments: &[String]) -> Option<Range<usize>> {
+        self.0.get(segments).cloned()
+    }
+}
+
+/// Deserializes a TOML document, collecting unknown keys as warnings.
+fn parse<T: DeserializeOwned>(
+    path: &Path,
+    text: &str,
+) -> Result<(T, Vec<Diagnostic>, Spans), Error> {
+    let table = DeTable::parse(text)
+        .map_err(|e| Error::Syntax(Diagnostic::new(path, text, e.span(), ".", e.message())))?;
+    let spans = Spans::new(&table);
+    let mut ignored = Vec::new();
+    let mut callback = |v: serde_ignored::Path| {
+        let mut segments = Vec::new();
+        path_to_key(&v, &mut segments);
+        ignored.push(segments);
+    };
+    let de = serde_ignored::Deserializer::new(toml::de::Deserializer::from(table), &mut callback);
+    let value: T = serde_path_to_error::deserialize(de).map_err(|e| {
+        let key = e.path().to_string();
+        let inner = e.into_inner();
+        Error::Invalid(Diagnostic::new(
+            path,
+            text,
+            inner.span(),
+            key,
+            inner.message(),
+        ))
+    })?;
+    let mut warnings: Vec<Diagnostic> = ignored
+        .into_iter()
+        .map(|v| Diagnostic::new(path, text, spans.locate(&v), join_key(&v), "unknown key"))
+        .collect();
+    warnings.sort_by_key(|v| v.location);
+    Ok((value, warnings, spans))
+}
+
+/// Reads and deserializes a TOML file, returning unknown keys as warnings.
+pub(crate) fn read<T: DeserializeOwned>(path: &Path) -> Result<(T, Vec<Diagnostic>), Error> {
+    let text = std::fs::read_to_string(path).map_err(Error::Io)?;
+    let (value, warnings, _) = parse(path, &text)?;
+    Ok((value, warnings))
+}
+
+/// A parsed `bp3d.toml` file.
+pub struct Document {
+    path: PathBuf,
+    text: String,
+    manifest: Manifest,
+    warnings: Vec<Diagnostic>,
+    spans: Spans,
+}
+
+impl Document {
+    /// Parses the given manifest text; `path` is only used to report errors.
+    pub fn parse(path: impl Into<PathBuf>, text: String) -> Result<Self, Error> {
+        let path = path.into();
+        let (manifest, warnings, spans) = parse(&path, &text)?;
+        Ok(Self {
+            path,
+            text,
+            manifest,
+            warnings,
+            spans,
+        })
+    }
+
+    /// Reads and parses the manifest file at the given path.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path).map_err(Error::Io)?;
+        Self::parse(path, text)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    pub fn into_manifest(self) -> Manifest {
+        self.manifest
+    }
+
+    /// Returns the list of unknown keys found in the manifest.
+    pub fn warnings(&self) -> &[Diagnostic] {
+        &self.warnings
+    }
+
+    /// Deserializes the `[packager.<name>]` table of this manifest.
+    ///
+    /// Unknown keys are returned as warnings.
+    pub fn packager<T: DeserializeOwned>(
+        &self,
+        name: &str,
+    ) -> Result<Option<(T, Vec<Diagnostic>)>, Error> {
+        let table = match self.manifest.packager.as_ref().and_then(|v| v.get(name)) {
+            Some(v) => v.clone(),
+            None => return Ok(None),
+        };
+        let prefix = [String::from("packager"), name.into()];
+        let mut ignored = Vec::new();
+        let mut callback = |v: serde_ignored::Path| {
+            let mut segments = prefix.to_vec();
+            path_to_key(&v, &mut segments);
+            ignored.push(segments);
+        };
+        let de = serde_ignored::Deserializer::new(table, &mut callback);
+        let value: T = serde_path_to_error::deserialize(de).map_err(|e| {
+            let mut segments = prefix.to_vec();
+            segments.extend(e.path().iter().map(|v| v.to_string()));
+            let message = e.into_inner().message().to_string();
+            let span = self.spans.locate(&segments);
+            Error::Invalid(Diagnostic::new(
+                &self.path,
+                &self.text,
+                span,
+                join_key(&segments),
+                message,
+            ))
+        })?;
+        let mut warnings: Vec<Diagnostic> = ignored
+            .into_iter()
+            .map(|v| {
+                let span = self.spans.locate(&v);
+                Diagnostic::new(&self.path, &self.text, span, join_key(&v), "unknown key")
+            })
+            .collect();
+        warnings.sort_by_key(|v| v.location);
+        Ok(Some((value, warnings)))
+    }
+}
+
+/// Loads the `bp3d.toml` manifest of the given project root, if it exists.
+///
+/// Unknown keys are not logged, they are available through [Document::warnings].
+pub fn load(root: &Path) -> Result<Option<Document>, Error> {
+    let path = root.join(MANIFEST_NAME);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    Document::open(&path).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Document;
+    use crate::error::{Diagnostic, Error};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Packager {
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    fn keys(warnings: &[Diagnostic]) -> Vec<(&str, Option<(usize, usize)>)> {
+        warnings.iter().map(|v| (&*v.key, v.location)).collect()
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let text = "unknown = 1\n\n[package]\nname = \"test\"\nversion = \"1.0.0\"\n  typo = true\n\n[lua]\npaths = [\"lua\"]\n";
+        let document = Document::parse("bp3d.toml", text.into()).unwrap();
+        assert_eq!(
+            keys(document.warnings()),
+            [("unknown", Some((1, 1))), ("package.typo", Some((6, 3)))]
+        );
+        assert_eq!(
+            document.warnings()[1].to_string(),
+            "bp3d.toml:6:3: unknown key (at `package.typo`)"
+        );
+        let lua = document.manifest().lua.as_ref().unwrap();
+        assert_eq!(lua.paths.as_deref(), Some(&[String::from("lua")][..]));
+    }
+
+    #[test]
+    fn unknown_packager_keys() {
+        let text = "[packager.dist]\nname = \"test\"\nextra = { a = 1 }\n";
+        let document = Document::parse("bp3d.toml", text.into()).unwrap();
+        assert!(document.warnings().is_empty());
+        let (packager, warnings) = document.packager::<Packager>("dist").unwrap().unwrap();
+        assert_eq!(packager.name, "test");
+        assert_eq!(keys(&warnings), [("packager.dist.extra", Some((3, 1)))]);
+        assert!(document.packager::<Packager>("other").unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid_packager_value() {
+        let text = "[packager.dist]\nname = 12\n";
+        let document = Document::parse("bp3d.toml", text.into()).unwrap();
+        match document.packager::<Packager>("dist") {
+            Err(Error::Invalid(v)) => {
+                assert_eq!(v.key, "packager.dist.name");
+                assert_eq!(v.location, Some((2, 1)));
+            }
+            _ => panic!("expected an invalid value error"),
+        }
+    }
+
+    #[test]
+    fn invalid_value() {
+        let text = "[package]\nname = \"test\"\nversion = 1\n";
+        match Document::parse("bp3d.toml", text.into()) {
+            Err(Error::Invalid(v)) => {
+                assert_eq!(v.key, "package.version");
+                assert_eq!(v.location, Some((3, 11)));
+            }
+            _ => panic!("expected an invalid value error"),
+        }
+    }
+
+    #[test]
+    fn syntax_error() {
+        let text = "[package]\nname = \"test\n";
+        match Document::parse("bp3d.toml", text.into()) {
+            Err(Error::Syntax(v)) => assert_eq!(v.location.map(|v| v.0), Some(2)),
+            _ => panic!("expected a syntax error"),
+        }
+    }
+
+    #[test]
+    fn array_locations() {
+        let text = "[workspace]\nmembers = [\n    \"a\",\n    \"b\",\n]\n\n[[workspace.unknown]]\nkey = 1\n";
+        let document = Document::parse("bp3d.toml", text.into()).unwrap();
+        let spans = &document.spans;
+        let location = |path: &[&str]| {
+            let path: Vec<String> = path.iter().map(|v| v.to_string()).collect();
+            let span = spans.locate(&path);
+            Diagnostic::new(document.path(), text, span, "", "").location
+        };
+        assert_eq!(location(&["workspace", "members", "1"]), Some((4, 5)));
+        assert_eq!(
+            location(&["workspace", "unknown", "0", "key"]),
+            Some((8, 1))
+        );
+        assert_eq!(location(&["workspace", "missing"]), None);
+        assert_eq!(
+            keys(document.warnings()),
+            [("workspace.unknown", Some((7, 13)))]
+        );
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::error::{Diagnostic, Error};
+use bp3d_debug::debug;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ParamValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl ParamValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ParamValue::String(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            ParamValue::Integer(v) => Some(*v as _),
+            ParamValue::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            ParamValue::Integer(v) => Some(*v),
+            ParamValue::Float(v) => Some(*v as _),
+            _ => None,
+        }
+    }
+
+    pub fn as_boolean(&self) -> Option<bool> {
+        match self {
+            ParamValue::Boolean(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_enum<T: Copy>(&self, possible_values: &[(&str, T)]) -> Option<T> {
+        let s = self.as_str()?;
+        for (key, val) in possible_values {
+            if *key == s {
+                return Some(*val);
+            }
+        }
+        None
+    }
+}
+
+pub type Parameters = HashMap<String, ParamValue>;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Source {
+    pub url: String,
+    pub params: Parameters,
+}
+
+impl Source {
+    pub fn scheme(&self) -> Option<&str> {
+        let id = self.url.as_bytes().iter().position(|b| *b == b':')?;
+        Some(&self.url[..id])
+    }
+
+    pub fn path(&self) -> &str {
+        let id = self.url.as_bytes().iter().position(|b| *b == b':');
+        match id {
+            Some(id) => {
+                let bytes = &self.url[id + 1..];
+                if bytes.len() > 2 && bytes.as_bytes()[0] == b'/' && bytes.as_bytes()[1] == b'/' {
+                    &bytes[2..]
+                } else {
+                    bytes
+                }
+            }
+            None => &self.url,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SourceDep {
+    pub source: String,
+    pub version: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum Dependency {
+    WithSource(SourceDep),
+    Version(String),
+}
+
+impl Dependency {
+    pub fn version(&self) -> &str {
+        match self {
+            Dependency::WithSource(v) => &v.version,
+            Dependency::Version(v) => v,
+        }
+    }
+
+    pub fn source(&self) -> Option<&str> {
+        match self {
+            Dependency::WithSource(v) => Some(&v.source),
+            Dependency::Version(_) => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Config {
+    /// Represents the default package source for publishing new packages.
+    #[serde(rename = "default")]
+    pub default_source: Option<String>,
+
+    /// The list of dependencies to be installed.
+    pub dependencies: Option<HashMap<String, Dependency>>,
+
+    /// A declaration of all available package sources.
+    pub sources: Option<HashMap<String, Source>>,
+}
+
+impl Config {
+    /// Merges another configuration file into this one.
+    ///
+    /// The default source is only taken from `other` when none is set, package sources from
+    /// `other` replace the ones with the same name.
+    pub fn merge(&mut self, other: Config) {
+        if self.default_source.is_none() && other.default_source.is_some() {
+            self.default_source = other.default_source;
+        }
+        self.sources
+            .get_or_insert_default()
+            .extend(other.sources.unwrap_or_default());
+    }
+
+    /// Merges the user and built-in fpkg.toml files, in that order, into this configuration.
+    ///
+    /// Returns the unknown keys found in the merged files.
+    pub fn merge_defaults(&mut self) -> Result<Vec<Diagnostic>, Error> {
+        let mut warnings = Vec::new();
+        for path in search_paths() {
+            if path.is_file() {
+                debug!("adding config path: {:?}...", &path);
+                let (config, mut w) = load_standalone(&path)?;
+                self.merge(config);
+                warnings.append(&mut w);
+            }
+        }
+        Ok(warnings)
+    }
+}
+
+/// Returns the list of standalone fpkg.toml files which may extend a project configuration.
+pub fn search_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(path) = bp3d_os::dirs::system::get_user_home() {
+        paths.push(path.join("fpkg.toml"));
+    }
+    if let Some(exe) = bp3d_os::assets::get_executable_path() {
+        paths.push(exe.join("../etc/fpkg.toml"));
+        paths.push(exe.join("../../res/config/fpkg.toml"));
+    }
+    paths
+}
+
+/// Loads a standalone fpkg.toml file, returning the configuration and its unknown keys.
+pub fn load_standalone(path: &Path) -> Result<(Config, Vec<Diagnostic>), Error> {
+    crate::document::read(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, Source};
+    use std::collections::HashMap;
+
+    fn source(url: &str) -> Source {
+        Source {
+            url: url.into(),
+            params: HashMap::new(),
+        }
+    }
+
+    fn config(default_source: Option<&str>, sources: &[(&str, &str)]) -> Config {
+        Config {
+            default_source: default_source.map(String::from),
+            dependencies: None,
+            sources: Some(
+                sources
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), source(v)))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn merge_overrides() {
+        let mut project = config(None, &[("main", "https://project")]);
+        project.merge(config(
+            Some("user"),
+            &[("main", "https://user"), ("user", "file://user")],
+        ));
+        project.merge(config(Some("builtin"), &[("builtin", "file://builtin")]));
+        assert_eq!(project.default_source.as_deref(), Some("user"));
+        let sources = project.sources.unwrap();
+        assert_eq!(sources.len(), 3);
+        assert_eq!(sources["main"].url, "https://user");
+        assert_eq!(sources["user"].path(), "user");
+        assert_eq!(sources["builtin"].scheme(), Some("file"));
+    }
+
+    #[test]
+    fn merge_keeps_project_default() {
+        let mut project = config(Some("project"), &[]);
+        project.merge(config(Some("user"), &[]));
+        assert_eq!(project.default_source.as_deref(), Some("project"));
+        let mut empty = Config::default();
+        empty.merge(Config::default());
+        assert!(empty.sources.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Overrides the primary package name and version detected by the build system.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Package {
+    pub name: String,
+    pub version: String,
+}
+
+/// Declares sub-projects which are built together with the project.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Workspace {
+    /// Paths to the member projects, relative to the workspace root.
+    pub members: Vec<String>,
+
+    /// The members each member depends on, keyed by member path.
+    pub dependencies: Option<HashMap<String, Vec<String>>>,
+}
+
+/// Lua engine configuration.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Lua {
+    /// Additional Lua search paths, relative to the project root.
+    pub paths: Option<Vec<String>>,
+}
+
+/// Budgets enforced when comparing build reports.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Report {
+    /// Maximum increase of an artifact size relative to the compared report, in percent.
+    pub max_size_increase: Option<f64>,
+
+    /// Maximum increase of a phase duration relative to the compared report, in percent.
+    pub max_time_increase: Option<f64>,
+
+    /// Maximum artifact sizes in bytes, keyed by artifact name for all targets or by
+    /// `<target>/<name>` for a single target.
+    pub sizes: Option<HashMap<String, u64>>,
+
+    /// Maximum phase durations in seconds, keyed by phase name for all targets or by
+    /// `<target>/<name>` for a single target.
+    pub times: Option<HashMap<String, f64>>,
+}
+
+/// The content of a `bp3d.toml` file.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct Manifest {
+    pub package: Option<Package>,
+
+    pub workspace: Option<Workspace>,
+
+    /// Per packager configuration, keyed by packager name.
+    pub packager: Option<HashMap<String, toml::Table>>,
+
+    pub fpkg: Option<crate::fpkg::Config>,
+
+    pub report: Option<Report>,
+
+    pub lua: Option<Lua>,
+}
+
+impl Manifest {
+    pub fn to_toml(&self) -> Result<String, Error> {
+        toml::to_string_pretty(self).map_err(Error::Serialize)
+    }
+}
@@ -26,17 +26,27 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::manifest_ext::parse_manifest;
 use crate::packager::{Context, Packager};
 use bp3d_util::result::ResultExt;
 use std::collections::VecDeque;
 
 pub fn run_packager<'a, T: Packager<'a>>(context: &'a Context) {
     println!("Initializing packager {}...", T::NAME);
-    let config: Option<T::Config> = parse_manifest(context.path, context.packager).expect_exit(
-        "Failed to load packager configuration from root manifest",
-        1,
-    );
+    let config: Option<T::Config> = bp3d_config::load(context.path)
+        .and_then(|v| match v {
+            Some(v) => v.packager(context.packager),
+            None => Ok(None),
+        })
+        .expect_exit(
+            "Failed to load packager configuration from root manifest",
+            1,
+        )
+        .map(|(config, warnings)| {
+            for w in warnings {
+                eprintln!("Warning: {}", w);
+            }
+            config
+        });
     let packager = T::new(config, context).expect_exit("Failed to initialize packager", 1);
     println!("Building targets...");
     let mut v = VecDeque::new();
@@ -33,9 +33,10 @@ use bp3d_lua::vm::table::Table;
 use bp3d_lua::vm::value::types::Function;
 use bp3d_lua::vm::Vm;
 use bp3d_util::simple_error;
-use bp3d_build::lua::core::dump_backtrace;
+use bp3d_build::lua::error::LuaError;
 use bp3d_build::system::artifact::List;
 use bp3d_build::system::Features;
+use bp3d_build::system::target::{self, Target};
 use crate::packager::Context;
 use crate::packager::interface::{build_target, Packager};
 use bp3d_build::lua::List as LuaList;
@@ -44,11 +45,18 @@ use bp3d_build::lua::util::convert_package;
 simple_error! {
     pub Error {
         (impl From) Lua(bp3d_lua::vm::error::Error) => "lua error: {}",
+        Script(LuaError) => "{}",
         Build(bp3d_build::core::Error) => "build error: {}",
+        Paths(bp3d_build::lua::paths::Error) => "lua search path error: {}",
+        Target(target::Error) => "invalid target: {}",
         NotFound(String) => "packager not found: {}"
     }
 }
 
+fn lua_error<'a>(vm: &'a bp3d_build::lua::core::Vm, phase: &'static str, target: Option<&'a str>) -> impl FnOnce(bp3d_lua::vm::error::Error) -> Error + 'a {
+    move |e| Error::Script(vm.to_error(&e).with_phase(phase, target))
+}
+
 pub struct Lua<'a> {
     vm: bp3d_build::lua::core::Vm,
     context: &'a Context<'a>
@@ -74,23 +82,24 @@ impl<'a> Packager<'a> for Lua<'a> {
 
     #[allow(dependency_on_unit_never_type_fallback)]
     fn new(config: Option<Self::Config>, context: &'a Context<'a>) -> Result<Self, Self::Error> {
-        let mut vm = bp3d_build::lua::core::Vm::new(context.path)?;
+        let paths = bp3d_build::lua::paths::resolve(context.path).map_err(Error::Paths)?;
+        let mut vm = bp3d_build::lua::core::Vm::new(context.path, paths)?;
         let path = vm.find(&format!("package/{}.lua", context.packager));
         if path.is_none() {
             return Err(Error::NotFound(context.packager.into()));
         }
         let path = path.unwrap();
-        vm.run(&path)?;
+        vm.run(&path).map_err(lua_error(&vm, "load", None))?;
         if let Some(config) = config {
-            vm.call_main(config.len(), config.iter().map(|(k, v)| (&**k, &**v)))?;
+            vm.call_main(config.len(), config.iter().map(|(k, v)| (&**k, &**v))).map_err(lua_error(&vm, "init", None))?;
         } else {
-            vm.call_main(0, [].into_iter()).map_err(Error::Lua)?;
+            vm.call_main(0, [].into_iter()).map_err(lua_error(&vm, "init", None))?;
         }
         vm.with_class(|vm, class| {
             let f: Function = class.get(c"init2")?;
             let ctx = create_context(vm, context)?;
-            dump_backtrace(f.call((class.clone(), ctx)))
-        }).map_err(Error::Lua)?;
+            f.call((class.clone(), ctx))
+        }).map_err(lua_error(&vm, "init2", None))?;
         Ok(Lua {
             context,
             vm
@@ -108,12 +117,13 @@ impl<'a> Packager<'a> for Lua<'a> {
                 configuration: self.context.configuration,
                 features: Features::All
             };
+            let info = Target::parse(target).map_err(Error::Target)?;
             let (f, _guard) = RClosure::from_rust_temporary(self.vm.get(), |config: Table| {
                 let target: &str = config.get(c"target")?;
                 build_target(&self.context, target).map(|v| LuaList::from(v)).map_err(Error::Build)
             });
             self.vm.get().set_global(c"baseBuild", f)?;
-            let value: LuaList = self.vm.call_userdata("buildTarget", &ctx, target).map_err(Error::Lua)?;
+            let value: LuaList = self.vm.call_userdata("buildTarget", &ctx, &info).map_err(lua_error(&self.vm, "buildTarget", Some(target)))?;
             Ok(value.into_inner())
         } else {
             build_target(&self.context, target).map_err(Error::Build)
@@ -123,8 +133,8 @@ impl<'a> Packager<'a> for Lua<'a> {
     fn do_build(&self) -> Result<(), Self::Error> {
         self.vm.with_class(|_, class| {
             let f: Function = class.get(c"build")?;
-            dump_backtrace(f.call(class.clone()))
-        }).map_err(Error::Lua)
+            f.call(class.clone())
+        }).map_err(lua_error(&self.vm, "build", None))
     }
 
     fn do_package_target(&self, list: &List, target: &str) -> Result<(), Self::Error> {
@@ -133,13 +143,14 @@ impl<'a> Packager<'a> for Lua<'a> {
             configuration: self.context.configuration,
             features: Features::All
         };
-        self.vm.call_context("packageTarget", &ctx, target, list.clone()).map_err(Error::Lua)
+        let info = Target::parse(target).map_err(Error::Target)?;
+        self.vm.call_artifacts("packageTarget", &ctx, &info, list).map_err(lua_error(&self.vm, "packageTarget", Some(target)))
     }
 
     fn do_package(&self) -> Result<(), Self::Error> {
         self.vm.with_class(|_, class| {
             let f: Function = class.get(c"package")?;
-            dump_backtrace(f.call(class.clone()))
-        }).map_err(Error::Lua)
+            f.call(class.clone())
+        }).map_err(lua_error(&self.vm, "package", None))
     }
 }
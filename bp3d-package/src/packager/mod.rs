@@ -29,6 +29,9 @@
 mod interface;
 mod util;
 
+/// Packagers provided by dynamically loaded plugins.
+pub mod plugin;
+
 use crate::packager::util::packager_registry;
 
 packager_registry! {
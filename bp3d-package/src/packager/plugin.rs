@@ -0,0 +1,182 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::packager::interface::{build_target, Packager};
+use crate::packager::Context;
+use bp3d_build::plugin::{abi, artifact_to_raw, Collector, Registry};
+use bp3d_build::system::artifact::List;
+use bp3d_util::simple_error;
+use std::ffi::c_void;
+
+simple_error! {
+    pub Error {
+        Plugin(String) => "plugin error: {}",
+        NotFound(String) => "packager not found: {}",
+        InvalidUtf8 => "invalid utf-8",
+        Config(toml::ser::Error) => "failed to serialize packager configuration: {}"
+    }
+}
+
+struct State<'a> {
+    context: &'a Context<'a>,
+    _config: String,
+    _targets: Vec<abi::Str>,
+    raw: abi::PackagerContext,
+}
+
+unsafe extern "C" fn base_build(
+    host: *mut c_void,
+    target: abi::Str,
+    out: *const abi::Output,
+) -> bool {
+    let state = &*(host as *const State);
+    let out = &*out;
+    let res = target
+        .as_str()
+        .ok_or_else(|| String::from("invalid utf-8"))
+        .and_then(|target| build_target(state.context, target).map_err(|e| e.to_string()));
+    match res {
+        Ok(list) => {
+            for artifact in list.into_inner() {
+                match artifact_to_raw(&artifact) {
+                    Some(raw) => (out.add_artifact)(out.data, &raw),
+                    None => {
+                        (out.set_error)(out.data, abi::Str::new("invalid utf-8 in artifact path"));
+                        return false;
+                    }
+                }
+            }
+            true
+        }
+        Err(e) => {
+            (out.set_error)(out.data, abi::Str::new(&e));
+            false
+        }
+    }
+}
+
+/// A packager implemented by a dynamically loaded plugin.
+pub struct Plugin<'a> {
+    table: &'static abi::Packager,
+    handle: *mut c_void,
+    _state: Box<State<'a>>,
+}
+
+impl<'a> Plugin<'a> {
+    fn call(&self, f: impl FnOnce(*mut c_void, &abi::Output) -> bool) -> Result<List, Error> {
+        let mut collector = Collector::new();
+        let out = collector.output();
+        let res = f(self.handle, &out);
+        collector.finish(res).map_err(Error::Plugin)
+    }
+}
+
+impl<'a> Drop for Plugin<'a> {
+    fn drop(&mut self) {
+        unsafe { (self.table.close)(self.handle) };
+    }
+}
+
+impl<'a> Packager<'a> for Plugin<'a> {
+    const NAME: &'static str = "Plugin";
+    type Error = Error;
+    type Config = toml::Table;
+
+    fn new(config: Option<Self::Config>, context: &'a Context<'a>) -> Result<Self, Self::Error> {
+        let table = Registry::lock()
+            .find_packager(context.packager)
+            .ok_or_else(|| Error::NotFound(context.packager.into()))?;
+        let config = match config {
+            Some(v) => toml::to_string(&v).map_err(Error::Config)?,
+            None => String::new(),
+        };
+        let targets: Vec<abi::Str> = context.targets.iter().map(|v| abi::Str::new(v)).collect();
+        let mut state = Box::new(State {
+            context,
+            raw: abi::PackagerContext {
+                path: abi::Str::new(context.path.to_str().ok_or(Error::InvalidUtf8)?),
+                configuration: abi::Str::new(context.configuration),
+                targets: abi::StrList::new(&targets),
+                host: std::ptr::null_mut(),
+                base_build,
+                config: abi::Str::new(&config),
+            },
+            _config: config,
+            _targets: targets,
+        });
+        state.raw.host = &*state as *const State as *mut c_void;
+        let mut collector = Collector::new();
+        let out = collector.output();
+        let handle = unsafe { (table.new)(&state.raw, &out) };
+        if handle.is_null() {
+            let message = collector
+                .take_error()
+                .unwrap_or_else(|| "unknown plugin error".into());
+            return Err(Error::Plugin(message));
+        }
+        Ok(Plugin {
+            table,
+            handle,
+            _state: state,
+        })
+    }
+
+    fn do_build_target(&self, target: &str) -> Result<List, Self::Error> {
+        self.call(|handle, out| unsafe {
+            (self.table.build_target)(handle, abi::Str::new(target), out)
+        })
+    }
+
+    fn do_build(&self) -> Result<(), Self::Error> {
+        self.call(|handle, out| unsafe { (self.table.build)(handle, out) })
+            .map(|_| ())
+    }
+
+    fn do_package_target(&self, list: &List, target: &str) -> Result<(), Self::Error> {
+        let artifacts: Vec<abi::Artifact> = list
+            .iter()
+            .map(artifact_to_raw)
+            .collect::<Option<_>>()
+            .ok_or(Error::InvalidUtf8)?;
+        self.call(|handle, out| unsafe {
+            (self.table.package_target)(
+                handle,
+                abi::Str::new(target),
+                artifacts.as_ptr(),
+                artifacts.len(),
+                out,
+            )
+        })
+        .map(|_| ())
+    }
+
+    fn do_package(&self) -> Result<(), Self::Error> {
+        self.call(|handle, out| unsafe { (self.table.package)(handle, out) })
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,331 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Stable plugin ABI for bp3d.
+//!
+//! A plugin is a bp3d-os module exporting a [Plugin] descriptor under the [ENTRY_POINT] symbol.
+//! Plugins are loaded through `bp3d_os::module::loader::ModuleLoader`, so the library must also
+//! carry the bp3d-os module metadata. When loading the library, the host checks that
+//! [Plugin::abi_version] is equal to [ABI_VERSION] and then calls [Plugin::register] which is
+//! expected to register any number of [BuildSystem] and [Packager] tables.
+//!
+//! All types in this crate are `#[repr(C)]` and only use C compatible types, which allows plugins
+//! to be written with any compiler or language. Strings are never NUL terminated, they are always
+//! passed as a pointer + length pair ([Str]) and must be valid UTF-8. Unless stated otherwise,
+//! pointers passed by the host are only valid for the duration of the call, and pointers returned
+//! by the plugin must stay valid until the handle they were obtained from is closed.
+
+use std::ffi::c_void;
+
+/// The current version of the plugin ABI.
+///
+/// This number is incremented on every change to the layout of any type of this crate. The host
+/// only loads plugins built against the exact same version.
+pub const ABI_VERSION: u32 = 1;
+
+/// The name of the symbol holding the [Plugin] descriptor.
+pub const ENTRY_POINT: &str = "bp3d_plugin";
+
+/// A borrowed UTF-8 string.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Str {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+unsafe impl Sync for Str {}
+
+impl Str {
+    /// The empty string, which is also used to represent an absent optional value.
+    pub const EMPTY: Str = Str::new("");
+
+    pub const fn new(value: &str) -> Self {
+        Self {
+            ptr: value.as_ptr(),
+            len: value.len(),
+        }
+    }
+
+    /// Returns the string pointed by this [Str], or [None] if it is not valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for reads of `len` bytes and for the whole lifetime `'a`.
+    pub unsafe fn as_str<'a>(&self) -> Option<&'a str> {
+        if self.len == 0 {
+            return Some("");
+        }
+        std::str::from_utf8(std::slice::from_raw_parts(self.ptr, self.len)).ok()
+    }
+}
+
+/// A borrowed list of strings.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct StrList {
+    pub ptr: *const Str,
+    pub len: usize,
+}
+
+impl StrList {
+    pub const EMPTY: StrList = StrList {
+        ptr: std::ptr::null(),
+        len: 0,
+    };
+
+    pub fn new(values: &[Str]) -> Self {
+        Self {
+            ptr: values.as_ptr(),
+            len: values.len(),
+        }
+    }
+
+    /// Returns the items of this list.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for reads of `len` items and for the whole lifetime `'a`.
+    pub unsafe fn as_slice<'a>(&self) -> &'a [Str] {
+        if self.len == 0 {
+            return &[];
+        }
+        std::slice::from_raw_parts(self.ptr, self.len)
+    }
+}
+
+/// A component of a package.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Component {
+    pub name: Str,
+    pub version: Str,
+    pub short_name: Str,
+    /// An empty description means no description.
+    pub description: Str,
+    pub public: bool,
+}
+
+/// Information about a package handled by a plugin build system.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PackageInfo {
+    pub name: Str,
+    pub version: Str,
+    pub components: *const Component,
+    pub components_len: usize,
+    pub targets: StrList,
+    pub configurations: StrList,
+    pub features: StrList,
+}
+
+/// The build context.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Context {
+    pub path: Str,
+    pub configuration: Str,
+    /// When true, all features are enabled and `features` is empty.
+    pub all_features: bool,
+    pub features: StrList,
+}
+
+pub const ARTIFACT_BIN: u32 = 0;
+pub const ARTIFACT_DYNAMIC_LIB: u32 = 1;
+pub const ARTIFACT_STATIC_LIB: u32 = 2;
+pub const ARTIFACT_HEADER: u32 = 3;
+pub const ARTIFACT_CONFIG: u32 = 4;
+pub const ARTIFACT_RESOURCE: u32 = 5;
+
+pub const CLEAN_DIST: u32 = 0;
+pub const CLEAN_TARGET: u32 = 1;
+pub const CLEAN_CONFIGURATION: u32 = 2;
+pub const CLEAN_EXT: u32 = 3;
+pub const CLEAN_ALL: u32 = 4;
+
+/// An artifact produced by a build.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Artifact {
+    /// One of the `ARTIFACT_*` constants.
+    pub ty: u32,
+    pub name: Str,
+    pub path: Str,
+    /// An empty path means no debug info.
+    pub debug_info: Str,
+    /// An empty path means no export file.
+    pub exports: Str,
+}
+
+pub const TEST_PASSED: u32 = 0;
+pub const TEST_FAILED: u32 = 1;
+pub const TEST_IGNORED: u32 = 2;
+
+/// The result of a single test.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct TestCase {
+    pub suite: Str,
+    pub name: Str,
+    /// One of the `TEST_*` constants.
+    pub status: u32,
+    /// Execution time in seconds, negative if unknown.
+    pub time: f64,
+    /// An empty message means no message.
+    pub message: Str,
+}
+
+/// Host callbacks used by plugins to return artifacts, test results and errors.
+#[repr(C)]
+pub struct Output {
+    pub data: *mut c_void,
+    /// Adds an artifact to the list returned to the host.
+    pub add_artifact: unsafe extern "C" fn(data: *mut c_void, artifact: *const Artifact),
+    /// Sets the error message, to be called before returning a failure to the host.
+    pub set_error: unsafe extern "C" fn(data: *mut c_void, message: Str),
+    /// Adds a test result to the report returned to the host.
+    pub add_test: unsafe extern "C" fn(data: *mut c_void, test: *const TestCase),
+}
+
+/// A build system implementation.
+#[repr(C)]
+pub struct BuildSystem {
+    /// The name of the build system, displayed by `bp3d plugins`.
+    pub name: Str,
+    /// Opens the project located at `root`.
+    ///
+    /// Returns a new handle if the project is handled by this build system, null otherwise. If
+    /// the project is handled but could not be loaded, the plugin should call
+    /// [Output::set_error] before returning null.
+    pub open: unsafe extern "C" fn(root: Str, out: *const Output) -> *mut c_void,
+    /// Closes a handle returned by `open`.
+    pub close: unsafe extern "C" fn(handle: *mut c_void),
+    /// Writes package information to `info`. The information must stay valid until the handle
+    /// is closed.
+    pub package: unsafe extern "C" fn(handle: *mut c_void, info: *mut PackageInfo),
+    pub configure: unsafe extern "C" fn(
+        handle: *mut c_void,
+        ctx: *const Context,
+        targets: StrList,
+        out: *const Output,
+    ) -> bool,
+    pub build: unsafe extern "C" fn(
+        handle: *mut c_void,
+        ctx: *const Context,
+        target: Str,
+        out: *const Output,
+    ) -> bool,
+    /// Returns the list of artifacts for the given target through [Output::add_artifact].
+    pub pre_package: unsafe extern "C" fn(
+        handle: *mut c_void,
+        ctx: *const Context,
+        target: Str,
+        out: *const Output,
+    ) -> bool,
+    /// Runs the tests for the given target and returns results through [Output::add_test].
+    pub test: unsafe extern "C" fn(
+        handle: *mut c_void,
+        ctx: *const Context,
+        target: Str,
+        out: *const Output,
+    ) -> bool,
+    /// Cleans build outputs owned by the build system, `scope` is one of the `CLEAN_*` constants.
+    pub clean: unsafe extern "C" fn(
+        handle: *mut c_void,
+        ctx: *const Context,
+        targets: StrList,
+        scope: u32,
+        out: *const Output,
+    ) -> bool,
+}
+
+unsafe impl Sync for BuildSystem {}
+
+/// The packaging context.
+///
+/// This context stays valid until the packager handle is closed.
+#[repr(C)]
+pub struct PackagerContext {
+    pub path: Str,
+    pub configuration: Str,
+    pub targets: StrList,
+    pub host: *mut c_void,
+    /// Runs pre-package on the project for the given target and returns the artifacts through
+    /// [Output::add_artifact].
+    pub base_build:
+        unsafe extern "C" fn(host: *mut c_void, target: Str, out: *const Output) -> bool,
+    /// The `[packager.<name>]` table of the root manifest serialized as TOML, empty if the
+    /// manifest has no such table.
+    pub config: Str,
+}
+
+/// A packager implementation.
+#[repr(C)]
+pub struct Packager {
+    /// The name used to select this packager with `bp3d package -p`.
+    pub name: Str,
+    /// Creates a new packager instance, returns null on failure.
+    pub new: unsafe extern "C" fn(ctx: *const PackagerContext, out: *const Output) -> *mut c_void,
+    /// Closes a handle returned by `new`.
+    pub close: unsafe extern "C" fn(handle: *mut c_void),
+    pub build_target:
+        unsafe extern "C" fn(handle: *mut c_void, target: Str, out: *const Output) -> bool,
+    pub build: unsafe extern "C" fn(handle: *mut c_void, out: *const Output) -> bool,
+    pub package_target: unsafe extern "C" fn(
+        handle: *mut c_void,
+        target: Str,
+        artifacts: *const Artifact,
+        artifacts_len: usize,
+        out: *const Output,
+    ) -> bool,
+    pub package: unsafe extern "C" fn(handle: *mut c_void, out: *const Output) -> bool,
+}
+
+unsafe impl Sync for Packager {}
+
+/// Host callbacks used to register plugin implementations.
+///
+/// Registered tables must stay valid as long as the plugin is loaded.
+#[repr(C)]
+pub struct Registrar {
+    pub data: *mut c_void,
+    pub register_build_system: unsafe extern "C" fn(data: *mut c_void, table: *const BuildSystem),
+    pub register_packager: unsafe extern "C" fn(data: *mut c_void, table: *const Packager),
+}
+
+/// The plugin descriptor exported under the [ENTRY_POINT] symbol.
+#[repr(C)]
+pub struct Plugin {
+    /// Must be set to [ABI_VERSION].
+    pub abi_version: u32,
+    pub name: Str,
+    pub version: Str,
+    pub register: unsafe extern "C" fn(registrar: *const Registrar),
+}
+
+unsafe impl Sync for Plugin {}
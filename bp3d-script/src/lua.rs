@@ -27,7 +27,9 @@
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::interface::{Context, Script};
-use bp3d_build::lua::core::{dump_backtrace, Vm};
+use bp3d_build::lua::core::Vm;
+use bp3d_build::lua::error::LuaError;
+use bp3d_build::lua::paths;
 use bp3d_build::lua::util::convert_package;
 use bp3d_build::system::Features;
 use bp3d_lua::libs::files::SandboxPath;
@@ -37,14 +39,16 @@ use std::fmt::{Display, Formatter};
 
 #[derive(Debug)]
 pub enum Error {
-    Lua(bp3d_lua::vm::error::Error),
+    Lua(LuaError),
+    Paths(paths::Error),
     NotFound(String),
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Error::Lua(e) => write!(f, "lua error: {}", e),
+            Error::Lua(e) => write!(f, "{}", e),
+            Error::Paths(e) => write!(f, "lua search path error: {}", e),
             Error::NotFound(name) => write!(f, "script not found ({})", name),
         }
     }
@@ -56,6 +60,13 @@ pub struct Lua {
     vm: Vm,
 }
 
+fn lua_error<'a>(
+    vm: &'a Vm,
+    phase: &'static str,
+) -> impl FnOnce(bp3d_lua::vm::error::Error) -> Error + 'a {
+    move |e| Error::Lua(vm.to_error(&e).with_phase(phase, None))
+}
+
 fn create_context<'a>(
     vm: &'a bp3d_lua::vm::Vm,
     context: &Context,
@@ -87,28 +98,32 @@ impl Script for Lua {
 
     #[allow(dependency_on_unit_never_type_fallback)]
     fn new(context: &Context, name: &str, args: &[&str]) -> Result<Self, Self::Error> {
-        let mut vm = Vm::new(context.path).map_err(Error::Lua)?;
+        let paths = paths::resolve(context.path).map_err(Error::Paths)?;
+        let mut vm =
+            Vm::new(context.path, paths).map_err(|e| Error::Lua(LuaError::new(&e, &[])))?;
         let path = vm.find(&format!("script/{}.lua", name));
         if path.is_none() {
             return Err(Error::NotFound(name.into()));
         }
         let path = path.unwrap();
-        vm.run(&path).map_err(Error::Lua)?;
+        vm.run(&path).map_err(lua_error(&vm, "load"))?;
         if args.is_empty() {
-            vm.call_main(0, [].into_iter()).map_err(Error::Lua)?;
+            vm.call_main(0, [].into_iter())
+                .map_err(lua_error(&vm, "init"))?;
         } else {
             let args = args.iter().map(|v| match v.find('=') {
                 Some(pos) => (&v[..pos], &v[pos + 1..]),
                 None => (*v, ""),
             });
-            vm.call_main(args.len(), args).map_err(Error::Lua)?;
+            vm.call_main(args.len(), args)
+                .map_err(lua_error(&vm, "init"))?;
         }
         vm.with_class(|vm, class| {
             let f: Function = class.get(c"init2")?;
             let ctx = create_context(vm, context)?;
-            dump_backtrace(f.call((class.clone(), ctx)))
+            f.call((class.clone(), ctx))
         })
-        .map_err(Error::Lua)?;
+        .map_err(lua_error(&vm, "init2"))?;
         Ok(Lua { vm })
     }
 
@@ -116,18 +131,18 @@ impl Script for Lua {
         self.vm
             .with_class(|_, class| {
                 let f: Function = class.get(c"needsConfigure")?;
-                dump_backtrace(f.call(class.clone()))
+                f.call(class.clone())
             })
-            .map_err(Error::Lua)
+            .map_err(lua_error(&self.vm, "needsConfigure"))
     }
 
     fn needs_build(&self) -> Result<bool, Self::Error> {
         self.vm
             .with_class(|_, class| {
                 let f: Function = class.get(c"needsBuild")?;
-                dump_backtrace(f.call(class.clone()))
+                f.call(class.clone())
             })
-            .map_err(Error::Lua)
+            .map_err(lua_error(&self.vm, "needsBuild"))
     }
 
     fn execute(&self) -> Result<i32, Self::Error> {
@@ -135,9 +150,9 @@ impl Script for Lua {
             .vm
             .with_class(|_, class| {
                 let f: Function = class.get(c"run")?;
-                dump_backtrace(f.call(class.clone()))
+                f.call(class.clone())
             })
-            .map_err(Error::Lua)?;
+            .map_err(lua_error(&self.vm, "run"))?;
         Ok(val.unwrap_or_default())
     }
 }
@@ -36,6 +36,24 @@ pub enum Command {
     PrePackage,
     Package,
     Run,
+    Config,
+    Plugins,
+    Clean,
+    Info,
+    Doctor,
+    Test,
+    Exec,
+    Report,
+    LuaStubs,
+    TestLua,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+pub enum CleanScope {
+    Dist,
+    Target,
+    Config,
+    Ext,
 }
 
 #[derive(Parser, Debug)]
@@ -74,6 +92,36 @@ pub struct Args {
     #[arg(short = 'p', long = "package", help = "The packager engine to use.")]
     pub package_type: Option<String>,
 
+    #[arg(long = "json", help = "Print machine readable output in JSON format.")]
+    pub json: bool,
+
+    #[arg(
+        short = 'w',
+        long = "watch",
+        help = "Re-run build, pre-package or run when project files change."
+    )]
+    pub watch: bool,
+
+    #[arg(
+        long = "compare",
+        help = "Previous report to compare against with the report command."
+    )]
+    pub compare: Option<PathBuf>,
+
+    #[arg(
+        long = "all",
+        help = "Clean everything with the clean command, including all cargo builds."
+    )]
+    pub clean_all: bool,
+
+    #[arg(
+        long = "scope",
+        value_enum,
+        conflicts_with = "clean_all",
+        help = "What to remove with the clean command, defaults to the packaging outputs."
+    )]
+    pub clean_scope: Option<CleanScope>,
+
     pub cmd: Command,
 
     pub other_args: Option<Vec<String>>,
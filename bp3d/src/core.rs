@@ -26,26 +26,200 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::args::Command;
+use crate::args::{CleanScope, Command};
 use bp3d_build::core;
 use bp3d_build::core::Error;
+use bp3d_build::lua::error::LuaError;
+use bp3d_build::plugin::Registry;
+use bp3d_build::report;
 use bp3d_build::system::Features;
-use bp3d_debug::{debug, info};
+use bp3d_build::system::test_report::TestStatus;
+use bp3d_config::{Manifest, Package};
+use bp3d_debug::{debug, info, warning};
 use bp3d_package::packager::PackagerType;
 use bp3d_package::packager::lua::Lua;
+use bp3d_package::packager::plugin::Plugin;
 use bp3d_package::run_packager;
 use bp3d_script::interface::Script;
-use bp3d_util::expect_return;
+use serde::Serialize;
 use std::path::Path;
 
+#[derive(Copy, Clone)]
 pub struct Context<'a> {
     pub path: &'a Path,
     pub configuration: &'a str,
     pub targets: &'a [&'a str],
     pub features: Features<'a>,
+    pub json: bool,
+    pub compare: Option<&'a Path>,
+    pub clean_all: bool,
+    pub clean_scope: Option<CleanScope>,
 }
 
-fn run_command(
+fn script_error(e: bp3d_script::lua::Error) -> Error {
+    match e {
+        bp3d_script::lua::Error::Lua(e) => Error::Lua(Box::new(e)),
+        e => Error::ScriptSystem(e.to_string()),
+    }
+}
+
+/// Returns the Lua error which caused the given error, if any.
+fn find_lua_error(e: &Error) -> Option<&LuaError> {
+    match e {
+        Error::Lua(e) => Some(e),
+        Error::Member(e) => find_lua_error(&e.error),
+        _ => None,
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorReport<'a> {
+    message: String,
+    lua: Option<&'a LuaError>,
+}
+
+pub(crate) fn print_error(ctx: &Context, msg: &str, e: &Error) {
+    if ctx.json {
+        let report = ErrorReport {
+            message: e.to_string(),
+            lua: find_lua_error(e),
+        };
+        match serde_json::to_string_pretty(&serde_json::json!({ "error": report })) {
+            Ok(v) => println!("{}", v),
+            Err(e) => eprintln!("Failed to serialize error: {}", e),
+        }
+        return;
+    }
+    eprintln!("{}: {}", msg, e);
+}
+
+fn print_config(tool: &dyn core::BuildTool, path: &Path) -> core::Result<i32> {
+    let document = bp3d_config::load(path).map_err(Error::Manifest)?;
+    let mut manifest = match document {
+        Some(document) => {
+            info!("Using manifest {:?}", document.path());
+            for w in document.warnings() {
+                warning!("{}", w);
+            }
+            document.into_manifest()
+        }
+        None => Manifest::default(),
+    };
+    manifest.package.get_or_insert_with(|| Package {
+        name: tool.package().get_primary_name().into(),
+        version: tool.package().get_primary_version().into(),
+    });
+    let warnings = manifest
+        .fpkg
+        .get_or_insert_default()
+        .merge_defaults()
+        .map_err(Error::Manifest)?;
+    for w in warnings {
+        warning!("{}", w);
+    }
+    let text = manifest.to_toml().map_err(Error::Manifest)?;
+    print!("{}", text);
+    Ok(0)
+}
+
+fn list_plugins() -> i32 {
+    let registry = Registry::lock();
+    if registry.plugins().is_empty() {
+        println!("No plugins loaded");
+        println!("Plugin search paths:");
+        for path in bp3d_build::plugin::search_paths() {
+            println!("    {}", path.display());
+        }
+        return 0;
+    }
+    for plugin in registry.plugins() {
+        println!(
+            "{} {} ({})",
+            plugin.name,
+            plugin.version,
+            plugin.path.display()
+        );
+        for name in &plugin.build_systems {
+            println!("    build system: {}", name);
+        }
+        for name in &plugin.packagers {
+            println!("    packager: {}", name);
+        }
+    }
+    0
+}
+
+fn write_lua_stubs(other_args: Option<Vec<String>>) -> i32 {
+    let Some(dir) = other_args.as_ref().and_then(|v| v.first()) else {
+        eprintln!("Usage: bp3d lua-stubs <dir>");
+        return 1;
+    };
+    match bp3d_build::lua::stubs::write(Path::new(dir)) {
+        Ok(files) => {
+            for file in files {
+                println!("Wrote {}", file.display());
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("Failed to write Lua stubs: {}", e);
+            1
+        }
+    }
+}
+
+fn clean(
+    tool: &dyn core::BuildTool,
+    ctx: &bp3d_build::system::Context,
+    targets: &[&str],
+    all: bool,
+    scope: Option<CleanScope>,
+) -> core::Result<i32> {
+    // Cleaning everything also removes all cargo builds, so it is never the default.
+    let scope = match scope {
+        Some(CleanScope::Dist) => bp3d_build::system::CleanScope::Dist,
+        Some(CleanScope::Target) => bp3d_build::system::CleanScope::Target,
+        Some(CleanScope::Config) => bp3d_build::system::CleanScope::Configuration,
+        Some(CleanScope::Ext) => bp3d_build::system::CleanScope::Ext,
+        None if all => bp3d_build::system::CleanScope::All,
+        None => bp3d_build::system::CleanScope::Dist,
+    };
+    info!("Cleaning {} for targets {:?}...", scope.name(), targets);
+    tool.clean(ctx, targets, scope)?;
+    Ok(0)
+}
+
+fn test(
+    tool: &dyn core::BuildTool,
+    ctx: &Context,
+    ctx2: &bp3d_build::system::Context,
+) -> core::Result<i32> {
+    info!("Configuring package for targets {:?}...", ctx.targets);
+    tool.configure(ctx2, ctx.targets)?;
+    let mut code = 0;
+    for target in ctx.targets {
+        info!("Testing package for target {}...", target);
+        let report = tool.test(ctx2, target)?;
+        let path = ctx.path.join("target").join(target).join(ctx.configuration);
+        let xml = report.to_junit(tool.package().get_primary_name());
+        std::fs::create_dir_all(&path)
+            .and_then(|_| std::fs::write(path.join("junit.xml"), xml))
+            .map_err(|e| Error::BuildSystem(e.to_string()))?;
+        println!(
+            "{}: {} passed, {} failed, {} ignored",
+            target,
+            report.count(TestStatus::Passed),
+            report.count(TestStatus::Failed),
+            report.count(TestStatus::Ignored)
+        );
+        if !report.is_success() {
+            code = 1;
+        }
+    }
+    Ok(code)
+}
+
+pub(crate) fn run_command(
     tool: &dyn core::BuildTool,
     ctx: Context,
     cmd: Command,
@@ -97,10 +271,17 @@ fn run_command(
                     packager: &packager_name,
                 };
                 let packager = PackagerType::from_name(&packager_name);
-                match packager {
-                    Some(packager) => packager.call(&ctx),
-                    None => run_packager::<Lua>(&ctx),
-                }
+                let is_plugin = Registry::lock().find_packager(&packager_name).is_some();
+                report::phase(
+                    tool.package().get_primary_name(),
+                    "package",
+                    None,
+                    || match packager {
+                        Some(packager) => packager.call(&ctx),
+                        None if is_plugin => run_packager::<Plugin>(&ctx),
+                        None => run_packager::<Lua>(&ctx),
+                    },
+                );
                 Ok(0)
             } else {
                 eprintln!("Please specify a packager type to run the packaging process");
@@ -128,13 +309,9 @@ fn run_command(
                 }
                 true => bp3d_script::lua::Lua::new(&ctx, &name, &[]),
             }
-            .map_err(|e| Error::ScriptSystem(e.to_string()))?;
-            let needs_configure = script
-                .needs_configure()
-                .map_err(|e| Error::ScriptSystem(e.to_string()))?;
-            let needs_build = script
-                .needs_build()
-                .map_err(|e| Error::ScriptSystem(e.to_string()))?;
+            .map_err(script_error)?;
+            let needs_configure = script.needs_configure().map_err(script_error)?;
+            let needs_build = script.needs_build().map_err(script_error)?;
             if needs_configure {
                 info!("Configuring package for targets {:?}...", ctx.targets);
                 tool.configure(&ctx2, ctx.targets)?;
@@ -146,10 +323,21 @@ fn run_command(
                 }
             }
             info!("Running script {}...", name);
-            script
-                .execute()
-                .map_err(|e| Error::ScriptSystem(e.to_string()))
+            report::phase(tool.package().get_primary_name(), "run", None, || {
+                script.execute()
+            })
+            .map_err(script_error)
         }
+        Command::Config => print_config(tool, ctx.path),
+        Command::Plugins => Ok(list_plugins()),
+        Command::Clean => clean(tool, &ctx2, ctx.targets, ctx.clean_all, ctx.clean_scope),
+        Command::Info => crate::info::print_info(tool, &ctx),
+        Command::Test => test(tool, &ctx, &ctx2),
+        Command::Exec => Ok(crate::exec::run_exec(&ctx, other_args)),
+        Command::Doctor => Ok(crate::doctor::run_doctor(&ctx, packager.as_deref())),
+        Command::Report => Ok(crate::report::run_report(&ctx)),
+        Command::LuaStubs => Ok(write_lua_stubs(other_args)),
+        Command::TestLua => Ok(crate::test_lua::run_test_lua(&ctx, other_args)),
     }
 }
 
@@ -159,6 +347,29 @@ pub fn dispatch_run(
     packager: Option<String>,
     other_args: Option<Vec<String>>,
 ) -> i32 {
-    let tool = expect_return!(core::open(&ctx.path) => ("Failed to load package", 1));
-    expect_return!(run_command(&*tool, ctx, cmd, packager, other_args) => ("Failed to run build", 2))
+    // These commands must not require a valid project.
+    match cmd {
+        Command::Plugins => return list_plugins(),
+        Command::Doctor => return crate::doctor::run_doctor(&ctx, packager.as_deref()),
+        Command::Report => return crate::report::run_report(&ctx),
+        Command::LuaStubs => return write_lua_stubs(other_args),
+        Command::TestLua => return crate::test_lua::run_test_lua(&ctx, other_args),
+        _ => (),
+    }
+    let tool = match core::open(ctx.path) {
+        Ok(v) => v,
+        Err(e) => {
+            print_error(&ctx, "Failed to load package", &e);
+            return 1;
+        }
+    };
+    let res = run_command(&*tool, ctx, cmd, packager, other_args);
+    crate::report::save_report(ctx.path, cmd);
+    match res {
+        Ok(code) => code,
+        Err(e) => {
+            print_error(&ctx, "Failed to run build", &e);
+            2
+        }
+    }
 }
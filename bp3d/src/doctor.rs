@@ -0,0 +1,139 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::core::Context;
+use bp3d_build::lua::core::Vm;
+use bp3d_build::plugin::Registry;
+use bp3d_build::tools::{Requirement, Status, check};
+use std::path::Path;
+
+fn describe(requirement: &Requirement) -> String {
+    match &requirement.version {
+        Some(version) => format!("{} >= {}", requirement.name, version),
+        None => requirement.name.clone(),
+    }
+}
+
+/// Checks all requirements of a class, returns the number of problems found.
+fn check_class(vm: &Vm, kind: &str, name: &str, path: &Path, targets: &[&str]) -> usize {
+    println!("{} {}:", kind, name);
+    let requirements = match vm.requirements(path) {
+        Ok(v) => v,
+        Err(e) => {
+            println!("    failed to load: {}", e);
+            return 1;
+        }
+    };
+    let mut problems = 0;
+    for requirement in requirements.iter().filter(|v| v.applies_to(targets)) {
+        let status = check(requirement);
+        if !status.is_ok() {
+            problems += 1;
+        }
+        match status {
+            Status::Found { path, version } => match version {
+                Some(version) => println!(
+                    "    ok: {} {} ({})",
+                    requirement.name,
+                    version,
+                    path.display()
+                ),
+                None => println!("    ok: {} ({})", requirement.name, path.display()),
+            },
+            Status::Missing => println!("    missing: {}", describe(requirement)),
+            Status::Outdated { path, version } => println!(
+                "    outdated: {} {} ({}), requires {}",
+                requirement.name,
+                version,
+                path.display(),
+                describe(requirement)
+            ),
+            Status::UnknownVersion { path } => println!(
+                "    unknown version: {} ({}), requires {}",
+                requirement.name,
+                path.display(),
+                describe(requirement)
+            ),
+            Status::MissingLibrary { path, library } => println!(
+                "    missing library: {} for {} ({})",
+                library,
+                requirement.name,
+                path.display()
+            ),
+        }
+    }
+    problems
+}
+
+pub fn run_doctor(ctx: &Context, packager: Option<&str>) -> i32 {
+    let paths = match bp3d_build::lua::paths::resolve(ctx.path) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to resolve Lua search paths: {}", e);
+            return 1;
+        }
+    };
+    let vm = match Vm::new(ctx.path, paths) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to initialize Lua engine: {}", e);
+            return 1;
+        }
+    };
+    let mut problems = 0;
+    match packager {
+        Some(name) => {
+            if Registry::lock().find_packager(name).is_some() {
+                println!(
+                    "Packager {} is provided by a plugin, nothing to check",
+                    name
+                );
+                return 0;
+            }
+            let Some(path) = vm.find(&format!("package/{}.lua", name)) else {
+                eprintln!("Packager {} not found", name);
+                return 1;
+            };
+            problems += check_class(&vm, "Packager", name, &path, ctx.targets);
+        }
+        None => {
+            for (name, path) in vm.list("package") {
+                problems += check_class(&vm, "Packager", &name, &path, ctx.targets);
+            }
+            for (name, path) in vm.list("script") {
+                problems += check_class(&vm, "Script", &name, &path, ctx.targets);
+            }
+        }
+    }
+    if problems > 0 {
+        eprintln!("{} problem(s) found", problems);
+        return 1;
+    }
+    println!("All required tools are available");
+    0
+}
@@ -0,0 +1,148 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::core::Context;
+use bp3d_build::system::artifact::Artifact;
+use bp3d_debug::{debug, info, warning};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[cfg(target_vendor = "apple")]
+const LOADER_PATH: &str = "DYLD_LIBRARY_PATH";
+#[cfg(windows)]
+const LOADER_PATH: &str = "PATH";
+#[cfg(not(any(target_vendor = "apple", windows)))]
+const LOADER_PATH: &str = "LD_LIBRARY_PATH";
+
+/// Prepends the given directories to `current`, the value of the dynamic loader search path.
+fn loader_path(dirs: &[&Path], current: Option<OsString>) -> Option<OsString> {
+    let mut paths: Vec<PathBuf> = dirs.iter().map(|v| v.to_path_buf()).collect();
+    if let Some(var) = current {
+        paths.extend(std::env::split_paths(&var));
+    }
+    std::env::join_paths(paths).ok()
+}
+
+pub fn run_exec(ctx: &Context, other_args: Option<Vec<String>>) -> i32 {
+    let mut args = other_args.unwrap_or_default();
+    if args.is_empty() {
+        eprintln!("Please specify a binary name to execute");
+        return 1;
+    }
+    let name = args.remove(0);
+    if ctx.targets.len() > 1 {
+        warning!("Multiple targets specified, using {}", ctx.targets[0]);
+    }
+    let target = ctx.targets[0];
+    let root = match ctx.path.canonicalize() {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to resolve project path: {}", e);
+            return 1;
+        }
+    };
+    let target_dir = root.join("target").join(target);
+    let artifact_dir = target_dir.join(ctx.configuration);
+    let Some(bin) = Artifact::find_bin(&artifact_dir, &name, false) else {
+        eprintln!(
+            "Binary {} not found in {}, did you run bp3d build?",
+            name,
+            artifact_dir.display()
+        );
+        return 1;
+    };
+    let ext_lib = target_dir.join("ext").join("lib");
+    let res = root.join("res");
+    let config = res.join("config");
+    let mut cmd = Command::new(bin.path());
+    cmd.args(&args)
+        .env("BP3D_ROOT", &root)
+        .env("BP3D_TARGET", target)
+        .env("BP3D_CONFIGURATION", ctx.configuration);
+    if res.is_dir() {
+        cmd.env("BP3D_RESOURCES_PATH", &res);
+    }
+    if config.is_dir() {
+        cmd.env("BP3D_CONFIG_PATH", &config);
+    }
+    match loader_path(&[&artifact_dir, &ext_lib], std::env::var_os(LOADER_PATH)) {
+        Some(path) => {
+            debug!("{}={:?}", LOADER_PATH, path);
+            cmd.env(LOADER_PATH, path);
+        }
+        None => warning!("Unable to set {}: invalid path", LOADER_PATH),
+    }
+    info!("Executing {}...", bin.path().display());
+    match cmd.status() {
+        Ok(status) => status.code().unwrap_or_else(|| {
+            eprintln!("{} terminated by signal", name);
+            1
+        }),
+        Err(e) => {
+            eprintln!("Failed to execute {}: {}", name, e);
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loader_path_empty() {
+        let path = loader_path(&[Path::new("/p/lib"), Path::new("/p/ext/lib")], None).unwrap();
+        let paths: Vec<PathBuf> = std::env::split_paths(&path).collect();
+        assert_eq!(paths, [Path::new("/p/lib"), Path::new("/p/ext/lib")]);
+    }
+
+    #[test]
+    fn loader_path_prepend() {
+        let current = std::env::join_paths(["/usr/lib", "/opt/lib"]).unwrap();
+        let path = loader_path(&[Path::new("/p/lib")], Some(current)).unwrap();
+        let paths: Vec<PathBuf> = std::env::split_paths(&path).collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("/p/lib"),
+                Path::new("/usr/lib"),
+                Path::new("/opt/lib")
+            ]
+        );
+    }
+
+    #[test]
+    fn loader_path_invalid() {
+        #[cfg(windows)]
+        let dir = Path::new("C:\\my\"lib");
+        #[cfg(not(windows))]
+        let dir = Path::new("/my:lib");
+        assert!(loader_path(&[dir], None).is_none());
+    }
+}
@@ -0,0 +1,288 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::core::Context;
+use bp3d_build::core::{BuildTool, Error, Result};
+use bp3d_build::lua::core::{ArgType, Vm};
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(Serialize)]
+struct ArgInfo {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+    optional: bool,
+    #[serde(rename = "enum", skip_serializing_if = "Vec::is_empty")]
+    variants: Vec<String>,
+}
+
+impl From<ArgType> for ArgInfo {
+    fn from(value: ArgType) -> Self {
+        Self {
+            name: value.name,
+            ty: value.ty,
+            optional: value.optional,
+            variants: value.variants,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClassInfo {
+    name: String,
+    path: PathBuf,
+    arg_types: Vec<ArgInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ComponentInfo {
+    short_name: String,
+    name: String,
+    version: String,
+    description: Option<String>,
+    public: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Info {
+    build_system: String,
+    name: String,
+    version: String,
+    manifest: Option<PathBuf>,
+    components: Vec<ComponentInfo>,
+    targets: Vec<String>,
+    configurations: Vec<String>,
+    features: Vec<String>,
+    packagers: Vec<ClassInfo>,
+    scripts: Vec<ClassInfo>,
+}
+
+fn load_classes(vm: &Vm, folder: &str) -> Vec<ClassInfo> {
+    vm.list(folder)
+        .into_iter()
+        .map(|(name, path)| {
+            let (arg_types, error) = match vm.arg_types(&path) {
+                Ok(v) => (v.into_iter().map(ArgInfo::from).collect(), None),
+                Err(e) => (Vec::new(), Some(e.to_string())),
+            };
+            ClassInfo {
+                name,
+                path,
+                arg_types,
+                error,
+            }
+        })
+        .collect()
+}
+
+fn collect(tool: &dyn BuildTool, ctx: &Context) -> Result<Info> {
+    let package = tool.package();
+    let to_vec = |list: &[std::borrow::Cow<str>]| list.iter().map(|v| v.to_string()).collect();
+    let components = (0..package.get_components())
+        .map(|i| {
+            let component = package.get_component(i);
+            ComponentInfo {
+                short_name: component.get_short_name().into(),
+                name: component.get_name().into(),
+                version: component.get_version().into(),
+                description: component.get_description().map(String::from),
+                public: component.is_public(),
+            }
+        })
+        .collect();
+    let manifest = ctx.path.join(bp3d_config::MANIFEST_NAME);
+    let paths = bp3d_build::lua::paths::resolve(ctx.path).map_err(Error::LuaPath)?;
+    let vm = Vm::new(ctx.path, paths).map_err(|e| Error::ScriptSystem(e.to_string()))?;
+    Ok(Info {
+        build_system: tool.name().into(),
+        name: package.get_primary_name().into(),
+        version: package.get_primary_version().into(),
+        manifest: manifest.exists().then_some(manifest),
+        components,
+        targets: to_vec(package.targets()),
+        configurations: to_vec(package.configurations()),
+        features: to_vec(package.features()),
+        packagers: load_classes(&vm, "package"),
+        scripts: load_classes(&vm, "script"),
+    })
+}
+
+fn print_classes(title: &str, classes: &[ClassInfo]) {
+    println!("{}:", title);
+    for class in classes {
+        println!("    {} ({})", class.name, class.path.display());
+        if let Some(error) = &class.error {
+            println!("        failed to load: {}", error);
+        }
+        for arg in &class.arg_types {
+            let mut ty = arg.ty.clone();
+            if !arg.variants.is_empty() {
+                ty = format!("{} [{}]", ty, arg.variants.join(", "));
+            }
+            match arg.optional {
+                true => println!("        {}: {} (optional)", arg.name, ty),
+                false => println!("        {}: {}", arg.name, ty),
+            }
+        }
+    }
+}
+
+pub fn print_info(tool: &dyn BuildTool, ctx: &Context) -> Result<i32> {
+    let info = collect(tool, ctx)?;
+    if ctx.json {
+        let text = serde_json::to_string_pretty(&info).expect("info should always serialize");
+        println!("{}", text);
+        return Ok(0);
+    }
+    println!("Build system: {}", info.build_system);
+    println!("Package: {} {}", info.name, info.version);
+    match &info.manifest {
+        Some(path) => println!("Manifest: {}", path.display()),
+        None => println!("Manifest: none"),
+    }
+    if !info.components.is_empty() {
+        println!("Components:");
+        for component in &info.components {
+            let visibility = match component.public {
+                true => "public",
+                false => "private",
+            };
+            print!(
+                "    {}: {} {} ({})",
+                component.short_name, component.name, component.version, visibility
+            );
+            match &component.description {
+                Some(description) => println!(" - {}", description),
+                None => println!(),
+            }
+        }
+    }
+    println!("Targets: {}", info.targets.join(", "));
+    println!("Configurations: {}", info.configurations.join(", "));
+    println!("Features: {}", info.features.join(", "));
+    print_classes("Packagers", &info.packagers);
+    print_classes("Scripts", &info.scripts);
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn info() -> Info {
+        Info {
+            build_system: "Cargo".into(),
+            name: "bp3d-test".into(),
+            version: "1.0.0".into(),
+            manifest: None,
+            components: vec![ComponentInfo {
+                short_name: "core".into(),
+                name: "bp3d-test-core".into(),
+                version: "1.0.0".into(),
+                description: None,
+                public: true,
+            }],
+            targets: vec!["x86_64-unknown-linux-gnu".into()],
+            configurations: vec!["debug".into(), "release".into()],
+            features: vec!["core/logging".into()],
+            packagers: vec![ClassInfo {
+                name: "zip".into(),
+                path: PathBuf::from("package/zip.lua"),
+                arg_types: vec![
+                    ArgInfo {
+                        name: "level".into(),
+                        ty: "number".into(),
+                        optional: true,
+                        variants: Vec::new(),
+                    },
+                    ArgInfo {
+                        name: "format".into(),
+                        ty: "string".into(),
+                        optional: false,
+                        variants: vec!["zip".into(), "tar".into()],
+                    },
+                ],
+                error: None,
+            }],
+            scripts: vec![ClassInfo {
+                name: "broken".into(),
+                path: PathBuf::from("script/broken.lua"),
+                arg_types: Vec::new(),
+                error: Some("syntax error".into()),
+            }],
+        }
+    }
+
+    #[test]
+    fn json_output() {
+        let value = serde_json::to_value(info()).unwrap();
+        let expected = json!({
+            "buildSystem": "Cargo",
+            "name": "bp3d-test",
+            "version": "1.0.0",
+            "manifest": null,
+            "components": [{
+                "shortName": "core",
+                "name": "bp3d-test-core",
+                "version": "1.0.0",
+                "description": null,
+                "public": true
+            }],
+            "targets": ["x86_64-unknown-linux-gnu"],
+            "configurations": ["debug", "release"],
+            "features": ["core/logging"],
+            "packagers": [{
+                "name": "zip",
+                "path": "package/zip.lua",
+                "argTypes": [
+                    { "name": "level", "type": "number", "optional": true },
+                    {
+                        "name": "format",
+                        "type": "string",
+                        "optional": false,
+                        "enum": ["zip", "tar"]
+                    }
+                ]
+            }],
+            "scripts": [{
+                "name": "broken",
+                "path": "script/broken.lua",
+                "argTypes": [],
+                "error": "syntax error"
+            }]
+        });
+        assert_eq!(value, expected);
+    }
+}
@@ -28,19 +28,36 @@
 
 use crate::args::Args;
 use crate::core::{Context, dispatch_run};
+use bp3d_build::plugin::Registry;
 use bp3d_build::system::Features;
+use bp3d_build::system::target::Target;
 use bp3d_os::module::loader::ModuleLoader;
 use clap::Parser;
-use current_platform::CURRENT_PLATFORM;
 use std::path::Path;
 
 mod args;
 mod core;
+mod doctor;
+mod exec;
+mod info;
+mod report;
+mod test_lua;
+mod watch;
 
 fn main() {
     let mut args = Args::parse();
     if args.targets.is_empty() {
-        args.targets.push(CURRENT_PLATFORM.into());
+        // The alias is resolved to the host triple below.
+        args.targets.push("host".into());
+    }
+    for target in &mut args.targets {
+        match Target::parse(target) {
+            Ok(v) => *target = v.name().into(),
+            Err(e) => {
+                eprintln!("Invalid target {}: {}", target, e);
+                std::process::exit(1);
+            }
+        }
     }
     let features: Vec<&str> = args.features.iter().map(|v| &**v).collect();
     let targets: Vec<&str> = args.targets.iter().map(|v| &**v).collect();
@@ -54,9 +71,17 @@ fn main() {
         } else {
             Features::List(&features)
         },
+        json: args.json,
+        compare: args.compare.as_deref(),
+        clean_all: args.clean_all,
+        clean_scope: args.clean_scope,
     };
     ModuleLoader::install(&[]);
-    let code = dispatch_run(ctx, args.cmd, args.package_type, args.other_args);
+    Registry::lock().load_default();
+    let code = match args.watch {
+        true => watch::run_watch(ctx, args.cmd, args.package_type, args.other_args),
+        false => dispatch_run(ctx, args.cmd, args.package_type, args.other_args),
+    };
     ModuleLoader::uninstall();
     std::process::exit(code);
 }
@@ -0,0 +1,215 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::args::Command;
+use crate::core::Context;
+use bp3d_build::report::{Delta, REPORT_NAME, Report, take};
+use bp3d_debug::{info, warning};
+use std::collections::HashMap;
+use std::path::Path;
+
+fn format_size(size: f64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB"];
+    let mut size = size;
+    let mut unit = 0;
+    while size.abs() >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.2} {}", size, UNITS[unit])
+}
+
+fn format_time(time: f64) -> String {
+    format!("{:.2}s", time)
+}
+
+/// Saves the report recorded while running a command to the project target directory.
+///
+/// Only build and package commands write a report, so that running tests or scripts afterwards
+/// does not replace the report of the last build.
+pub fn save_report(path: &Path, cmd: Command) {
+    let report = take();
+    if report.is_empty() || !matches!(cmd, Command::Build | Command::PrePackage | Command::Package)
+    {
+        return;
+    }
+    let path = path.join("target").join(REPORT_NAME);
+    match report.save(&path) {
+        Ok(()) => info!("Build report written to {:?}", path),
+        Err(e) => warning!("Failed to write build report: {}", e),
+    }
+}
+
+fn print_deltas(title: &str, deltas: &[Delta], format: fn(f64) -> String) {
+    println!("{}:", title);
+    for delta in deltas {
+        match (delta.old, delta.new) {
+            (Some(old), Some(new)) => {
+                let percent = delta
+                    .percent()
+                    .map(|v| format!(" ({:+.1}%)", v))
+                    .unwrap_or_default();
+                println!(
+                    "    {}: {} -> {}{}",
+                    delta.key,
+                    format(old),
+                    format(new),
+                    percent
+                );
+            }
+            (None, Some(new)) => println!("    {}: new {}", delta.key, format(new)),
+            (Some(old), None) => println!("    {}: removed (was {})", delta.key, format(old)),
+            (None, None) => (),
+        }
+    }
+}
+
+/// Checks relative budgets, returns the number of violations.
+fn check_increase(deltas: &[Delta], max: Option<f64>, what: &str) -> usize {
+    let Some(max) = max else {
+        return 0;
+    };
+    let mut violations = 0;
+    for delta in deltas {
+        if let Some(percent) = delta.percent().filter(|v| *v > max) {
+            eprintln!(
+                "Budget exceeded: {} of {} increased by {:.1}% (max {:.1}%)",
+                what, delta.key, percent, max
+            );
+            violations += 1;
+        }
+    }
+    violations
+}
+
+/// Returns the budget of `name` on `target`, a `<target>/<name>` key takes precedence over `name`.
+fn budget<T: Copy>(budgets: &HashMap<String, T>, target: Option<&str>, name: &str) -> Option<T> {
+    target
+        .and_then(|v| budgets.get(&format!("{}/{}", v, name)))
+        .or_else(|| budgets.get(name))
+        .copied()
+}
+
+/// Checks absolute budgets, returns the number of violations.
+fn check_budgets(report: &Report, budgets: &bp3d_config::Report) -> usize {
+    let mut violations = 0;
+    if let Some(sizes) = &budgets.sizes {
+        for artifact in &report.artifacts {
+            let max = budget(sizes, Some(&artifact.target), &artifact.name);
+            if let Some(max) = max.filter(|v| artifact.size > *v) {
+                eprintln!(
+                    "Budget exceeded: size of {} ({}) is {} (max {})",
+                    artifact.name,
+                    artifact.target,
+                    format_size(artifact.size as f64),
+                    format_size(max as f64)
+                );
+                violations += 1;
+            }
+        }
+    }
+    if let Some(times) = &budgets.times {
+        for phase in &report.phases {
+            let max = budget(times, phase.target.as_deref(), &phase.name);
+            if let Some(max) = max.filter(|v| phase.duration > *v) {
+                eprintln!(
+                    "Budget exceeded: {} of {} took {} (max {})",
+                    phase.name,
+                    phase.package,
+                    format_time(phase.duration),
+                    format_time(max)
+                );
+                violations += 1;
+            }
+        }
+    }
+    violations
+}
+
+pub fn run_report(ctx: &Context) -> i32 {
+    let path = ctx.path.join("target").join(REPORT_NAME);
+    let report = match Report::load(&path) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to load build report {}: {}", path.display(), e);
+            return 1;
+        }
+    };
+    let budgets = match bp3d_config::load(ctx.path) {
+        Ok(v) => v.and_then(|v| v.into_manifest().report),
+        Err(e) => {
+            eprintln!("Failed to load manifest: {}", e);
+            return 1;
+        }
+    };
+    let mut violations = 0;
+    match ctx.compare {
+        Some(compare) => {
+            let old = match Report::load(compare) {
+                Ok(v) => v,
+                Err(e) => {
+                    eprintln!("Failed to load build report {}: {}", compare.display(), e);
+                    return 1;
+                }
+            };
+            let times = report.compare_times(&old);
+            let sizes = report.compare_sizes(&old);
+            print_deltas("Phases", &times, format_time);
+            print_deltas("Artifacts", &sizes, format_size);
+            if let Some(budgets) = &budgets {
+                violations += check_increase(&times, budgets.max_time_increase, "duration");
+                violations += check_increase(&sizes, budgets.max_size_increase, "size");
+            }
+        }
+        None if ctx.json => match serde_json::to_string_pretty(&report) {
+            Ok(v) => println!("{}", v),
+            Err(e) => {
+                eprintln!("Failed to serialize build report: {}", e);
+                return 1;
+            }
+        },
+        None => {
+            println!("Phases:");
+            for (key, time) in report.phase_times() {
+                println!("    {}: {}", key, format_time(time));
+            }
+            println!("Artifacts:");
+            for (key, size) in report.artifact_sizes() {
+                println!("    {}: {}", key, format_size(size as f64));
+            }
+        }
+    }
+    if let Some(budgets) = &budgets {
+        violations += check_budgets(&report, budgets);
+    }
+    if violations > 0 {
+        eprintln!("{} budget(s) exceeded", violations);
+        return 1;
+    }
+    0
+}
@@ -0,0 +1,140 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::core::Context;
+use bp3d_build::lua::error::LuaError;
+use bp3d_build::lua::test::{FileResult, discover, run_file};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Serialize)]
+struct CaseSummary<'a> {
+    name: &'a str,
+    assertions: usize,
+    error: Option<&'a LuaError>,
+}
+
+#[derive(Serialize)]
+struct FileSummary<'a> {
+    path: &'a Path,
+    cases: Vec<CaseSummary<'a>>,
+    error: Option<&'a LuaError>,
+    cleanup: Option<String>,
+}
+
+fn print_json(results: &[FileResult]) {
+    let files: Vec<FileSummary> = results
+        .iter()
+        .map(|file| FileSummary {
+            path: &file.path,
+            cases: file
+                .cases
+                .iter()
+                .map(|v| CaseSummary {
+                    name: &v.name,
+                    assertions: v.assertions,
+                    error: v.error.as_ref(),
+                })
+                .collect(),
+            error: file.error.as_ref(),
+            cleanup: file.cleanup.as_ref().map(|e| e.to_string()),
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&files).unwrap());
+}
+
+fn print_file(root: &Path, file: &FileResult) {
+    let path = file.path.strip_prefix(root).unwrap_or(&file.path);
+    println!("{}:", path.display());
+    if let Some(e) = &file.error {
+        println!("    failed to load:\n{}", e);
+    }
+    for case in &file.cases {
+        match &case.error {
+            Some(e) => println!(
+                "    FAILED {} ({} assertion(s))\n{}",
+                case.name, case.assertions, e
+            ),
+            None => println!("    ok {} ({} assertion(s))", case.name, case.assertions),
+        }
+    }
+    if let Some(e) = &file.cleanup {
+        println!("    failed to remove the temporary directory: {}", e);
+    }
+}
+
+/// Runs the Lua tests found in the given paths, or in the project when none is given.
+pub fn run_test_lua(ctx: &Context, other_args: Option<Vec<String>>) -> i32 {
+    let paths: Vec<PathBuf> = match other_args {
+        Some(args) if !args.is_empty() => args.into_iter().map(PathBuf::from).collect(),
+        _ => vec![ctx.path.into()],
+    };
+    let mut files = Vec::new();
+    for path in &paths {
+        match discover(path) {
+            Ok(v) => files.extend(v),
+            Err(e) => {
+                eprintln!("Failed to list tests in {}: {}", path.display(), e);
+                return 1;
+            }
+        }
+    }
+    let mut results = Vec::new();
+    for file in &files {
+        match run_file(ctx.path, file) {
+            Ok(v) => {
+                if !ctx.json {
+                    print_file(ctx.path, &v);
+                }
+                results.push(v);
+            }
+            Err(e) => {
+                eprintln!("Failed to run {}: {}", file.display(), e);
+                return 1;
+            }
+        }
+    }
+    if ctx.json {
+        print_json(&results);
+    }
+    let cases: usize = results.iter().map(|v| v.cases.len()).sum();
+    let assertions: usize = results
+        .iter()
+        .flat_map(|v| &v.cases)
+        .map(|v| v.assertions)
+        .sum();
+    let failures: usize = results.iter().map(|v| v.failures()).sum();
+    eprintln!(
+        "{} file(s), {} case(s), {} assertion(s), {} failure(s)",
+        files.len(),
+        cases,
+        assertions,
+        failures
+    );
+    if failures > 0 { 1 } else { 0 }
+}
@@ -0,0 +1,182 @@
+// Copyright (c) 2026, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::args::Command;
+use crate::core::{Context, print_error, run_command};
+use bp3d_build::core;
+use bp3d_debug::{debug, info, warning};
+use notify_debouncer_mini::notify::RecursiveMode;
+use notify_debouncer_mini::{DebounceEventResult, new_debouncer};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, channel};
+use std::time::Duration;
+
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Files which require re-opening the project (and therefore a new Lua VM) when changed.
+const PROJECT_FILES: &[&str] = &["build.lua", "bp3d.toml", "Cargo.toml"];
+
+/// Returns the list of paths to watch and whether they must be watched recursively.
+///
+/// All top-level directories of the project except the target directory and hidden directories
+/// are watched recursively; this includes sources and the project `bp3d-build` Lua directory.
+fn watch_paths(root: &Path) -> std::io::Result<Vec<(PathBuf, RecursiveMode)>> {
+    let mut paths = vec![(root.to_path_buf(), RecursiveMode::NonRecursive)];
+    for entry in std::fs::read_dir(root)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() || is_ignored(root, &entry.path()) {
+            continue;
+        }
+        paths.push((entry.path(), RecursiveMode::Recursive));
+    }
+    Ok(paths)
+}
+
+fn is_ignored(root: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return true;
+    };
+    relative.starts_with("target")
+        || relative.components().any(|v| {
+            v.as_os_str()
+                .to_str()
+                .map(|v| v.starts_with('.'))
+                .unwrap_or_default()
+        })
+}
+
+fn needs_reload(root: &Path, path: &Path) -> bool {
+    path.extension().map(|v| v == "lua").unwrap_or_default()
+        || PROJECT_FILES.iter().any(|v| path == root.join(v))
+}
+
+/// Waits for relevant changes, returns Some(true) if the project must be re-opened and None if
+/// the watcher was shut down.
+fn wait_changes(rx: &Receiver<DebounceEventResult>, root: &Path) -> Option<bool> {
+    loop {
+        let events = match rx.recv().ok()? {
+            Ok(events) => events,
+            Err(e) => {
+                warning!("File watcher error: {}", e);
+                continue;
+            }
+        };
+        let changed: Vec<&Path> = events
+            .iter()
+            .map(|v| &*v.path)
+            // Directory events only signal that their content changed, which is reported separately.
+            .filter(|v| !v.is_dir() && !is_ignored(root, v))
+            .collect();
+        if changed.is_empty() {
+            continue;
+        }
+        debug!("Changed files: {:?}", changed);
+        return Some(changed.iter().any(|v| needs_reload(root, v)));
+    }
+}
+
+fn run_once(
+    tool: &mut Option<Box<dyn core::BuildTool>>,
+    ctx: Context,
+    cmd: Command,
+    packager: Option<String>,
+    other_args: Option<Vec<String>>,
+) {
+    bp3d_build::report::reset();
+    if tool.is_none() {
+        match core::open(ctx.path) {
+            Ok(v) => *tool = Some(v),
+            Err(e) => {
+                print_error(&ctx, "Failed to load package", &e);
+                return;
+            }
+        }
+    }
+    let Some(tool) = tool else { return };
+    let res = run_command(&**tool, ctx, cmd, packager, other_args);
+    crate::report::save_report(ctx.path, cmd);
+    match res {
+        Ok(0) => info!("Command {:?} succeeded", cmd),
+        Ok(code) => eprintln!("Command {:?} exited with code {}", cmd, code),
+        Err(e) => print_error(&ctx, "Failed to run build", &e),
+    }
+}
+
+pub fn run_watch(
+    ctx: Context,
+    cmd: Command,
+    packager: Option<String>,
+    other_args: Option<Vec<String>>,
+) -> i32 {
+    if !matches!(cmd, Command::Build | Command::PrePackage | Command::Run) {
+        eprintln!("--watch is only supported by build, pre-package and run");
+        return 1;
+    }
+    let root = match ctx.path.canonicalize() {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to resolve project path: {}", e);
+            return 1;
+        }
+    };
+    let (tx, rx) = channel();
+    let mut debouncer = match new_debouncer(DEBOUNCE_TIMEOUT, tx) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to create file watcher: {}", e);
+            return 1;
+        }
+    };
+    let paths = match watch_paths(&root) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to list project directories: {}", e);
+            return 1;
+        }
+    };
+    for (path, mode) in paths {
+        debug!("Watching {:?}", path);
+        if let Err(e) = debouncer.watcher().watch(&path, mode) {
+            eprintln!("Failed to watch {}: {}", path.display(), e);
+            return 1;
+        }
+    }
+    let mut tool = None;
+    loop {
+        run_once(&mut tool, ctx, cmd, packager.clone(), other_args.clone());
+        info!("Watching for changes in {}...", root.display());
+        match wait_changes(&rx, &root) {
+            Some(true) => {
+                info!("Project files changed, reloading project...");
+                tool = None;
+            }
+            Some(false) => info!("Sources changed, re-running..."),
+            None => return 0,
+        }
+    }
+}
@@ -26,158 +26,5 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use bp3d_util::simple_error;
-use serde::Deserialize;
-use std::collections::HashMap;
-use std::path::Path;
-
-#[derive(Deserialize, Debug)]
-#[serde(untagged)]
-pub enum ParamValue {
-    String(String),
-    Integer(i64),
-    Float(f64),
-    Boolean(bool),
-}
-
-impl ParamValue {
-    pub fn as_str(&self) -> Option<&str> {
-        match self {
-            ParamValue::String(s) => Some(s.as_str()),
-            _ => None,
-        }
-    }
-
-    pub fn as_number(&self) -> Option<f64> {
-        match self {
-            ParamValue::Integer(v) => Some(*v as _),
-            ParamValue::Float(v) => Some(*v),
-            _ => None,
-        }
-    }
-
-    pub fn as_integer(&self) -> Option<i64> {
-        match self {
-            ParamValue::Integer(v) => Some(*v),
-            ParamValue::Float(v) => Some(*v as _),
-            _ => None,
-        }
-    }
-
-    pub fn as_boolean(&self) -> Option<bool> {
-        match self {
-            ParamValue::Boolean(v) => Some(*v),
-            _ => None,
-        }
-    }
-
-    pub fn as_enum<T: Copy>(&self, possible_values: &[(&str, T)]) -> Option<T> {
-        let s = self.as_str()?;
-        for (key, val) in possible_values {
-            if *key == s {
-                return Some(*val);
-            }
-        }
-        None
-    }
-}
-
-pub type Parameters = HashMap<String, ParamValue>;
-
-#[derive(Deserialize)]
-pub struct Source {
-    pub url: String,
-    pub params: Parameters,
-}
-
-impl Source {
-    pub fn scheme(&self) -> Option<&str> {
-        let id = self.url.as_bytes().iter().position(|b| *b == b':')?;
-        Some(&self.url[..id])
-    }
-
-    pub fn path(&self) -> &str {
-        let id = self.url.as_bytes().iter().position(|b| *b == b':');
-        match id {
-            Some(id) => {
-                let bytes = &self.url[id + 1..];
-                if bytes.len() > 2 && bytes.as_bytes()[0] == b'/' && bytes.as_bytes()[1] == b'/' {
-                    &bytes[2..]
-                } else {
-                    bytes
-                }
-            }
-            None => &self.url,
-        }
-    }
-}
-
-#[derive(Deserialize)]
-pub struct SourceDep {
-    pub source: String,
-    pub version: String,
-}
-
-#[derive(Deserialize)]
-#[serde(untagged)]
-pub enum Dependency {
-    WithSource(SourceDep),
-    Version(String),
-}
-
-impl Dependency {
-    pub fn version(&self) -> &str {
-        match self {
-            Dependency::WithSource(v) => &v.version,
-            Dependency::Version(v) => v,
-        }
-    }
-
-    pub fn source(&self) -> Option<&str> {
-        match self {
-            Dependency::WithSource(v) => Some(&v.source),
-            Dependency::Version(_) => None,
-        }
-    }
-}
-
-#[derive(Deserialize, Default)]
-pub struct Config {
-    /// Represents the default package source for publishing new packages.
-    #[serde(rename = "default")]
-    pub default_source: Option<String>,
-
-    /// The list of dependencies to be installed.
-    pub dependencies: Option<HashMap<String, Dependency>>,
-
-    /// A declaration of all available package sources.
-    pub sources: Option<HashMap<String, Source>>,
-}
-
-simple_error! {
-    pub Error {
-        Toml(toml::de::Error) => "toml error: {}",
-        Io(std::io::Error) => "io error: {}"
-    }
-}
-
-#[derive(Deserialize)]
-pub struct ManifestExt {
-    pub fpkg: Config,
-}
-
-pub fn parse_config(project_root: &Path) -> Result<Option<Config>, Error> {
-    let path = project_root.join("bp3d.toml");
-    if path.exists() && path.is_file() {
-        let data = std::fs::read(path).map_err(Error::Io)?;
-        let config: ManifestExt = toml::from_slice(&data).map_err(Error::Toml)?;
-        return Ok(Some(config.fpkg));
-    }
-    Ok(None)
-}
-
-pub fn parse_standalone_config(path: &Path) -> Result<Config, Error> {
-    let data = std::fs::read(path).map_err(Error::Io)?;
-    let config: Config = toml::from_slice(&data).map_err(Error::Toml)?;
-    Ok(config)
-}
+pub use bp3d_config::Error;
+pub use bp3d_config::fpkg::{Config, ParamValue, Parameters};
@@ -26,10 +26,10 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::config::{Config, ParamValue, parse_config, parse_standalone_config};
+use crate::config::{Config, ParamValue};
 use crate::source::interface::{Dependency, Source};
 use crate::source::registry::get_provider;
-use bp3d_debug::{debug, trace};
+use bp3d_debug::{debug, trace, warning};
 use bp3d_util::simple_error;
 use bpx::core::Container;
 use bpx::package::util::unpack;
@@ -180,27 +180,26 @@ pub struct Project {
 
 impl Project {
     pub fn new(path: &Path) -> Result<Self, Error> {
-        let config = parse_config(path).map_err(Error::Config)?;
+        let config = bp3d_config::load(path).map_err(Error::Config)?;
+        if let Some(config) = &config {
+            for w in config.warnings() {
+                warning!("{}", w);
+            }
+        }
         Ok(Self {
-            config: config.unwrap_or_default(),
+            config: config
+                .and_then(|v| v.into_manifest().fpkg)
+                .unwrap_or_default(),
             sources: HashMap::new(),
             path: PathBuf::from(path),
         })
     }
 
-    pub fn add_config_if_exists(&mut self, path: &Path) -> Result<(), Error> {
-        if !path.exists() || !path.is_file() {
-            return Ok(());
+    pub fn add_default_configs(&mut self) -> Result<(), Error> {
+        let warnings = self.config.merge_defaults().map_err(Error::Config)?;
+        for w in warnings {
+            warning!("{}", w);
         }
-        debug!("adding config path: {:?}...", &path);
-        let config = parse_standalone_config(path).map_err(Error::Config)?;
-        if self.config.default_source.is_none() && config.default_source.is_some() {
-            self.config.default_source = config.default_source;
-        }
-        self.config
-            .sources
-            .get_or_insert_default()
-            .extend(config.sources.unwrap_or_default().into_iter());
         Ok(())
     }
 
@@ -58,4 +58,13 @@ function Builder:prePackage(ctx)
     return bp3d.build.List.new()
 end
 
+-- Runs the tests for ctx.target, returns nil or a list of suites:
+-- { { name = "suite", tests = { { name = "test", status = "passed", time = 0.1, message = nil } } } }
+-- status is one of "passed", "failed" or "ignored".
+function Builder:test(ctx) end
+
+-- Called by bp3d clean before the dist and ext folders are removed.
+-- scope is one of "dist", "target", "configuration", "ext" or "all".
+function Builder:clean(ctx, scope) end
+
 return Builder
@@ -45,7 +45,7 @@ function Dist:init2(ctx)
 end
 
 function Dist:buildTarget(ctx)
-    if bp3d.util.string.contains(ctx.target, "windows") then
+    if ctx.target:isWindows() then
         return self.windows:buildTarget(ctx)
     else
         return self.unix:buildTarget(ctx)
@@ -53,7 +53,7 @@ function Dist:buildTarget(ctx)
 end
 
 function Dist:packageTarget(ctx, artifacts)
-    if bp3d.util.string.contains(ctx.target, "windows") then
+    if ctx.target:isWindows() then
         return self.windows:packageTarget(ctx, artifacts)
     else
         return self.unix:packageTarget(ctx, artifacts)
@@ -48,7 +48,7 @@ function BaseDist.appendObjects(filteredExts, artifacts, path, f)
 end
 
 function BaseDist.getExtPath(ctx)
-    return ctx.path:join("target"):join(ctx.target):join("ext")
+    return ctx.path:join("target"):join(ctx.target:name()):join("ext")
 end
 
 function BaseDist.addExtUsr(ctx, artifacts)
@@ -75,39 +75,40 @@ function BaseDist.getDistPath(ctx)
 end
 
 function BaseDist.packUsr(ctx, artifacts)
+    artifacts = artifact.toList(artifacts)
     local distPath = BaseDist.getDistPath(ctx)
     local usrPath = distPath:join("usr")
     build.clean(distPath, usrPath)
 
     -- Package headers.
-    local headers = artifact.find(artifacts, "header")
-    if bp3d.util.table.count(headers) > 0 then
+    local headers = artifacts:find("header")
+    if headers:len() > 0 then
         print("Packaging headers...")
         local includeDir = usrPath:join("include")
         build.clean(includeDir)
-        for _, v in pairs(headers) do
+        for _, v in headers:iter() do
             artifact.copyTo(v, includeDir)
         end
     end
 
     -- Package configs.
-    local configs = artifact.find(artifacts, "config")
-    if bp3d.util.table.count(configs) > 0 then
+    local configs = artifacts:find("config")
+    if configs:len() > 0 then
         print("Packaging configs...")
         local etcDir = distPath:join("etc")
         build.clean(etcDir)
-        for _, v in pairs(configs) do
+        for _, v in configs:iter() do
             artifact.copyTo(v, etcDir)
         end
     end
 
     -- Package resources.
-    local resources = artifact.find(artifacts, "resource")
-    if bp3d.util.table.count(resources) > 0 then
+    local resources = artifacts:find("resource")
+    if resources:len() > 0 then
         print("Packaging resources...")
         local shareDir = usrPath:join("share")
         build.clean(shareDir)
-        for _, v in pairs(resources) do
+        for _, v in resources:iter() do
             artifact.copyTo(v, shareDir)
         end
     end
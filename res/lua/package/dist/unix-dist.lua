@@ -62,33 +62,34 @@ function UnixDist:buildTarget(ctx)
 end
 
 function UnixDist:packageTarget(ctx, artifacts)
+    artifacts = artifact.toList(artifacts)
     local targetPath = context.getTargetPath(ctx)
     local distPath = BaseDist.getDistPath(ctx)
 
     BaseDist.packUsr(ctx, artifacts)
 
     -- Package binarries.
-    local bins = artifact.find(artifacts, "bin")
-    if bp3d.util.table.count(bins) > 0 then
+    local bins = artifacts:find("bin")
+    if bins:len() > 0 then
         print("Packaging binarries...")
         local binDir = distPath:join("bin")
         build.clean(binDir)
-        for _, v in pairs(bins) do
+        for _, v in bins:iter() do
             artifact.copyTo(v, binDir)
         end
     end
 
     -- Package libraries.
     local libs = artifact.findDynamicLibraries(artifacts, targetPath)
-    local slibs = artifact.find(artifacts, "lib::static")
+    local slibs = artifacts:find("lib::static")
     local libDir = distPath:join("lib")
-    if bp3d.util.table.count(libs) > 0 or bp3d.util.table.count(slibs) > 0 then
+    if #libs > 0 or slibs:len() > 0 then
         print("Packaging libraries...")
         build.clean(libDir)
-        for _, v in pairs(libs) do
+        for _, v in ipairs(libs) do
             artifact.copyTo(v, libDir)
         end
-        for _, v in pairs(slibs) do
+        for _, v in slibs:iter() do
             artifact.copyTo(v, libDir)
         end
     end
@@ -62,33 +62,34 @@ function WindowsDist:buildTarget(ctx)
 end
 
 function WindowsDist:packageTarget(ctx, artifacts)
+    artifacts = artifact.toList(artifacts)
     local targetPath = context.getTargetPath(ctx)
     local distPath = BaseDist.getDistPath(ctx)
 
     BaseDist.packUsr(ctx, artifacts)
 
     -- Package binarries.
-    local bins = artifact.find(artifacts, "bin")
+    local bins = artifacts:find("bin")
     local libs = artifact.findDynamicLibraries(artifacts, targetPath)
-    if bp3d.util.table.count(bins) > 0 or bp3d.util.table.count(libs) > 0 then
+    if bins:len() > 0 or #libs > 0 then
         print("Packaging binarries...")
         local binDir = distPath:join("bin")
         build.clean(binDir)
-        for _, v in pairs(bins) do
+        for _, v in bins:iter() do
             artifact.copyTo(v, binDir)
         end
-        for _, v in pairs(libs) do
+        for _, v in ipairs(libs) do
             artifact.copyTo(v, binDir)
         end
     end
 
     -- Package libraries.
-    local libs = artifact.find(artifacts, "lib::static")
+    local slibs = artifacts:find("lib::static")
     local libDir = distPath:join("lib")
-    if bp3d.util.table.count(libs) > 0 then
+    if slibs:len() > 0 then
         print("Packaging libraries...")
         build.clean(libDir)
-        for _, v in pairs(libs) do
+        for _, v in slibs:iter() do
             artifact.copyTo(v, libDir)
         end
     end
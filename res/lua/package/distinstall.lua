@@ -33,22 +33,24 @@ local templates = require "bp3d.templates.distinstall"
 
 local DistInstall = Class(Dist)
 
+DistInstall.requires = {
+    bpxp = {},
+    rustc = { version = "1.56", libraries = { "installer" } }
+}
+
 function DistInstall:packageTarget(ctx, artifacts)
     Dist.packageTarget(self, ctx, artifacts)
     local name = self.context.package.name
     local version = self.context.package.version
     local targetPath = context.getTargetPath(ctx)
     print("Building BPX package...")
-    build.run("bpxp", { "-t", ctx.target, "-m", "Name=" .. name, "-m", "Version=" .. version, "-cf", "../dist.bpx", "." }, { workdir = targetPath:join("dist") })
+    build.run("bpxp", { "-t", ctx.target:name(), "-m", "Name=" .. name, "-m", "Version=" .. version, "-cf", "../dist.bpx", "." }, { workdir = targetPath:join("dist") })
     print("Generating installer...")
     bp3d.files.writeText(targetPath:join("installer.rs"), build.render(templates.INSTALLER_MAIN, {
         NAME = name,
         VERSION = version
     }))
-    local installerName = "install-" .. name .. "-" .. version .. "." .. ctx.target
-    if bp3d.util.string.contains(ctx.target, "windows") then
-        installerName = installerName .. ".exe"
-    end
+    local installerName = "install-" .. name .. "-" .. version .. "." .. ctx.target .. ctx.target:exeSuffix()
     local libPath = bp3d.build.files.getLibraryPath()
     build.run("rustc", { "-L", libPath, "-linstaller", "--edition=2021", "--crate-type", "bin", "installer.rs", "-O", "-o", installerName }, { workdir = targetPath })
     local dstInstaller = ctx.path:join("target"):join(installerName)
@@ -40,6 +40,14 @@ Framework.argTypes = {
     umbrella = { type = "string", optional = true }
 }
 
+Framework.requires = {
+    cargo = {},
+    install_name_tool = {},
+    lipo = {},
+    sw_vers = {},
+    xcrun = {}
+}
+
 function Framework:buildTarget(ctx)
     local files = baseBuild(ctx)
     print("Adding version information...")
@@ -52,13 +60,14 @@ function Framework:buildTarget(ctx)
 end
 
 function Framework:packageTarget(ctx, artifacts)
+    artifacts = artifact.toList(artifacts)
     print("Packaging " .. self.args.name .. "-" .. self.context.package.version .. " for target " .. ctx.target)
     local targetPath = context.getTargetPath(ctx)
     local frameworkDir = self.args.name .. ".framework"
     local binDir = nil
     local resDir = nil
     local moduleDir = nil
-    local isDarwin = bp3d.util.utf8.contains(ctx.target, "darwin")
+    local isDarwin = ctx.target:os() == "darwin"
     if isDarwin then
         binDir = frameworkDir .. "/Versions/A/"
         resDir = frameworkDir .. "/Versions/A/Resources"
@@ -77,7 +86,7 @@ function Framework:packageTarget(ctx, artifacts)
     print("Generating frameowrk " .. tostring(frameworkDir) .. "...")
     build.run("lipo", {
         "-create",
-        artifact.findFirst(artifacts, "lib::dynamic"):path(),
+        artifacts:findFirst("lib::dynamic"):path(),
         "-output",
         binDir:join(self.args.name)
     })
@@ -92,13 +101,13 @@ function Framework:packageTarget(ctx, artifacts)
         bp3d.files.symlink("Versions/Current/Resources", frameworkDir:join("Resources"))
         bp3d.files.symlink("Versions/Current/Modules", frameworkDir:join("Modules"))
     end
-    local includes = artifact.find(artifacts, "header")
-    if bp3d.util.table.count(includes) > 0 then
+    local includes = artifacts:find("header")
+    if includes:len() > 0 then
         print("Adding headers...")
         local headerPath = binDir:join("Headers")
         local umbrella = "/* Generated umbrella header to ensure Xcode can link the framework. */\n"
         umbrella = umbrella .. "/* Do not edit this file manually. */\n\n"
-        for _, include in pairs(includes) do
+        for _, include in includes:iter() do
             local name = include:name()
             if bp3d.util.string.startsWith(name, self.args.name) then
                 --stupidly broken lua language which requires a +2 where it does not make any sense...
@@ -121,12 +130,12 @@ function Framework:packageTarget(ctx, artifacts)
         }))
     end
     print("Generating Info.plist...")
-    local buildNumber = bp3d.util.utf8.replace(build.getOutput("sw_vers", { "-buildVersion" }), "\n", "")
+    local buildNumber = bp3d.util.utf8.replace(build.output("sw_vers", { "-buildVersion" }).stdout, "\n", "")
     local platforms
     if isDarwin then
-        platforms = "<string>MacOSX</string>"
+        platforms = { "MacOSX" }
     else
-        platforms = "<string>iPhoneOS</string>\n        <string>iPadOS</string>"
+        platforms = { "iPhoneOS", "iPadOS" }
     end
     bp3d.files.writeText(resDir:join("Info.plist"), build.render(templates.PLIST, {
         NAME = self.args.name,
@@ -27,9 +27,9 @@
 -- SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 local UnixDist = require "bp3d.package.unix-dist"
-local artifact = require "bp3d.util.artifact"
 local context = require "bp3d.util.context"
 local build = require "bp3d.util.build"
+local artifact = require "bp3d.util.artifact"
 local templates = require "bp3d.templates.library"
 
 local Library = Class(UnixDist)
@@ -38,20 +38,26 @@ Library.argTypes = {
     name = { type = "string" }
 }
 
+Library.requires = {
+    cargo = {},
+    install_name_tool = { targets = { "apple" } },
+    patchelf = { version = "0.9", targets = { "linux" } }
+}
+
 function Library:buildTarget(ctx)
     local files = baseBuild(ctx)
-    if bp3d.util.string.contains(ctx.target, "apple") then
+    if ctx.target:isApple() then
         print("Adding version information...")
         build.runCargo("rustc", ctx, {
             "--",
             "-Clink-arg=-compatibility_version" .. self.context.package.version,
             "-Clink-arg=-current_version" .. self.context.package.version
         })
-    elseif bp3d.util.string.contains(ctx.target, "msvc") then
+    elseif ctx.target:isMsvc() then
         build.runCargo("rustc", ctx, {
             "--",
             "--emit",
-            "link=target/" .. ctx.target .. "/" .. ctx.configuration .. "/" .. self.args.name .. ".dll"
+            "link=target/" .. ctx.target .. "/" .. ctx.configuration .. "/" .. self.args.name .. ctx.target:dylibSuffix()
         })
         --TODO: Inject product information RC file.
     end
@@ -59,29 +65,30 @@ function Library:buildTarget(ctx)
 end
 
 function Library:packageTarget(ctx, artifacts)
+    artifacts = artifact.toList(artifacts)
     UnixDist.packageTarget(self, ctx, artifacts)
-    local coreLibName = artifact.findFirst(artifacts, "lib::dynamic"):path():fullName()
+    local coreLibName = artifacts:findFirst("lib::dynamic"):path():fullName()
     local targetPath = context.getTargetPath(ctx)
     local distPath = targetPath:join("dist")
     local libName = ""
-    if not bp3d.util.string.contains(ctx.target, "msvc") then
+    if not ctx.target:isMsvc() then
         local originalLibPath = distPath:join("lib"):join(coreLibName)
-        libName = "lib" .. self.args.name .. "." .. originalLibPath:extension()
+        libName = ctx.target:libPrefix() .. self.args.name .. "." .. originalLibPath:extension()
         local newLibPath = distPath:join("lib"):join(libName)
         bp3d.files.rename(originalLibPath, newLibPath)
     else
-        libName = self.args.name .. ".dll"
-        local staticLibPath = targetPath:join(self.args.name .. ".lib")
-        bp3d.files.copyFile(staticLibPath, distPath:join("lib"):join(self.args.name .. ".lib"))
+        libName = self.args.name .. ctx.target:dylibSuffix()
+        local staticLibName = self.args.name .. ctx.target:staticlibSuffix()
+        bp3d.files.copyFile(targetPath:join(staticLibName), distPath:join("lib"):join(staticLibName))
         bp3d.files.copyFile(targetPath:join(libName), distPath:join("lib"):join(libName))
     end
-    if bp3d.util.string.contains(ctx.target, "apple") then
+    if ctx.target:isApple() then
         build.run("install_name_tool", {
             "-id",
             "@rpath/" .. libName,
             libName
         }, { workdir = distPath:join("lib") })
-    elseif bp3d.util.string.contains(ctx.target, "msvc") then
+    elseif ctx.target:isMsvc() then
         -- Nothing to do as cargo rustc has already done it...
     else
         build.run("patchelf", {
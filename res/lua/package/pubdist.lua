@@ -32,13 +32,20 @@ local Dist = require "bp3d.package.dist"
 
 local PubDist = Class(Dist)
 
+PubDist.requires = {
+    bpxp = {},
+    fpkg = {}
+}
+
 function PubDist:packageTarget(ctx, artifacts)
     Dist.packageTarget(self, ctx, artifacts)
     local name = self.context.package.name
     local version = self.context.package.version
     local targetPath = context.getTargetPath(ctx)
     print("Building BPX package...")
-    build.run("bpxp", { "-t", ctx.target, "-m", "Name=" .. name, "-m", "Version=" .. version, "-cf", "../dist.bpx", "." }, { workdir = targetPath:join("dist") })
+    build.run("bpxp", { "-t", ctx.target:name(), "-m", "Name=" .. name, "-m", "Version=" .. version, "-cf", "../dist.bpx", "." }, { workdir = targetPath:join("dist") })
+    print("Writing checksums...")
+    bp3d.build.hash.writeSums(targetPath:join("SHA256SUMS"), { targetPath:join("dist.bpx") })
     print("Publishing package...")
     build.run("fpkg", { "publish" })
 end
@@ -31,6 +31,15 @@ local args = require "bp3d.util.args"
 
 local Packager = Class()
 
+-- External tools required by this packager, checked by bp3d doctor.
+-- Each key is a tool name and each value is a table with the following optional fields:
+--   version: minimum version of the tool,
+--   args: arguments used to print the version of the tool (defaults to "--version"),
+--   targets: only require the tool for targets matching any of these patterns (a target name,
+--            arch, vendor, os, env or abi, or one of "unix", "windows" and "apple"),
+--   libraries: native libraries which must be installed in the bp3d library path.
+Packager.requires = {}
+
 function Packager:init(args1)
     if self.argTypes ~= nil then
         self.args = args.create(self.argTypes)
@@ -31,6 +31,10 @@ local args = require "bp3d.util.args"
 
 local Script = Class()
 
+-- External tools required by this script, see Packager.requires.
+Script.requires = {}
+
+---@param args1 table<string, string>
 function Script:init(args1)
     if self.argTypes ~= nil then
         self.args = args.create(self.argTypes)
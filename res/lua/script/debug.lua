@@ -31,12 +31,17 @@ local build = require "bp3d.util.build"
 
 local Debug = Class(Script)
 
+Debug.requires = {
+    codesign = { targets = { "apple" } },
+    file = {}
+}
+
 function Debug:run()
     local files = bp3d.files.list(self.context.path:join("target"):join(self.context.configuration))
     local bins = {}
     for _, v in ipairs(files) do
         if v.type == "file" then
-            local output = build.getOutput("file", { v.path })
+            local output = build.output("file", { v.path }).stdout
             if bp3d.util.utf8.contains(output, "Mach-O") and bp3d.util.utf8.contains(output, "executable") then
                 bins[v.name] = v.path
             end
@@ -31,6 +31,10 @@ local Script = require "bp3d.script"
 
 local ReleaseInfo = Class(Script)
 
+ReleaseInfo.requires = {
+    git = {}
+}
+
 function ReleaseInfo:canRelease(dirName, component)
     return component.public
 end
@@ -74,14 +78,8 @@ function ReleaseInfo:getParameters()
 end
 
 function ReleaseInfo:checkTagExists(tag)
-    local function eventHandler()
-        repeat
-            local ty, _ = coroutine.yield()
-        until ty == nil
-    end
-    local success, status = build.spawn("git", { "rev-parse", tag }, eventHandler, nil,
-        { ignoreFailure = true })
-    return success and status == 0
+    local result = build.output("git", { "rev-parse", tag })
+    return result.success and result.code == 0
 end
 
 function ReleaseInfo:getBlockVariables(params)
@@ -47,8 +47,8 @@ extern "C" {
 
 fn main() {
     let config = Installer {
-        name: c"{NAME}".as_ptr(),
-        version: c"{VERSION}".as_ptr(),
+        name: c"{{ NAME }}".as_ptr(),
+        version: c"{{ VERSION }}".as_ptr(),
         package_len: PACKAGE.len(),
         package: PACKAGE.as_ptr()
     };
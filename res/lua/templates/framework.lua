@@ -29,8 +29,8 @@
 local templates = {}
 
 templates.MODULE_MAP_TEMPLATE = [[
-framework module {NAME} {
-    umbrella header "{UMBRELLA}"
+framework module {{ NAME }} {
+    umbrella header "{{ UMBRELLA }}"
 
     export *
     module * {
@@ -40,32 +40,34 @@ framework module {NAME} {
 ]]
 
 templates.PLIST = [[
-<?xml version=\"1.0\" encoding=\"UTF-8\"?>
-<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">
-<plist version=\"1.0\">
+<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
 <dict>
     <key>BuildMachineOSBuild</key>
-    <string>{BUILD_NUMBER}</string>
+    <string>{{ BUILD_NUMBER | xml }}</string>
     <key>CFBundleDevelopmentRegion</key>
     <string>en</string>
     <key>CFBundleExecutable</key>
-    <string>{NAME}</string>
+    <string>{{ NAME | xml }}</string>
     <key>CFBundleIdentifier</key>
-    <string>{IDENTIFIER}</string>
+    <string>{{ IDENTIFIER | xml }}</string>
     <key>CFBundleInfoDictionaryVersion</key>
     <string>6.0</string>
     <key>CFBundleName</key>
-    <string>{NAME}</string>
+    <string>{{ NAME | xml }}</string>
     <key>CFBundlePackageType</key>
     <string>FMWK</string>
     <key>CFBundleShortVersionString</key>
-    <string>{VERSION}</string>
+    <string>{{ VERSION | xml }}</string>
     <key>CFBundleSupportedPlatforms</key>
     <array>
-        {PLATFORMS}
+        {% for platform in PLATFORMS %}
+        <string>{{ platform | xml }}</string>
+        {% endfor %}
     </array>
     <key>CFBundleVersion</key>
-    <string>{VERSION}</string>
+    <string>{{ VERSION | xml }}</string>
     <key>MinimumOSVersion</key>
     <string>11.0</string>
     <key>UIDeviceFamily</key>
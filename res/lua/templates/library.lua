@@ -29,11 +29,11 @@
 local templates = {}
 
 templates.CMAKE = [[
-add_library({NAME} INTERFACE IMPORTED)
+add_library({{ NAME }} INTERFACE IMPORTED)
 set_target_properties(
-    {NAME}
+    {{ NAME }}
     PROPERTIES
-        INTERFACE_LINK_LIBRARIES "${CMAKE_CURRENT_LIST_DIR}/../lib/{LIB_NAME}"
+        INTERFACE_LINK_LIBRARIES "${CMAKE_CURRENT_LIST_DIR}/../lib/{{ LIB_NAME | cmake }}"
         INTERFACE_INCLUDE_DIRECTORIES "${CMAKE_CURRENT_LIST_DIR}/include"
 )
 ]]
@@ -0,0 +1,161 @@
+-- Copyright (c) 2026, BlockProject 3D
+--
+-- All rights reserved.
+--
+-- Redistribution and use in source and binary forms, with or without modification,
+-- are permitted provided that the following conditions are met:
+--
+--     * Redistributions of source code must retain the above copyright notice,
+--       this list of conditions and the following disclaimer.
+--     * Redistributions in binary form must reproduce the above copyright notice,
+--       this list of conditions and the following disclaimer in the documentation
+--       and/or other materials provided with the distribution.
+--     * Neither the name of BlockProject 3D nor the names of its contributors
+--       may be used to endorse or promote products derived from this software
+--       without specific prior written permission.
+--
+-- THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+-- "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+-- LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+-- A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+-- CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+-- EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+-- PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+-- PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+-- LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+-- NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+-- SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+-- Helpers for *_test.lua files run by bp3d test-lua.
+--
+-- A test file declares its cases and must end with `return test.cases()`:
+--   local test = require "bp3d.test"
+--   test.case("runs cargo", function()
+--       test.mock({ exe = "cargo", stdout = "cargo 1.80.0" })
+--       ...
+--       test.called("cargo", { "build" })
+--   end)
+--   return test.cases()
+
+local test = {}
+local cases = {}
+
+-- Scripts the result of bp3d.build.command calls, see bp3d.build.test.mock.
+test.mock = bp3d.build.test.mock
+
+-- Returns the commands run so far.
+test.calls = bp3d.build.test.calls
+
+-- Returns the temporary directory the test can freely write to.
+test.root = bp3d.build.test.root
+
+test.case = function(name, fn)
+    table.insert(cases, { name = name, fn = fn })
+end
+
+test.cases = function()
+    return cases
+end
+
+-- Returns a build context rooted in the temporary directory of the test.
+test.context = function(args)
+    if args == nil then args = {} end
+    local target = bp3d.build.Target.parse(args.target or "x86_64-unknown-linux-gnu")
+    return {
+        path = test.root(),
+        target = target,
+        targets = args.targets or { target:name() },
+        configuration = args.configuration or "debug",
+        features = args.features,
+        package = args.package
+    }
+end
+
+local function fail(message, default)
+    -- Level 3 reports the line of the test calling the assertion.
+    error(message or default, 3)
+end
+
+local function pass()
+    bp3d.build.test.pass()
+end
+
+local function equals(a, b)
+    if type(a) ~= "table" or type(b) ~= "table" then
+        return a == b
+    end
+    for k, v in pairs(a) do
+        if not equals(v, b[k]) then return false end
+    end
+    for k, _ in pairs(b) do
+        if a[k] == nil then return false end
+    end
+    return true
+end
+
+local function matches(call, exe, args)
+    if call.exe ~= exe and string.sub(call.exe, -#exe - 1) ~= "/" .. exe then
+        return false
+    end
+    for i, v in ipairs(args or {}) do
+        if call.args[i] ~= v then return false end
+    end
+    return true
+end
+
+test.ok = function(value, message)
+    if not value then fail(message, "expected a truthy value") end
+    pass()
+end
+
+test.eq = function(actual, expected, message)
+    if not equals(actual, expected) then
+        fail(message, "expected " .. tostring(expected) .. ", got " .. tostring(actual))
+    end
+    pass()
+end
+
+test.contains = function(haystack, needle, message)
+    local found = false
+    if type(haystack) == "table" then
+        for _, v in pairs(haystack) do
+            if equals(v, needle) then found = true end
+        end
+    else
+        found = string.find(haystack, needle, 1, true) ~= nil
+    end
+    if not found then
+        fail(message, tostring(haystack) .. " does not contain " .. tostring(needle))
+    end
+    pass()
+end
+
+-- Checks a file exists, typically an output of the code under test.
+test.exists = function(path, message)
+    if not bp3d.files.exists(path) then
+        fail(message, "file " .. tostring(path) .. " does not exist")
+    end
+    pass()
+end
+
+-- Checks a command was run with exe and starting with the given args, returns the first match.
+test.called = function(exe, args, message)
+    for _, call in ipairs(test.calls()) do
+        if matches(call, exe, args) then
+            pass()
+            return call
+        end
+    end
+    fail(message, "command " .. exe .. " was not run")
+end
+
+test.notCalled = function(exe, args, message)
+    for _, call in ipairs(test.calls()) do
+        if matches(call, exe, args) then
+            fail(message, "command " .. exe .. " was run")
+        end
+    end
+    pass()
+end
+
+return test
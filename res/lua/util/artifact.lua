@@ -28,55 +28,45 @@
 
 local artifact = {}
 
-artifact.findFirst = function(artifacts, type)
+-- Returns a bp3d.build.List containing the given artifacts, packageTarget receives a plain table
+-- of artifacts which can be converted using this function.
+artifact.toList = function(artifacts)
+    if type(artifacts) == "userdata" then
+        return artifacts
+    end
+    local list = bp3d.build.List.new()
     for _, v in pairs(artifacts) do
-        if v:ty() == type then
-            return v
-        end
+        list:add(v)
     end
-    return nil
+    return list
 end
 
+-- Deprecated: use List:findFirst.
+artifact.findFirst = function(artifacts, type)
+    return artifact.toList(artifacts):findFirst(type)
+end
+
+-- Deprecated: use List:find.
 artifact.find = function(artifacts, type)
-    local res = {}
-    for k, v in pairs(artifacts) do
-        if v:ty() == type then
-            res[k] = v
-        end
-    end
-    return res
+    return artifact.toList(artifacts):find(type):toTable()
 end
 
+-- Deprecated: use List:get.
 artifact.contains = function(artifacts, name)
-    for _, v in pairs(artifacts) do
-        if v:name() == name then return true end
-    end
-    return false
+    return artifact.toList(artifacts):get(name) ~= nil
 end
 
-local LocalArtifact = {}
-LocalArtifact.path = function(self) return self._path end
-LocalArtifact.name = function(self) return self._name end
-LocalArtifact.debugInfo = function(self) return self._debugInfo end
-LocalArtifact.exports = function(self) return self._exports end
-LocalArtifact.__index = LocalArtifact
-
 artifact.findDynamicLibraries = function(artifacts, targetPath)
+    artifacts = artifact.toList(artifacts)
     local res = {}
-    local libs = artifact.find(artifacts, "lib::dynamic")
-    for _, v in pairs(libs) do
+    for _, v in artifacts:find("lib::dynamic"):iter() do
         table.insert(res, v)
     end
     local files = bp3d.files.list(targetPath)
     for _, v in ipairs(files) do
         local ext = v.path:extension()
-        if v.type == "file" and (ext == "dylib" or ext == "dll" or ext == "so") and not artifact.contains(artifacts, v.name) then
-            local vv = {
-                _name = v.name,
-                _path = v.path
-            }
-            setmetatable(vv, LocalArtifact)
-            table.insert(res, vv)
+        if v.type == "file" and (ext == "dylib" or ext == "dll" or ext == "so") and artifacts:get(v.name) == nil then
+            table.insert(res, bp3d.build.Artifact.lib(v.path, v.name, "dynamic"))
         end
     end
     return res
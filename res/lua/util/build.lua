@@ -51,26 +51,67 @@ build.spawn = function(exe, args, eventThread, userdata, config)
     config.args = args
     local co = coroutine.create(eventThread)
     coroutine.resume(co, userdata)
-    local success, code = bp3d.build.command.spawn(config, co)
+    local success, code, timedOut = bp3d.build.command.spawn(config, co)
     if config.ignoreFailure then
-        return success, code
+        return success, code, timedOut
     end
+    assert(not timedOut, "command timed out")
     assert(success and code == 0, "command failed")
-    return success, code
+    return success, code, timedOut
 end
 
-build.getOutput = function(exe, args, config)
+-- Starts a process without waiting for it, see bp3d.build.command.Process.
+build.start = function(exe, args, config)
+    if config == nil then config = {} end
+    config.exe = exe
+    config.args = args
+    return bp3d.build.command.start(config)
+end
+
+-- Waits for all processes started with build.start.
+-- Processes still running timeout seconds after the call are killed.
+-- Returns true when all processes succeeded, followed by a list of { success, code } tables.
+build.waitAll = function(processes, timeout)
+    local results = {}
+    local allSucceeded = true
+    local clock = bp3d.build.command.Process.clock
+    local start = clock()
+    for i, process in ipairs(processes) do
+        local remaining = nil
+        if timeout ~= nil then
+            remaining = math.max(timeout - (clock() - start), 0)
+        end
+        local success, code = process:wait(remaining)
+        results[i] = { success = success, code = code }
+        allSucceeded = allSucceeded and success and code == 0
+    end
+    return allSucceeded, results
+end
+
+-- Runs a command and returns a table of stdout, stderr, success, code and timedOut.
+build.output = function(exe, args, config)
     if config == nil then config = {} end
     config.exe = exe
     config.args = args
     return bp3d.build.command.output(config)
 end
 
+-- Deprecated: use build.output, this returns only the stdout of the command.
+build.getOutput = function(exe, args, config)
+    return build.output(exe, args, config).stdout
+end
+
+-- Renders a template string, see bp3d.build.template for the syntax.
+-- Templates without any {{ }}, {% %} or {# #} tag use the legacy syntax where {KEY} is replaced
+-- by the value of args.KEY.
 build.render = function(template, args)
-    for k, v in pairs(args) do
-        template = bp3d.util.utf8.replace(template, "{" .. k .. "}", v)
+    if not string.find(template, "{[{%%#]") then
+        for k, v in pairs(args) do
+            template = bp3d.util.utf8.replace(template, "{" .. k .. "}", tostring(v))
+        end
+        return template
     end
-    return template
+    return bp3d.build.template.render(template, args)
 end
 
 build.getCargo = function(cmd, ctx, args2)
@@ -82,7 +123,7 @@ build.getCargo = function(cmd, ctx, args2)
     }
     if ctx.target then
         table.insert(args, "--target")
-        table.insert(args, ctx.target)
+        table.insert(args, ctx.target:name())
     end
     if ctx.configuration == "release" then
         table.insert(args, "--release")
@@ -123,7 +164,7 @@ build.runBP3D = function(subPath, cmd, ctx, args)
             bp3d.util.table.concat(args1, { "-t", v })
         end
     elseif ctx.target then
-        bp3d.util.table.concat(args1, { "-t", ctx.target })
+        bp3d.util.table.concat(args1, { "-t", ctx.target:name() })
     end
     if ctx.features then
         for _, v in ipairs(ctx.features) do
@@ -29,7 +29,7 @@
 local context = {}
 
 context.getTargetPath = function(ctx, target)
-    if target == nil then target = ctx.target end
+    if target == nil then target = ctx.target:name() end
     return ctx.path:join("target"):join(target):join(ctx.configuration)
 end
 